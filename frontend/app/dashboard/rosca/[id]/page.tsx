@@ -19,7 +19,7 @@ import { useDisclosure } from "@heroui/modal"; // For modal state
 // --- Data & Types ---
 import { fetchRoscaDetails } from '@/app/lib/data-fetchers';
 import { Rosca, Round } from '@/app/lib/types';
-import { useSubmitAddToSecurityDeposit } from '@/app/lib/hooks/useSubmitExtrinsic';
+import { useSubmitAddToSecurityDeposit, PAYMENT_ASSET_IDS } from '@/app/lib/hooks/useSubmitExtrinsic';
 import { useWallet } from '@/app/lib/wallet/WalletProvider';
 import { addToast } from '@heroui/toast';
 
@@ -127,7 +127,7 @@ export default function RoscaDetailsPage() {
         if (rosca) {
             // We wrap the specific submit function call within the generic handler
             // to benefit from the centralized loading/error/refresh logic
-             await handleAction('addDeposit', () => addToSecurityDeposit(rosca.roscaId, amount));
+             await handleAction('addDeposit', () => addToSecurityDeposit(rosca.roscaId, amount, PAYMENT_ASSET_IDS[rosca.paymentAsset]));
              onDepositModalClose(); // Close modal only if action was attempted (handleAction handles success/error alerts)
         }
      };
@@ -7,6 +7,12 @@ import type { ApiPromise, SubmittableResult } from "@polkadot/api";
 import type { Signer } from "@polkadot/api/types";
 import { NodeNextRequest } from "next/dist/server/base-http/node";
 
+/** Asset ids of the ROSCA payment assets, usable to pay transaction fees */
+export const PAYMENT_ASSET_IDS: Record<string, number> = {
+    USDT: 1984,
+    USDC: 1337,
+};
+
 interface TxResult {
     success: boolean;
     blockHash?: string;
@@ -16,13 +22,15 @@ interface TxResult {
 /** 
  * Performs a Polkadot extrinsic call and awaits the `Finalized` status. 
  * Dynamically imports extension-dapp APIs so it never runs on the server.
+ * When `feeAssetId` is given, the transaction fee is paid in that asset instead of the native token.
  */
 async function executeTx(
     api: ApiPromise,
     account: string,
     txCreator: () => any,
     section: string,
-    method: string
+    method: string,
+    feeAssetId?: number
 ): Promise<TxResult> {
     try {
         // 1. Enable all extensions (loads window.injectedWeb3)
@@ -40,7 +48,7 @@ async function executeTx(
         return new Promise((resolve, reject) => {
             tx.signAndSend(
                 account,
-                { signer: injector.signer as Signer, nonce: -1 },
+                { signer: injector.signer as Signer, nonce: -1, assetId: feeAssetId },
                 (result: SubmittableResult) => {
                     if (result.status.isFinalized) {
                         const blockHash = result.status.asFinalized.toString();
@@ -94,7 +102,7 @@ export function useSubmitJoinRosca() {
     const api = useApi();
     const { currentAccount } = useWallet();
 
-    return async (roscaId: number): Promise<TxResult> => {
+    return async (roscaId: number, feeAssetId?: number): Promise<TxResult> => {
         if (!currentAccount) {
             return { success: false, error: "Wallet not connected" };
         }
@@ -103,7 +111,8 @@ export function useSubmitJoinRosca() {
             currentAccount.address,
             () => api.tx.rosca.joinRosca(roscaId, null),
             "rosca",
-            "JoinedRosca"
+            "JoinedRosca",
+            feeAssetId
         );
     };
 }
@@ -151,7 +160,7 @@ export function useSubmitContributeToRosca() {
     const api = useApi();
     const { currentAccount } = useWallet();
 
-    return async (roscaId: number): Promise<TxResult> => {
+    return async (roscaId: number, feeAssetId?: number): Promise<TxResult> => {
         if (!currentAccount) {
             return { success: false, error: "Wallet not connected" };
         }
@@ -160,7 +169,8 @@ export function useSubmitContributeToRosca() {
             currentAccount.address,
             () => api.tx.rosca.contributeToRosca(roscaId),
             "rosca",
-            "ContributionMade"
+            "ContributionMade",
+            feeAssetId
         );
     };
 }
@@ -170,7 +180,7 @@ export function useSubmitAddToSecurityDeposit() {
     const api = useApi();
     const { currentAccount } = useWallet();
 
-    return async (roscaId: number, amount: number): Promise<TxResult> => {
+    return async (roscaId: number, amount: number, feeAssetId?: number): Promise<TxResult> => {
         if (!currentAccount) {
             return { success: false, error: "Wallet not connected" };
         }
@@ -179,7 +189,8 @@ export function useSubmitAddToSecurityDeposit() {
             currentAccount.address,
             () => api.tx.rosca.addToSecurityDeposit(roscaId, amount),
             "rosca",
-            "SecurityDepositContribution"
+            "SecurityDepositContribution",
+            feeAssetId
         );
     };
 }
@@ -0,0 +1,250 @@
+//! One function per `pallet_rosca` call. Each signs the call with `signer`, waits for the block
+//! including it to be finalized and returns the Rosca events the call emitted. A failed call is
+//! returned as [`subxt::Error::Runtime`] with the pallet error decoded. Calls that need root are
+//! made through `pallet_sudo` and take the sudo key as their signer.
+
+use frame_support::{dispatch::GetCallName, BoundedVec};
+use generic_runtime_template::RuntimeCall;
+use pallet_rosca::{Call, InsuranceConfig, ProposalAction, ProposalId, VoteThreshold};
+use parity_scale_codec::{Decode, Encode};
+use sp_core::Get;
+use sp_runtime::Permill;
+use subxt::{blocks::ExtrinsicEvents, tx::TxPayload, Metadata, PolkadotConfig};
+use xcm::VersionedLocation;
+
+use crate::{AccountId, Keypair, PaymentAssets, Result, RoscaClient, RoscaEvent, RoscaId, Runtime};
+
+/// A runtime call, encoded with the runtime's own types once the node's metadata agrees on
+/// where it lives.
+struct RuntimePayload(RuntimeCall);
+
+impl RuntimePayload {
+    fn check(&self, metadata: &Metadata, encoded: &[u8]) -> Result<()> {
+        let (pallet_name, call_name) = match &self.0 {
+            RuntimeCall::Rosca(call) => ("Rosca", call.get_call_name()),
+            RuntimeCall::Sudo(call) => ("Sudo", call.get_call_name()),
+            _ => return Ok(()),
+        };
+        let pallet = metadata.pallet_by_name_err(pallet_name)?;
+        let index = pallet.call_variant_by_name(call_name).map(|variant| variant.index);
+        if encoded[..2] != [pallet.index(), index.unwrap_or(u8::MAX)] {
+            return Err(subxt::Error::Other(format!(
+                "{pallet_name}::{call_name} is encoded differently by the node's runtime"
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl TxPayload for RuntimePayload {
+    fn encode_call_data_to(&self, metadata: &Metadata, out: &mut Vec<u8>) -> Result<()> {
+        let encoded = self.0.encode();
+        self.check(metadata, &encoded)?;
+        out.extend(encoded);
+        Ok(())
+    }
+}
+
+fn bounded<T, S: Get<u32>>(items: Vec<T>, what: &str) -> Result<BoundedVec<T, S>> {
+    BoundedVec::try_from(items)
+        .map_err(|_| subxt::Error::Other(format!("{what} is longer than {}", S::get())))
+}
+
+impl RoscaClient {
+    /// Signs and submits `call`, returning the Rosca events it emitted.
+    pub async fn submit(&self, signer: &Keypair, call: RuntimeCall) -> Result<Vec<RoscaEvent>> {
+        let events = self.submit_and_watch(signer, call).await?;
+        events.iter().filter_map(|event| crate::events::decode(&event?).transpose()).collect()
+    }
+
+    async fn submit_and_watch(
+        &self,
+        signer: &Keypair,
+        call: RuntimeCall,
+    ) -> Result<ExtrinsicEvents<PolkadotConfig>> {
+        self.api
+            .tx()
+            .sign_and_submit_then_watch_default(&RuntimePayload(call), signer)
+            .await?
+            .wait_for_finalized_success()
+            .await
+    }
+
+    async fn rosca_call(&self, signer: &Keypair, call: Call<Runtime>) -> Result<Vec<RoscaEvent>> {
+        self.submit(signer, RuntimeCall::Rosca(call)).await
+    }
+
+    /// Submits `call` as root through `pallet_sudo`. `signer` must be the sudo key. Sudo
+    /// succeeds even when the call it dispatches fails, so the call's own result is checked.
+    async fn sudo_rosca_call(
+        &self,
+        signer: &Keypair,
+        call: Call<Runtime>,
+    ) -> Result<Vec<RoscaEvent>> {
+        let call = Box::new(RuntimeCall::Rosca(call));
+        let sudo = RuntimeCall::Sudo(pallet_sudo::Call::sudo { call });
+        let events = self.submit_and_watch(signer, sudo).await?;
+        let mut rosca_events = Vec::new();
+        for event in events.iter() {
+            let event = event?;
+            if event.pallet_name() == "Sudo" {
+                let bytes = [&[event.variant_index()][..], event.field_bytes()].concat();
+                let sudo_event = pallet_sudo::Event::<Runtime>::decode(&mut &bytes[..])?;
+                if let pallet_sudo::Event::Sudid { sudo_result: Err(error) } = sudo_event {
+                    return Err(subxt::Error::Other(format!("sudo call failed: {error:?}")));
+                }
+            } else if let Some(rosca_event) = crate::events::decode(&event)? {
+                rosca_events.push(rosca_event);
+            }
+        }
+        Ok(rosca_events)
+    }
+
+    /// Creates a Rosca with `signer` as its creator, inviting `invited`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_rosca(
+        &self,
+        signer: &Keypair,
+        random_order: bool,
+        invited: Vec<AccountId>,
+        minimum_participant_threshold: u32,
+        contribution_amount: u32,
+        payment_asset: PaymentAssets,
+        contribution_frequency: u64,
+        start_by_timestamp: u64,
+        position: Option<u32>,
+        name: Vec<u8>,
+    ) -> Result<Vec<RoscaEvent>> {
+        let call = Call::create_rosca {
+            random_order,
+            invited_pre_verified_participants: bounded(invited, "invited participants")?,
+            minimum_participant_threshold,
+            contribution_amount,
+            payment_asset,
+            contribution_frequency,
+            start_by_timestamp,
+            position,
+            name: bounded(name, "name")?,
+        };
+        self.rosca_call(signer, call).await
+    }
+
+    /// Sets whether new Roscas may be created in `asset`.
+    pub async fn set_asset_allowed(
+        &self,
+        sudo: &Keypair,
+        asset: PaymentAssets,
+        allowed: bool,
+    ) -> Result<Vec<RoscaEvent>> {
+        self.sudo_rosca_call(sudo, Call::set_asset_allowed { asset, allowed }).await
+    }
+
+    /// Sets or clears the insurance config of `asset`.
+    pub async fn set_insurance_config(
+        &self,
+        sudo: &Keypair,
+        asset: PaymentAssets,
+        config: Option<InsuranceConfig>,
+    ) -> Result<Vec<RoscaEvent>> {
+        self.sudo_rosca_call(sudo, Call::set_insurance_config { asset, config }).await
+    }
+
+    /// Pays the current contribution of `participant`, who has autopay enabled. The scheduler
+    /// normally does this.
+    pub async fn autopay_contribution(
+        &self,
+        sudo: &Keypair,
+        rosca_id: RoscaId,
+        participant: AccountId,
+    ) -> Result<Vec<RoscaEvent>> {
+        self.sudo_rosca_call(sudo, Call::autopay_contribution { rosca_id, participant }).await
+    }
+
+    /// Sets or clears the platform fee, of `asset` if given or the default otherwise.
+    pub async fn set_platform_fee(
+        &self,
+        sudo: &Keypair,
+        asset: Option<PaymentAssets>,
+        fee: Option<Permill>,
+    ) -> Result<Vec<RoscaEvent>> {
+        self.sudo_rosca_call(sudo, Call::set_platform_fee { asset, fee }).await
+    }
+}
+
+/// Defines a client function per signed call, taking the call's arguments in order.
+macro_rules! signed_calls {
+    ($($(#[$doc:meta])* fn $call:ident($($arg:ident: $ty:ty),* $(,)?);)*) => {
+        impl RoscaClient {
+            $(
+                $(#[$doc])*
+                pub async fn $call(
+                    &self,
+                    signer: &Keypair,
+                    $($arg: $ty),*
+                ) -> Result<Vec<RoscaEvent>> {
+                    self.rosca_call(signer, Call::$call { $($arg),* }).await
+                }
+            )*
+        }
+    };
+}
+
+signed_calls! {
+    /// Joins a Rosca `signer` was invited to, optionally asking for a payout position.
+    fn join_rosca(rosca_id: RoscaId, position: Option<u32>);
+    /// Leaves a pending Rosca.
+    fn leave_rosca(rosca_id: RoscaId);
+    /// Starts a pending Rosca that enough participants have joined.
+    fn start_rosca(rosca_id: RoscaId);
+    /// Pays `signer`'s contribution to the current round.
+    fn contribute_to_rosca(rosca_id: RoscaId);
+    /// Ends an active Rosca whose final cutoff has passed, settling any rounds left.
+    fn manually_end_rosca(rosca_id: RoscaId);
+    /// Claims back `signer`'s security deposit once the Rosca's final cutoff has passed.
+    fn claim_security_deposit(rosca_id: RoscaId, asset: PaymentAssets);
+    /// Adds `amount` to `signer`'s security deposit.
+    fn add_to_security_deposit(rosca_id: RoscaId, amount: u32);
+    /// Pays the contribution of `participant` to the current round.
+    fn contribute_for(rosca_id: RoscaId, participant: AccountId);
+    /// Adds `amount` to the security deposit of `participant`.
+    fn add_to_security_deposit_for(rosca_id: RoscaId, participant: AccountId, amount: u32);
+    /// Proposes swapping payout positions with `counterparty`.
+    fn propose_swap(rosca_id: RoscaId, counterparty: AccountId);
+    /// Accepts a payout position swap proposed by `proposer`.
+    fn accept_swap(rosca_id: RoscaId, proposer: AccountId);
+    /// Nominates `substitute` to take over `signer`'s place.
+    fn nominate_substitute(rosca_id: RoscaId, substitute: AccountId);
+    /// Takes over the place of `departing`, who nominated `signer`.
+    fn accept_substitution(rosca_id: RoscaId, departing: AccountId);
+    /// Proposes a governance action to the other participants.
+    fn propose(rosca_id: RoscaId, action: ProposalAction<Runtime>, threshold: VoteThreshold);
+    /// Votes on an open proposal. It's enacted as soon as its threshold is met.
+    fn vote(rosca_id: RoscaId, proposal_id: ProposalId, aye: bool);
+    /// Removes a proposal whose voting period ended without reaching its threshold.
+    fn close_proposal(rosca_id: RoscaId, proposal_id: ProposalId);
+    /// Sets or clears the number of defaults after which a participant is ejected. Only the
+    /// creator may, before the Rosca starts.
+    fn set_max_defaults(rosca_id: RoscaId, max_defaults: Option<u32>);
+    /// Sets or clears the guarantor of a pending Rosca, returning a replaced guarantor's
+    /// guarantee. Only its creator may.
+    fn set_guarantor(rosca_id: RoscaId, guarantor: Option<AccountId>);
+    /// Pre-funds `amount` of guarantee as the Rosca's guarantor.
+    fn fund_guarantee(rosca_id: RoscaId, amount: u32);
+    /// Repays `amount` of what `signer` owes the guarantor.
+    fn repay_guarantor(rosca_id: RoscaId, amount: u32);
+    /// Claims back the undrawn guarantee of a completed or not yet started Rosca as its
+    /// guarantor.
+    fn claim_guarantee(rosca_id: RoscaId);
+    /// Sets or clears the organiser fee of a pending Rosca. Only its creator may.
+    fn set_organiser_fee(rosca_id: RoscaId, fee: Option<Permill>);
+    /// Adds `amount` of native tokens to the Rosca's fee sponsorship.
+    fn fund_sponsorship(rosca_id: RoscaId, amount: u128);
+    /// Withdraws `amount` from the Rosca's fee sponsorship. Only its creator may.
+    fn withdraw_sponsorship(rosca_id: RoscaId, amount: u128);
+    /// Sets or clears where `signer`'s payouts are sent.
+    fn set_payout_destination(rosca_id: RoscaId, destination: Option<Box<VersionedLocation>>);
+    /// Pays `signer`'s contributions automatically from now on.
+    fn enable_autopay(rosca_id: RoscaId);
+    /// Stops paying `signer`'s contributions automatically.
+    fn disable_autopay(rosca_id: RoscaId);
+}
@@ -0,0 +1,436 @@
+//! Decoding and streaming of `pallet_rosca` events.
+//!
+//! The pallet's event type is generic over the runtime and carries a variant that can't be
+//! serialized, so events decode into [`RoscaEvent`] instead. It has the same variants and fields,
+//! with the runtime's account and moment types, and serializes to JSON as an object with an
+//! `event` name and camelCase fields. A test checks it against the pallet's event type.
+
+use core::fmt;
+
+use futures::{Stream, StreamExt};
+use pallet_rosca::{Balance, ProposalId};
+use parity_scale_codec::Decode;
+use scale_info::TypeInfo;
+use serde::{Serialize, Serializer};
+use sp_runtime::{DispatchError, Permill};
+use subxt::{events::EventDetails, PolkadotConfig};
+use xcm::VersionedLocation;
+
+use crate::{AccountId, Moment, PaymentAssets, Result, RoscaClient, RoscaId};
+
+/// A balance. JSON numbers can't hold every balance exactly, so it serializes as a string.
+#[derive(Decode, TypeInfo, Clone, Copy, PartialEq, Eq)]
+pub struct Amount(pub Balance);
+
+impl fmt::Debug for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+/// A Rosca's name, which serializes as text.
+fn name<S: Serializer>(name: &[u8], serializer: S) -> core::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(&String::from_utf8_lossy(name))
+}
+
+/// An XCM location, which serializes as its debug string.
+fn location<S: Serializer>(
+    location: &VersionedLocation,
+    serializer: S,
+) -> core::result::Result<S::Ok, S::Error> {
+    serializer.collect_str(&format_args!("{location:?}"))
+}
+
+fn optional_location<S: Serializer>(
+    location: &Option<VersionedLocation>,
+    serializer: S,
+) -> core::result::Result<S::Ok, S::Error> {
+    match location {
+        Some(location) => self::location(location, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// One payout round of a Rosca, as its events report it.
+#[derive(Decode, TypeInfo, Serialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Round {
+    pub round_number: u32,
+    pub payment_cutoff: Moment,
+    pub expected_contributors: Vec<AccountId>,
+    pub recipient: AccountId,
+}
+
+/// An action a Rosca's members can vote on.
+#[derive(Decode, TypeInfo, Serialize, Clone, PartialEq, Eq, Debug)]
+#[serde(tag = "type", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum Action {
+    ExtendDeadline { extension: Moment },
+    ForgiveDefault { participant: AccountId },
+    EndEarly,
+    SubstituteMember { departing: AccountId, substitute: AccountId },
+    Pause,
+    Resume,
+    EjectMember { participant: AccountId },
+}
+
+/// Share of a Rosca's participants that must vote aye for a proposal to pass.
+#[derive(Decode, TypeInfo, Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum Threshold {
+    Majority,
+    Supermajority,
+    Unanimous,
+}
+
+/// Insurance settings of a payment asset.
+#[derive(Decode, TypeInfo, Serialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InsuranceTerms {
+    pub premium: Permill,
+    pub max_claim: Amount,
+}
+
+/// An event of `pallet_rosca`. See the pallet's `Event` for what each one means.
+#[derive(Decode, TypeInfo, Serialize, Clone, PartialEq, Eq, Debug)]
+#[serde(tag = "event", rename_all_fields = "camelCase")]
+pub enum RoscaEvent {
+    RoscaCreated {
+        rosca_id: RoscaId,
+        contribution_amount: Amount,
+        payment_asset: PaymentAssets,
+        contribution_frequency: Moment,
+        random_order: bool,
+        #[serde(serialize_with = "name")]
+        name: Vec<u8>,
+        number_of_participants: u32,
+        minimum_participant_threshold: u32,
+        start_by_timestamp: Moment,
+        eligible_participants: Vec<AccountId>,
+        creator: AccountId,
+    },
+    ParticipantDefaulted {
+        rosca_id: RoscaId,
+        unpaid_recipient: AccountId,
+        defaulter: AccountId,
+    },
+    ContributionMade {
+        rosca_id: RoscaId,
+        contributor: AccountId,
+        recipient: AccountId,
+        amount: Amount,
+        payer: AccountId,
+        platform_fee: Amount,
+        organiser_fee: Amount,
+    },
+    DepositDeducted {
+        rosca_id: RoscaId,
+        contributor: AccountId,
+        recipient: AccountId,
+        amount: Amount,
+        sufficient: bool,
+        platform_fee: Amount,
+        organiser_fee: Amount,
+    },
+    JoinedRosca {
+        rosca_id: RoscaId,
+        contributor: AccountId,
+    },
+    LeftRosca {
+        rosca_id: RoscaId,
+        contributor: AccountId,
+    },
+    RoscaStarted {
+        rosca_id: RoscaId,
+        started_by: AccountId,
+        rounds: Vec<Round>,
+        first_eligible_claimant: AccountId,
+        first_payment_cutoff: Moment,
+    },
+    RoscaComplete {
+        rosca_id: RoscaId,
+    },
+    SecurityDepositContribution {
+        rosca_id: RoscaId,
+        depositor: AccountId,
+        amount: Amount,
+        payer: AccountId,
+    },
+    SecurityDepositClaimed {
+        rosca_id: RoscaId,
+        depositor: AccountId,
+        amount: Amount,
+    },
+    RoscaManuallyEnded {
+        rosca_id: RoscaId,
+    },
+    NewRoundStarted {
+        rosca_id: RoscaId,
+        new_eligible_recipient: AccountId,
+        payment_cutoff: Moment,
+    },
+    SwapProposed {
+        rosca_id: RoscaId,
+        proposer: AccountId,
+        counterparty: AccountId,
+    },
+    PositionsSwapped {
+        rosca_id: RoscaId,
+        proposer: AccountId,
+        counterparty: AccountId,
+        rounds: Vec<Round>,
+    },
+    SubstituteNominated {
+        rosca_id: RoscaId,
+        departing: AccountId,
+        substitute: AccountId,
+    },
+    ParticipantSubstituted {
+        rosca_id: RoscaId,
+        departing: AccountId,
+        substitute: AccountId,
+        already_received: bool,
+        outstanding_contributions: u32,
+        inherited_debt: Amount,
+        deposit_transferred: Amount,
+        debt_deposit: Amount,
+    },
+    ProposalCreated {
+        rosca_id: RoscaId,
+        proposal_id: ProposalId,
+        proposer: AccountId,
+        action: Action,
+        threshold: Threshold,
+        voting_ends: Moment,
+    },
+    Voted {
+        rosca_id: RoscaId,
+        proposal_id: ProposalId,
+        voter: AccountId,
+        aye: bool,
+    },
+    ProposalApproved {
+        rosca_id: RoscaId,
+        proposal_id: ProposalId,
+    },
+    ProposalExecutionFailed {
+        rosca_id: RoscaId,
+        proposal_id: ProposalId,
+        error: DispatchError,
+    },
+    ProposalRejected {
+        rosca_id: RoscaId,
+        proposal_id: ProposalId,
+    },
+    ProposalExpired {
+        rosca_id: RoscaId,
+        proposal_id: ProposalId,
+    },
+    DeadlineExtended {
+        rosca_id: RoscaId,
+        next_payment_cutoff: Moment,
+        final_payment_cutoff: Moment,
+    },
+    DefaultForgiven {
+        rosca_id: RoscaId,
+        participant: AccountId,
+        remaining_defaults: u32,
+    },
+    RoscaPaused {
+        rosca_id: RoscaId,
+        paused_at: Moment,
+    },
+    RoscaResumed {
+        rosca_id: RoscaId,
+        paused_duration: Moment,
+        rounds: Vec<Round>,
+    },
+    MaxDefaultsSet {
+        rosca_id: RoscaId,
+        max_defaults: Option<u32>,
+    },
+    ParticipantEjected {
+        rosca_id: RoscaId,
+        participant: AccountId,
+        defaults: u32,
+        forfeited_deposit: Amount,
+        forfeited_to: Vec<(AccountId, Amount)>,
+        slot_dropped: bool,
+        final_payment_cutoff: Moment,
+        rounds: Vec<Round>,
+    },
+    InsuranceConfigSet {
+        asset: PaymentAssets,
+        config: Option<InsuranceTerms>,
+    },
+    InsurancePremiumPaid {
+        rosca_id: RoscaId,
+        payer: AccountId,
+        asset: PaymentAssets,
+        amount: Amount,
+    },
+    InsuranceClaimPaid {
+        rosca_id: RoscaId,
+        defaulter: AccountId,
+        recipient: AccountId,
+        asset: PaymentAssets,
+        shortfall: Amount,
+        amount: Amount,
+    },
+    GuarantorSet {
+        rosca_id: RoscaId,
+        guarantor: Option<AccountId>,
+    },
+    GuaranteeFunded {
+        rosca_id: RoscaId,
+        guarantor: AccountId,
+        amount: Amount,
+    },
+    GuaranteeDrawn {
+        rosca_id: RoscaId,
+        guarantor: AccountId,
+        defaulter: AccountId,
+        recipient: AccountId,
+        amount: Amount,
+    },
+    GuarantorRepaid {
+        rosca_id: RoscaId,
+        guarantor: AccountId,
+        debtor: AccountId,
+        amount: Amount,
+        remaining_debt: Amount,
+    },
+    GuaranteeClaimed {
+        rosca_id: RoscaId,
+        guarantor: AccountId,
+        amount: Amount,
+    },
+    PlatformFeeSet {
+        asset: Option<PaymentAssets>,
+        fee: Option<Permill>,
+    },
+    OrganiserFeeSet {
+        rosca_id: RoscaId,
+        fee: Option<Permill>,
+    },
+    SponsorshipFunded {
+        rosca_id: RoscaId,
+        amount: Amount,
+        balance: Amount,
+    },
+    SponsorshipWithdrawn {
+        rosca_id: RoscaId,
+        amount: Amount,
+        balance: Amount,
+    },
+    FeeSponsored {
+        rosca_id: RoscaId,
+        participant: AccountId,
+        fee: Amount,
+    },
+    PayoutDestinationSet {
+        rosca_id: RoscaId,
+        participant: AccountId,
+        #[serde(serialize_with = "optional_location")]
+        destination: Option<VersionedLocation>,
+    },
+    RemotePayoutSent {
+        rosca_id: RoscaId,
+        recipient: AccountId,
+        #[serde(serialize_with = "location")]
+        destination: VersionedLocation,
+        amount: Amount,
+    },
+    RemotePayoutFailed {
+        rosca_id: RoscaId,
+        recipient: AccountId,
+        amount: Amount,
+        error: DispatchError,
+    },
+    AutopayEnabled {
+        rosca_id: RoscaId,
+        participant: AccountId,
+    },
+    AutopayDisabled {
+        rosca_id: RoscaId,
+        participant: AccountId,
+    },
+    AutopayFailed {
+        rosca_id: RoscaId,
+        participant: AccountId,
+        error: DispatchError,
+    },
+    AssetAllowedSet {
+        asset: PaymentAssets,
+        allowed: bool,
+    },
+    EarlyEndRefund {
+        rosca_id: RoscaId,
+        member: AccountId,
+        amount: Amount,
+    },
+}
+
+/// Decodes `event` if it's a Rosca event.
+pub(crate) fn decode(event: &EventDetails<PolkadotConfig>) -> Result<Option<RoscaEvent>> {
+    if event.pallet_name() != "Rosca" {
+        return Ok(None);
+    }
+    let bytes = [&[event.variant_index()][..], event.field_bytes()].concat();
+    Ok(Some(RoscaEvent::decode(&mut &bytes[..])?))
+}
+
+impl RoscaClient {
+    /// The Rosca events of each finalized block from now on, with the block's number. Blocks
+    /// without Rosca events are skipped.
+    pub async fn subscribe_events(
+        &self,
+    ) -> Result<impl Stream<Item = Result<(u32, Vec<RoscaEvent>)>>> {
+        let blocks = self.api.blocks().subscribe_finalized().await?;
+        let events = blocks.then(|block| async move {
+            let block = block?;
+            let events = block.events().await?;
+            let rosca_events = events
+                .iter()
+                .filter_map(|event| event.and_then(|event| decode(&event)).transpose())
+                .collect::<Result<Vec<_>>>()?;
+            Ok((block.number(), rosca_events))
+        });
+        Ok(events.filter(|events| {
+            futures::future::ready(!matches!(events, Ok((_, events)) if events.is_empty()))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use scale_info::TypeDef;
+
+    use super::*;
+    use crate::Runtime;
+
+    /// The codec index, name and field names of each variant of an enum.
+    fn variants<T: TypeInfo>() -> Vec<(u8, &'static str, Vec<Option<&'static str>>)> {
+        let TypeDef::Variant(def) = T::type_info().type_def else {
+            panic!("events are enums");
+        };
+        def.variants
+            .into_iter()
+            .map(|variant| {
+                let fields = variant.fields.into_iter().map(|field| field.name).collect();
+                (variant.index, variant.name, fields)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn events_match_the_pallet() {
+        assert_eq!(variants::<RoscaEvent>(), variants::<pallet_rosca::Event<Runtime>>());
+    }
+}
@@ -0,0 +1,137 @@
+//! Typed client for the ROSCA pallet of the generic runtime template.
+//!
+//! [`RoscaClient`] submits every `pallet_rosca` call, reads every item of its storage, calls the
+//! `RoscaApi` runtime API and streams the pallet's events. Calls and storage values are the
+//! runtime's own types, so the client can't disagree with the runtime it was built with. Events
+//! decode into [`RoscaEvent`], which mirrors the pallet's and serializes to JSON.
+//! Before submitting a call the client checks it against the node's metadata, which catches a
+//! node running a different runtime.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), subxt::Error> {
+//! use rosca_client::{dev, RoscaClient};
+//!
+//! let client = RoscaClient::connect("ws://127.0.0.1:9944").await?;
+//! let events = client.join_rosca(&dev::bob(), 0, None).await?;
+//! let rosca = client.rosca(0).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod calls;
+mod events;
+mod storage;
+
+pub use events::{Action, Amount, InsuranceTerms, RoscaEvent, Round, Threshold};
+pub use generic_runtime_template::{AccountId, Moment, Runtime};
+use parity_scale_codec::{Decode, Encode};
+pub use rosca_primitives::{
+    ParticipationSummary, PaymentAssets, RoscaId, RoscaStatus, RoscaSummary, RoundSummary,
+};
+pub use storage::Storage;
+use subxt::{
+    backend::{
+        legacy::LegacyRpcMethods,
+        rpc::{rpc_params, RpcClient},
+    },
+    utils::H256,
+    OnlineClient, PolkadotConfig,
+};
+pub use subxt_signer::sr25519::{dev, Keypair};
+
+/// Result of a client request.
+pub type Result<T> = core::result::Result<T, subxt::Error>;
+
+/// A connection to a node running the generic runtime template.
+#[derive(Clone)]
+pub struct RoscaClient {
+    api: OnlineClient<PolkadotConfig>,
+    rpc: RpcClient,
+    legacy: LegacyRpcMethods<PolkadotConfig>,
+}
+
+impl RoscaClient {
+    /// Connects to the node at `url`, e.g. `ws://127.0.0.1:9944`.
+    pub async fn connect(url: &str) -> Result<Self> {
+        let rpc = RpcClient::from_url(url).await?;
+        let api = OnlineClient::from_rpc_client(rpc.clone()).await?;
+        let legacy = LegacyRpcMethods::new(rpc.clone());
+        Ok(Self { api, rpc, legacy })
+    }
+
+    /// The underlying subxt client, for anything outside the ROSCA pallet.
+    pub fn api(&self) -> &OnlineClient<PolkadotConfig> {
+        &self.api
+    }
+
+    /// Storage at the best block.
+    pub fn storage(&self) -> Storage {
+        Storage::new(self.legacy.clone(), None)
+    }
+
+    /// Storage at the block `hash`.
+    pub fn storage_at(&self, hash: H256) -> Storage {
+        Storage::new(self.legacy.clone(), Some(hash))
+    }
+
+    /// The Rosca `rosca_id`, if it exists.
+    pub async fn rosca(
+        &self,
+        rosca_id: RoscaId,
+    ) -> Result<Option<RoscaSummary<AccountId, Moment>>> {
+        self.call_api("RoscaApi_rosca", rosca_id).await
+    }
+
+    /// Every Rosca, optionally only those with `status`.
+    pub async fn roscas(
+        &self,
+        status: Option<RoscaStatus>,
+    ) -> Result<Vec<RoscaSummary<AccountId, Moment>>> {
+        self.call_api("RoscaApi_roscas", status).await
+    }
+
+    /// The current round of an active Rosca followed by every round still to be paid out.
+    pub async fn schedule(
+        &self,
+        rosca_id: RoscaId,
+    ) -> Result<Vec<RoundSummary<AccountId, Moment>>> {
+        self.call_api("RoscaApi_schedule", rosca_id).await
+    }
+
+    /// The Roscas `account` has joined.
+    pub async fn participations(&self, account: AccountId) -> Result<Vec<ParticipationSummary>> {
+        self.call_api("RoscaApi_participations", account).await
+    }
+
+    async fn call_api<R: Decode>(&self, function: &str, args: impl Encode) -> Result<R> {
+        let args = args.encode();
+        self.api.runtime_api().at_latest().await?.call_raw(function, Some(&args[..])).await
+    }
+
+    /// Timestamp of the best block, in milliseconds since the epoch. Payment cutoffs are
+    /// compared against it.
+    pub async fn timestamp(&self) -> Result<Moment> {
+        let key = frame_support::storage::storage_prefix(b"Timestamp", b"Now");
+        match self.legacy.state_get_storage(&key, None).await? {
+            Some(bytes) => Ok(Moment::decode(&mut &bytes[..])?),
+            None => Ok(0),
+        }
+    }
+
+    /// Moves the clock of a `--dev-seal` node ahead by `millis` and seals a block. Returns the
+    /// block's timestamp.
+    pub async fn dev_advance_time(&self, millis: u64) -> Result<u64> {
+        self.rpc.request("dev_advanceTime", rpc_params![millis]).await
+    }
+
+    /// Moves the clock of a `--dev-seal` node to `timestamp` and seals a block. Returns the
+    /// block's timestamp.
+    pub async fn dev_set_timestamp(&self, timestamp: u64) -> Result<u64> {
+        self.rpc.request("dev_setTimestamp", rpc_params![timestamp]).await
+    }
+}
+
+/// The runtime account of `signer`.
+pub fn account_of(signer: &Keypair) -> AccountId {
+    AccountId::new(signer.public_key().0)
+}
@@ -0,0 +1,418 @@
+//! Typed reads of every `pallet_rosca` storage item.
+//!
+//! Keys are built the way the pallet builds them, from the `Rosca` pallet prefix, the item name
+//! and the `Blake2_128Concat` hash of each key part, and values are decoded with the runtime's
+//! own types. Items with a default return it when unset, like the pallet's getters.
+
+use frame_support::{storage::storage_prefix, Blake2_128Concat, StorageHasher};
+use pallet_rosca::{
+    Balance, InsuranceConfig, PotLedger, Proposal, ProposalId, RoscaDetails, RoscaId,
+};
+use parity_scale_codec::{Decode, Encode};
+use sp_runtime::Permill;
+use subxt::{backend::legacy::LegacyRpcMethods, utils::H256, PolkadotConfig};
+use xcm::VersionedLocation;
+
+use crate::{AccountId, Moment, PaymentAssets, Result, Runtime};
+
+/// Name of the ROSCA pallet in the runtime.
+const PALLET: &[u8] = b"Rosca";
+/// Keys fetched per request when iterating over a map.
+const PAGE_SIZE: u32 = 512;
+/// Length of the `Blake2_128Concat` hash in front of each key part.
+const HASH_LEN: usize = 16;
+
+fn item_key(item: &str) -> Vec<u8> {
+    storage_prefix(PALLET, item.as_bytes()).to_vec()
+}
+
+fn with_part(mut key: Vec<u8>, part: impl Encode) -> Vec<u8> {
+    key.extend(Blake2_128Concat::hash(&part.encode()));
+    key
+}
+
+fn map_key(item: &str, key: impl Encode) -> Vec<u8> {
+    with_part(item_key(item), key)
+}
+
+fn double_map_key(item: &str, key1: impl Encode, key2: impl Encode) -> Vec<u8> {
+    with_part(map_key(item, key1), key2)
+}
+
+/// `pallet_rosca` storage at a block, or at the best block when created without one.
+#[derive(Clone)]
+pub struct Storage {
+    rpc: LegacyRpcMethods<PolkadotConfig>,
+    at: Option<H256>,
+}
+
+impl Storage {
+    pub(crate) fn new(rpc: LegacyRpcMethods<PolkadotConfig>, at: Option<H256>) -> Self {
+        Self { rpc, at }
+    }
+
+    async fn get<V: Decode>(&self, key: &[u8]) -> Result<Option<V>> {
+        match self.rpc.state_get_storage(key, self.at).await? {
+            Some(bytes) => Ok(Some(V::decode(&mut &bytes[..])?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_or_default<V: Decode + Default>(&self, key: &[u8]) -> Result<V> {
+        Ok(self.get(key).await?.unwrap_or_default())
+    }
+
+    /// Every entry under `prefix`, keyed by the key part that follows it. All pages are read
+    /// at the same block, so the entries are consistent even while the chain moves on.
+    async fn iter<K: Decode, V: Decode>(&self, prefix: Vec<u8>) -> Result<Vec<(K, V)>> {
+        let at = match self.at {
+            Some(at) => Some(at),
+            None => self.rpc.chain_get_block_hash(None).await?,
+        };
+        let mut entries = Vec::new();
+        let mut start_key: Option<Vec<u8>> = None;
+        loop {
+            let keys =
+                self.rpc.state_get_keys_paged(&prefix, PAGE_SIZE, start_key.as_deref(), at).await?;
+            for key in &keys {
+                let part = K::decode(&mut &key[prefix.len() + HASH_LEN..])?;
+                if let Some(bytes) = self.rpc.state_get_storage(key, at).await? {
+                    entries.push((part, V::decode(&mut &bytes[..])?));
+                }
+            }
+            if keys.len() < PAGE_SIZE as usize {
+                return Ok(entries);
+            }
+            start_key = keys.last().cloned();
+        }
+    }
+
+    async fn keys<K: Decode>(&self, prefix: Vec<u8>) -> Result<Vec<K>> {
+        Ok(self.iter::<K, ()>(prefix).await?.into_iter().map(|(key, ())| key).collect())
+    }
+
+    /// The id the next Rosca will get. Every Rosca has a lower one.
+    pub async fn next_rosca_id(&self) -> Result<RoscaId> {
+        self.get_or_default(&item_key("NextRoscaId")).await
+    }
+
+    /// Details of a pending Rosca.
+    pub async fn pending_rosca(&self, rosca_id: RoscaId) -> Result<Option<RoscaDetails<Runtime>>> {
+        self.get(&map_key("PendingRoscaDetails", rosca_id)).await
+    }
+
+    /// Every pending Rosca.
+    pub async fn pending_roscas(&self) -> Result<Vec<(RoscaId, RoscaDetails<Runtime>)>> {
+        self.iter(item_key("PendingRoscaDetails")).await
+    }
+
+    /// Details of an active Rosca.
+    pub async fn active_rosca(&self, rosca_id: RoscaId) -> Result<Option<RoscaDetails<Runtime>>> {
+        self.get(&map_key("ActiveRoscas", rosca_id)).await
+    }
+
+    /// Every active Rosca.
+    pub async fn active_roscas(&self) -> Result<Vec<(RoscaId, RoscaDetails<Runtime>)>> {
+        self.iter(item_key("ActiveRoscas")).await
+    }
+
+    /// Whether a Rosca has completed.
+    pub async fn is_completed(&self, rosca_id: RoscaId) -> Result<bool> {
+        Ok(self.get::<()>(&map_key("CompletedRoscas", rosca_id)).await?.is_some())
+    }
+
+    /// Ids of every completed Rosca.
+    pub async fn completed_roscas(&self) -> Result<Vec<RoscaId>> {
+        self.keys(item_key("CompletedRoscas")).await
+    }
+
+    /// Details of a completed Rosca, kept for claims made after completion.
+    pub async fn completed_rosca(
+        &self,
+        rosca_id: RoscaId,
+    ) -> Result<Option<RoscaDetails<Runtime>>> {
+        self.get(&map_key("CompletedRoscaDetails", rosca_id)).await
+    }
+
+    /// The position `account` asked for when joining, if it's a participant.
+    pub async fn participant(&self, rosca_id: RoscaId, account: &AccountId) -> Result<Option<u32>> {
+        self.get(&double_map_key("RoscaParticipants", rosca_id, account)).await
+    }
+
+    /// Every participant of a Rosca with the position they asked for.
+    pub async fn participants(&self, rosca_id: RoscaId) -> Result<Vec<(AccountId, u32)>> {
+        self.iter(map_key("RoscaParticipants", rosca_id)).await
+    }
+
+    /// Whether `account` is invited to a Rosca. The creator counts as invited.
+    pub async fn is_invited(&self, rosca_id: RoscaId, account: &AccountId) -> Result<bool> {
+        let key = double_map_key("RoscaInvitedPreverifiedParticipants", rosca_id, account);
+        Ok(self.get::<()>(&key).await?.is_some())
+    }
+
+    /// Every account invited to a Rosca, including its creator.
+    pub async fn invited(&self, rosca_id: RoscaId) -> Result<Vec<AccountId>> {
+        self.keys(map_key("RoscaInvitedPreverifiedParticipants", rosca_id)).await
+    }
+
+    /// Number of participants in a Rosca.
+    pub async fn participants_count(&self, rosca_id: RoscaId) -> Result<Option<u32>> {
+        self.get(&map_key("RoscaParticipantsCount", rosca_id)).await
+    }
+
+    /// The security deposit `account` holds in a Rosca.
+    pub async fn security_deposit(
+        &self,
+        rosca_id: RoscaId,
+        account: &AccountId,
+    ) -> Result<Option<u32>> {
+        self.get(&double_map_key("RoscaSecurityDeposits", rosca_id, account)).await
+    }
+
+    /// Every security deposit held in a Rosca.
+    pub async fn security_deposits(&self, rosca_id: RoscaId) -> Result<Vec<(AccountId, u32)>> {
+        self.iter(map_key("RoscaSecurityDeposits", rosca_id)).await
+    }
+
+    /// The claim order of a pending Rosca, with unclaimed positions empty.
+    pub async fn pending_participants_order(
+        &self,
+        rosca_id: RoscaId,
+    ) -> Result<Option<Vec<Option<AccountId>>>> {
+        self.get(&map_key("PendingRoscaParticipantsOrder", rosca_id)).await
+    }
+
+    /// The claim order of an active Rosca. The current recipient is first and the next one last.
+    pub async fn active_participants_order(
+        &self,
+        rosca_id: RoscaId,
+    ) -> Result<Option<Vec<AccountId>>> {
+        self.get(&map_key("ActiveRoscaParticipantsOrder", rosca_id)).await
+    }
+
+    /// The payment cutoff of the current round of an active Rosca.
+    pub async fn next_pay_by_timestamp(&self, rosca_id: RoscaId) -> Result<Option<Moment>> {
+        self.get(&map_key("NextPayByTimestamp", rosca_id)).await
+    }
+
+    /// The payment cutoff of the last round of an active Rosca.
+    pub async fn final_pay_by_timestamp(&self, rosca_id: RoscaId) -> Result<Option<Moment>> {
+        self.get(&map_key("FinalPayByTimestamp", rosca_id)).await
+    }
+
+    /// The recipient of the current round's pot.
+    pub async fn eligible_claimant(&self, rosca_id: RoscaId) -> Result<Option<AccountId>> {
+        self.get(&map_key("EligibleClaimant", rosca_id)).await
+    }
+
+    /// The account holding a Rosca's funds.
+    pub async fn rosca_account(&self, rosca_id: RoscaId) -> Result<Option<AccountId>> {
+        self.get(&map_key("RoscaAccounts", rosca_id)).await
+    }
+
+    /// Whether `account` has paid into the current round.
+    pub async fn is_current_contributor(
+        &self,
+        rosca_id: RoscaId,
+        account: &AccountId,
+    ) -> Result<bool> {
+        let key = double_map_key("CurrentContributors", rosca_id, account);
+        Ok(self.get::<()>(&key).await?.is_some())
+    }
+
+    /// Every participant that has paid into the current round.
+    pub async fn current_contributors(&self, rosca_id: RoscaId) -> Result<Vec<AccountId>> {
+        self.keys(map_key("CurrentContributors", rosca_id)).await
+    }
+
+    /// Number of contributions paid into the current round.
+    pub async fn current_contribution_count(&self, rosca_id: RoscaId) -> Result<u32> {
+        self.get_or_default(&map_key("CurrentContributionCount", rosca_id)).await
+    }
+
+    /// Number of the round an active Rosca is in, counting from 1.
+    pub async fn current_round(&self, rosca_id: RoscaId) -> Result<u32> {
+        self.get_or_default(&map_key("CurrentRound", rosca_id)).await
+    }
+
+    /// What `account` has paid into and received from the Rosca's pots.
+    pub async fn pot_ledger(&self, rosca_id: RoscaId, account: &AccountId) -> Result<PotLedger> {
+        self.get_or_default(&double_map_key("PotLedgers", rosca_id, account)).await
+    }
+
+    /// Number of defaults recorded against `account`.
+    pub async fn default_count(&self, rosca_id: RoscaId, account: &AccountId) -> Result<u32> {
+        self.get_or_default(&double_map_key("DefaultCount", rosca_id, account)).await
+    }
+
+    /// Every participant with a recorded default, with their count.
+    pub async fn default_counts(&self, rosca_id: RoscaId) -> Result<Vec<(AccountId, u32)>> {
+        self.iter(map_key("DefaultCount", rosca_id)).await
+    }
+
+    /// The counterparty of a position swap `proposer` proposed.
+    pub async fn pending_swap(
+        &self,
+        rosca_id: RoscaId,
+        proposer: &AccountId,
+    ) -> Result<Option<AccountId>> {
+        self.get(&double_map_key("PendingSwaps", rosca_id, proposer)).await
+    }
+
+    /// Every position swap awaiting acceptance, as (proposer, counterparty).
+    pub async fn pending_swaps(&self, rosca_id: RoscaId) -> Result<Vec<(AccountId, AccountId)>> {
+        self.iter(map_key("PendingSwaps", rosca_id)).await
+    }
+
+    /// The substitute `departing` nominated.
+    pub async fn pending_substitution(
+        &self,
+        rosca_id: RoscaId,
+        departing: &AccountId,
+    ) -> Result<Option<AccountId>> {
+        self.get(&double_map_key("PendingSubstitutions", rosca_id, departing)).await
+    }
+
+    /// Every substitution awaiting acceptance, as (departing, substitute).
+    pub async fn pending_substitutions(
+        &self,
+        rosca_id: RoscaId,
+    ) -> Result<Vec<(AccountId, AccountId)>> {
+        self.iter(map_key("PendingSubstitutions", rosca_id)).await
+    }
+
+    /// The account that created a Rosca.
+    pub async fn rosca_creator(&self, rosca_id: RoscaId) -> Result<Option<AccountId>> {
+        self.get(&map_key("RoscaCreator", rosca_id)).await
+    }
+
+    /// Number of defaults after which a participant is ejected, if limited.
+    pub async fn max_defaults(&self, rosca_id: RoscaId) -> Result<Option<u32>> {
+        self.get(&map_key("MaxDefaults", rosca_id)).await
+    }
+
+    /// When a paused Rosca was paused.
+    pub async fn paused_at(&self, rosca_id: RoscaId) -> Result<Option<Moment>> {
+        self.get(&map_key("PausedAt", rosca_id)).await
+    }
+
+    /// Guarantee pre-funded by a Rosca's guarantor and not yet drawn or claimed.
+    pub async fn guarantee_balance(&self, rosca_id: RoscaId) -> Result<u32> {
+        self.get_or_default(&map_key("GuaranteeBalances", rosca_id)).await
+    }
+
+    /// What `account` owes a Rosca's guarantor for covered contributions.
+    pub async fn guarantor_debt(&self, rosca_id: RoscaId, account: &AccountId) -> Result<u32> {
+        self.get_or_default(&double_map_key("GuarantorDebts", rosca_id, account)).await
+    }
+
+    /// Every debt owed to a Rosca's guarantor.
+    pub async fn guarantor_debts(&self, rosca_id: RoscaId) -> Result<Vec<(AccountId, u32)>> {
+        self.iter(map_key("GuarantorDebts", rosca_id)).await
+    }
+
+    /// Insurance settings of a payment asset, if insured.
+    pub async fn insurance_config(&self, asset: PaymentAssets) -> Result<Option<InsuranceConfig>> {
+        self.get(&map_key("InsuranceConfigs", asset)).await
+    }
+
+    /// Funds in the insurance pool of a payment asset.
+    pub async fn insurance_pool(&self, asset: PaymentAssets) -> Result<Balance> {
+        self.get_or_default(&map_key("InsurancePools", asset)).await
+    }
+
+    /// Platform fee taken from contributions in assets without an override.
+    pub async fn platform_fee(&self) -> Result<Permill> {
+        self.get_or_default(&item_key("PlatformFee")).await
+    }
+
+    /// Platform fee override of a payment asset.
+    pub async fn asset_platform_fee(&self, asset: PaymentAssets) -> Result<Option<Permill>> {
+        self.get(&map_key("AssetPlatformFees", asset)).await
+    }
+
+    /// Organiser fee the creator of a Rosca takes from contributions.
+    pub async fn organiser_fee(&self, rosca_id: RoscaId) -> Result<Option<Permill>> {
+        self.get(&map_key("OrganiserFees", rosca_id)).await
+    }
+
+    /// Native balance left in a Rosca's fee sponsorship.
+    pub async fn sponsorship_balance(&self, rosca_id: RoscaId) -> Result<Balance> {
+        self.get_or_default(&map_key("SponsorshipBalances", rosca_id)).await
+    }
+
+    /// Sponsored calls `account` made, with the payment cutoff of the round they were made in.
+    pub async fn sponsored_calls(
+        &self,
+        rosca_id: RoscaId,
+        account: &AccountId,
+    ) -> Result<Option<(Option<Moment>, u32)>> {
+        self.get(&double_map_key("SponsoredCalls", rosca_id, account)).await
+    }
+
+    /// Where `account` wants its payouts from a Rosca delivered, if not to its account here.
+    pub async fn payout_destination(
+        &self,
+        rosca_id: RoscaId,
+        account: &AccountId,
+    ) -> Result<Option<VersionedLocation>> {
+        self.get(&double_map_key("PayoutDestinations", rosca_id, account)).await
+    }
+
+    /// Whether `account` has autopay enabled in a Rosca.
+    pub async fn is_autopay(&self, rosca_id: RoscaId, account: &AccountId) -> Result<bool> {
+        Ok(self.get::<()>(&double_map_key("Autopay", rosca_id, account)).await?.is_some())
+    }
+
+    /// Every participant of a Rosca with autopay enabled.
+    pub async fn autopay_participants(&self, rosca_id: RoscaId) -> Result<Vec<AccountId>> {
+        self.keys(map_key("Autopay", rosca_id)).await
+    }
+
+    /// Whether new Roscas may be created in `asset`.
+    pub async fn is_asset_allowed(&self, asset: PaymentAssets) -> Result<bool> {
+        Ok(self.get::<()>(&map_key("DisallowedAssets", asset)).await?.is_none())
+    }
+
+    /// The id the next proposal in a Rosca will get.
+    pub async fn next_proposal_id(&self, rosca_id: RoscaId) -> Result<ProposalId> {
+        self.get_or_default(&map_key("NextProposalId", rosca_id)).await
+    }
+
+    /// An open proposal of a Rosca.
+    pub async fn proposal(
+        &self,
+        rosca_id: RoscaId,
+        proposal_id: ProposalId,
+    ) -> Result<Option<Proposal<Runtime>>> {
+        self.get(&double_map_key("RoscaProposals", rosca_id, proposal_id)).await
+    }
+
+    /// Every open proposal of a Rosca.
+    pub async fn proposals(
+        &self,
+        rosca_id: RoscaId,
+    ) -> Result<Vec<(ProposalId, Proposal<Runtime>)>> {
+        self.iter(map_key("RoscaProposals", rosca_id)).await
+    }
+
+    /// The vote `voter` cast on a proposal, aye being `true`.
+    pub async fn proposal_vote(
+        &self,
+        rosca_id: RoscaId,
+        proposal_id: ProposalId,
+        voter: &AccountId,
+    ) -> Result<Option<bool>> {
+        let key = with_part(double_map_key("ProposalVotes", rosca_id, proposal_id), voter);
+        self.get(&key).await
+    }
+
+    /// Every vote cast on a proposal.
+    pub async fn proposal_votes(
+        &self,
+        rosca_id: RoscaId,
+        proposal_id: ProposalId,
+    ) -> Result<Vec<(AccountId, bool)>> {
+        self.iter(double_map_key("ProposalVotes", rosca_id, proposal_id)).await
+    }
+}
@@ -0,0 +1,163 @@
+// Integration tests against a `--dev-seal` node.
+//
+// They need a node binary, so they're ignored by a plain `cargo test`. `scripts/client-tests.sh`
+// builds the node and runs them, and the `client-tests` workflow runs that in CI. By hand:
+//   cargo build --release -p generic-template-node
+//   cargo test -p rosca-client -- --ignored
+// Set `ROSCA_NODE` to use a node binary from somewhere other than `target/release`.
+use std::{
+    net::TcpListener,
+    path::PathBuf,
+    process::{Child, Command, Stdio},
+    time::Duration,
+};
+
+use futures::StreamExt;
+use rosca_client::{account_of, dev, PaymentAssets, RoscaClient, RoscaEvent, RoscaStatus};
+
+const DAY: u64 = 24 * 60 * 60 * 1000;
+// 100 USDT
+const CONTRIBUTION: u32 = 100_000_000;
+
+/// A dev node on a free port, killed when dropped.
+struct DevNode {
+    process: Child,
+    url: String,
+}
+
+impl DevNode {
+    fn start() -> Self {
+        let binary = std::env::var_os("ROSCA_NODE").map(PathBuf::from).unwrap_or_else(|| {
+            let target = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../target/release");
+            target.join("generic-template-node")
+        });
+        let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let process = Command::new(&binary)
+            .args(["--dev", "--dev-seal=instant", "--rpc-port", &port.to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap_or_else(|err| panic!("can't start {}: {err}", binary.display()));
+        Self { process, url: format!("ws://127.0.0.1:{port}") }
+    }
+
+    async fn connect(&self) -> RoscaClient {
+        for _ in 0..120 {
+            if let Ok(client) = RoscaClient::connect(&self.url).await {
+                return client;
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+        panic!("dev node at {} didn't come up", self.url);
+    }
+}
+
+impl Drop for DevNode {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
+
+/// Alice creates Rosca 0 with Bob and Charlie invited, and both join.
+async fn create_and_join(client: &RoscaClient) {
+    let now = client.dev_advance_time(0).await.unwrap();
+    let invited = vec![account_of(&dev::bob()), account_of(&dev::charlie())];
+    let events = client
+        .create_rosca(
+            &dev::alice(),
+            false,
+            invited,
+            3,
+            CONTRIBUTION,
+            PaymentAssets::USDT,
+            DAY,
+            now + DAY,
+            None,
+            b"Test circle".to_vec(),
+        )
+        .await
+        .unwrap();
+    assert!(matches!(events[..], [RoscaEvent::RoscaCreated { rosca_id: 0, .. }]));
+
+    for signer in [dev::bob(), dev::charlie()] {
+        let events = client.join_rosca(&signer, 0, None).await.unwrap();
+        assert!(events.iter().any(|event| matches!(event, RoscaEvent::JoinedRosca { .. })));
+    }
+}
+
+#[tokio::test]
+#[ignore = "needs a release build of the node"]
+async fn runs_a_rosca_round() {
+    let node = DevNode::start();
+    let client = node.connect().await;
+    create_and_join(&client).await;
+
+    let storage = client.storage();
+    assert_eq!(storage.next_rosca_id().await.unwrap(), 1);
+    assert_eq!(storage.participants(0).await.unwrap().len(), 3);
+    assert!(storage.pending_rosca(0).await.unwrap().is_some());
+    assert_eq!(client.rosca(0).await.unwrap().unwrap().status, RoscaStatus::Pending);
+
+    let events = client.start_rosca(&dev::alice(), 0).await.unwrap();
+    let Some(RoscaEvent::RoscaStarted { first_eligible_claimant, first_payment_cutoff, .. }) =
+        events.into_iter().find(|event| matches!(event, RoscaEvent::RoscaStarted { .. }))
+    else {
+        panic!("Rosca didn't start");
+    };
+
+    let rosca = client.rosca(0).await.unwrap().unwrap();
+    assert_eq!(rosca.status, RoscaStatus::Active);
+    assert_eq!(rosca.next_payment_cutoff, Some(first_payment_cutoff));
+    assert_eq!(rosca.eligible_claimant, Some(first_eligible_claimant.clone()));
+    assert_eq!(client.schedule(0).await.unwrap().len(), 3);
+
+    for signer in [dev::alice(), dev::bob(), dev::charlie()] {
+        if account_of(&signer) != first_eligible_claimant {
+            client.contribute_to_rosca(&signer, 0).await.unwrap();
+        }
+    }
+    assert_eq!(storage.current_contribution_count(0).await.unwrap(), 2);
+    assert_eq!(storage.current_contributors(0).await.unwrap().len(), 2);
+
+    let participations = client.participations(account_of(&dev::bob())).await.unwrap();
+    assert_eq!(participations.len(), 1);
+    assert_eq!(participations[0].status, RoscaStatus::Active);
+}
+
+#[tokio::test]
+#[ignore = "needs a release build of the node"]
+async fn returns_pallet_errors() {
+    let node = DevNode::start();
+    let client = node.connect().await;
+    create_and_join(&client).await;
+
+    let error = client.join_rosca(&dev::bob(), 0, None).await.unwrap_err();
+    assert!(matches!(error, subxt::Error::Runtime(_)), "{error:?}");
+    assert!(error.to_string().contains("AlreadyJoined"), "{error}");
+
+    // Root calls go through sudo, and only Alice holds the sudo key.
+    client.set_asset_allowed(&dev::alice(), PaymentAssets::USDC, false).await.unwrap();
+    assert!(!client.storage().is_asset_allowed(PaymentAssets::USDC).await.unwrap());
+    assert!(client.set_asset_allowed(&dev::bob(), PaymentAssets::USDC, true).await.is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a release build of the node"]
+async fn streams_rosca_events() {
+    let node = DevNode::start();
+    let client = node.connect().await;
+    let mut events = Box::pin(client.subscribe_events().await.unwrap());
+
+    create_and_join(&client).await;
+
+    let mut seen = Vec::new();
+    while seen.len() < 3 {
+        let next = tokio::time::timeout(Duration::from_secs(30), events.next()).await.unwrap();
+        let (_, block_events) = next.unwrap().unwrap();
+        seen.extend(block_events);
+    }
+    assert!(matches!(seen[0], RoscaEvent::RoscaCreated { rosca_id: 0, .. }));
+    let joined = |event: &RoscaEvent| matches!(event, RoscaEvent::JoinedRosca { rosca_id: 0, .. });
+    assert!(seen[1..].iter().all(joined));
+}
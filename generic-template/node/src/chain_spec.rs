@@ -1,20 +1,25 @@
-use cumulus_primitives_core::ParaId;
+use cumulus_client_service::ParachainHostFunctions;
 use generic_runtime_template::{
-    constants::currency::EXISTENTIAL_DEPOSIT, AccountId, AuraId, Signature,
+    genesis_config_presets::{testnet_genesis, DEMO_ROSCA_PRESET, DEV_PRESET},
+    AccountId, AuraId, Signature,
 };
+use parity_scale_codec::{Decode, Encode};
 use sc_chain_spec::{ChainSpecExtension, ChainSpecGroup};
+use sc_executor::WasmExecutor;
 use sc_service::ChainType;
 use serde::{Deserialize, Serialize};
-use sp_core::{sr25519, Pair, Public};
+use sp_core::{
+    sr25519,
+    traits::{CallContext, CodeExecutor, RuntimeCode, WrappedRuntimeCode},
+    Pair, Public,
+};
 use sp_runtime::traits::{IdentifyAccount, Verify};
+use sp_state_machine::BasicExternalities;
 
 /// Specialized `ChainSpec` for the normal parachain runtime.
 pub type ChainSpec =
     sc_service::GenericChainSpec<generic_runtime_template::RuntimeGenesisConfig, Extensions>;
 
-/// The default XCM version to set in genesis config.
-const SAFE_XCM_VERSION: u32 = xcm::prelude::XCM_VERSION;
-
 /// Helper function to generate a crypto pair from seed
 pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
     TPublic::Pair::from_string(&format!("//{}", seed), None)
@@ -57,12 +62,33 @@ where
     AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
-/// Generate the session keys from individual elements.
-///
-/// The input must be a tuple of individual keys (a single arg for now since we
-/// have just one key).
-pub fn template_session_keys(keys: AuraId) -> generic_runtime_template::SessionKeys {
-    generic_runtime_template::SessionKeys { aura: keys }
+/// The genesis config patch of the runtime's preset `name`, read through its `GenesisPresets` API.
+fn genesis_preset(name: &str) -> serde_json::Value {
+    let code =
+        generic_runtime_template::WASM_BINARY.expect("WASM binary was not built, please build it!");
+    let runtime_code = RuntimeCode {
+        code_fetcher: &WrappedRuntimeCode(code.into()),
+        heap_pages: None,
+        hash: sp_core::blake2_256(code).to_vec(),
+    };
+    let executor = WasmExecutor::<ParachainHostFunctions>::builder()
+        .with_allow_missing_host_functions(true)
+        .build();
+
+    let result = executor
+        .call(
+            &mut BasicExternalities::new_empty(),
+            &runtime_code,
+            "GenesisPresets_get_preset",
+            &name.as_bytes().to_vec().encode(),
+            CallContext::Offchain,
+        )
+        .0
+        .unwrap_or_else(|e| panic!("the runtime failed to build the {name} preset: {e}"));
+    let patch = Option::<Vec<u8>>::decode(&mut &result[..])
+        .expect("the runtime returns an encoded patch; qed")
+        .unwrap_or_else(|| panic!("the runtime has no {name} preset"));
+    serde_json::from_slice(&patch).expect("the runtime returns a JSON patch; qed")
 }
 
 pub fn development_config() -> ChainSpec {
@@ -85,35 +111,38 @@ pub fn development_config() -> ChainSpec {
     .with_name("Development")
     .with_id("dev")
     .with_chain_type(ChainType::Development)
-    .with_genesis_config_patch(testnet_genesis(
-        // initial collators.
-        vec![
-            (
-                get_account_id_from_seed::<sr25519::Public>("Alice"),
-                get_collator_keys_from_seed("Alice"),
-            ),
-            (
-                get_account_id_from_seed::<sr25519::Public>("Bob"),
-                get_collator_keys_from_seed("Bob"),
-            ),
-        ],
-        vec![
-            get_account_id_from_seed::<sr25519::Public>("Alice"),
-            get_account_id_from_seed::<sr25519::Public>("Bob"),
-            get_account_id_from_seed::<sr25519::Public>("Charlie"),
-            get_account_id_from_seed::<sr25519::Public>("Dave"),
-            get_account_id_from_seed::<sr25519::Public>("Eve"),
-            get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-            get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-            get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-            get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
-            get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
-            get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
-            get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-        ],
-        get_account_id_from_seed::<sr25519::Public>("Alice"),
-        1000.into(),
-    ))
+    .with_genesis_config_patch(genesis_preset(DEV_PRESET))
+    .build()
+}
+
+/// The dev chain with a couple of ROSCAs between the development accounts, timed from now.
+pub fn demo_rosca_config() -> ChainSpec {
+    let mut properties = sc_chain_spec::Properties::new();
+    properties.insert("tokenSymbol".into(), "UNIT".into());
+    properties.insert("tokenDecimals".into(), 12.into());
+    properties.insert("ss58Format".into(), 42.into());
+    properties.insert("basedOn".into(), "OpenZeppelin Generic Template".into());
+
+    let mut genesis = genesis_preset(DEMO_ROSCA_PRESET);
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("current time is after the epoch; qed")
+        .as_millis() as u64;
+    genesis["rosca"]["genesisTimestamp"] = now.into();
+
+    ChainSpec::builder(
+        generic_runtime_template::WASM_BINARY.expect("WASM binary was not built, please build it!"),
+        Extensions {
+            relay_chain: "rococo-local".into(),
+            // You MUST set this to the correct network!
+            para_id: 1000,
+        },
+    )
+    .with_name("ROSCA Demo")
+    .with_id("demo_rosca")
+    .with_chain_type(ChainType::Development)
+    .with_genesis_config_patch(genesis)
+    .with_properties(properties)
     .build()
 }
 
@@ -163,69 +192,9 @@ pub fn local_testnet_config() -> ChainSpec {
             get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
         ],
         get_account_id_from_seed::<sr25519::Public>("Alice"),
-        1000.into(),
+        1000,
     ))
     .with_protocol_id("template-local")
     .with_properties(properties)
     .build()
 }
-
-fn testnet_genesis(
-    invulnerables: Vec<(AccountId, AuraId)>,
-    endowed_accounts: Vec<AccountId>,
-    root: AccountId,
-    id: ParaId,
-) -> serde_json::Value {
-    serde_json::json!({
-        "balances": {
-            "balances": endowed_accounts.iter().cloned().map(|k| (k, 1u64 << 60)).collect::<Vec<_>>(),
-        },
-        "parachainInfo": {
-            "parachainId": id,
-        },
-        "collatorSelection": {
-            "invulnerables": invulnerables.iter().cloned().map(|(acc, _)| acc).collect::<Vec<_>>(),
-            "candidacyBond": EXISTENTIAL_DEPOSIT * 16,
-        },
-        "session": {
-            "keys": invulnerables
-                .into_iter()
-                .map(|(acc, aura)| {
-                    (
-                        acc.clone(),                 // account id
-                        acc,                         // validator id
-                        template_session_keys(aura), // session keys
-                    )
-                })
-            .collect::<Vec<_>>(),
-        },
-        "treasury": {},
-        "polkadotXcm": {
-            "safeXcmVersion": Some(SAFE_XCM_VERSION),
-        },
-        "sudo": { "key": Some(root.clone()) },
-        "assets":  {
-            "assets": vec![(
-                1984,
-                root.clone(),
-                true,
-                70000,
-            ),
-            (
-                1337,
-                root.clone(),
-                true,
-                70000,
-            )],
-            // (id, name, symbol, decimals)
-            "metadata": vec![
-                (1984, b"USDT", b"USDT", 6), (1337, b"USDC", b"USDC", 6),
-            ],
-            // (id, account_id, amount)
-            "accounts": vec![
-                (1984, root.clone(), 1000000u64),
-                (1337, root.clone(), 1000000u64),
-            ],
-        },
-    })
-}
@@ -15,6 +15,9 @@ pub enum Subcommand {
     /// Export the state of a given block into a chain spec.
     ExportState(sc_cli::ExportStateCmd),
 
+    /// Report the ROSCA pallet's storage at a given block and check its consistency.
+    InspectRosca(crate::inspect::InspectRoscaCmd),
+
     /// Import blocks.
     ImportBlocks(sc_cli::ImportBlocksCmd),
 
@@ -55,6 +58,8 @@ const AFTER_HELP_EXAMPLE: &str = color_print::cstr!(
            Launch a full node with default parachain <italic>local-testnet</> and relay chain <italic>rococo-local</>.
    <bold>parachain-template-node --collator</>
            Launch a collator with default parachain <italic>local-testnet</> and relay chain <italic>rococo-local</>.
+   <bold>parachain-template-node --dev --dev-seal manual</>
+           Launch a standalone dev node that seals blocks on request and whose clock can be moved over RPC.
  "#
 );
 #[derive(Debug, clap::Parser)]
@@ -81,11 +86,28 @@ pub struct Cli {
     #[arg(long)]
     pub no_hardware_benchmarks: bool,
 
+    /// Run a standalone development node that seals its own blocks, without a relay chain.
+    ///
+    /// `instant`, the default, seals a block for every transaction and `manual` only when asked
+    /// to through `engine_createBlock`. Either way, `dev_setTimestamp` and `dev_advanceTime` move
+    /// the chain's clock and seal a block at the new time.
+    #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "instant")]
+    pub dev_seal: Option<DevSeal>,
+
     /// Relay chain arguments
     #[arg(raw = true)]
     pub relay_chain_args: Vec<String>,
 }
 
+/// How a `--dev-seal` node seals blocks.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum DevSeal {
+    /// Seal a block for every transaction.
+    Instant,
+    /// Seal blocks only when asked to over RPC.
+    Manual,
+}
+
 #[derive(Debug)]
 pub struct RelayChainCli {
     /// The actual relay chain cli object.
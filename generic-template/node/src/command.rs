@@ -21,6 +21,7 @@ use crate::{
 fn load_spec(id: &str) -> std::result::Result<Box<dyn ChainSpec>, String> {
     Ok(match id {
         "dev" => Box::new(chain_spec::development_config()),
+        "demo-rosca" => Box::new(chain_spec::demo_rosca_config()),
         "template-paseo" => Box::new(chain_spec::local_testnet_config()),
         "" | "local" => Box::new(chain_spec::local_testnet_config()),
         path => Box::new(chain_spec::ChainSpec::from_json_file(std::path::PathBuf::from(path))?),
@@ -132,6 +133,11 @@ pub fn run() -> Result<()> {
 				Ok(cmd.run(components.client, config.chain_spec))
 			})
 		},
+		Some(Subcommand::InspectRosca(cmd)) => {
+			construct_async_run!(|components, cli, cmd, config| {
+				Ok(cmd.run(components.client, components.backend))
+			})
+		},
 		Some(Subcommand::ImportBlocks(cmd)) => {
 			construct_async_run!(|components, cli, cmd, config| {
 				Ok(cmd.run(components.client, components.import_queue))
@@ -217,6 +223,13 @@ pub fn run() -> Result<()> {
 		Some(Subcommand::TryRuntime) => Err("The `try-runtime` subcommand has been migrated to a standalone CLI (https://github.com/paritytech/try-runtime-cli). It is no longer being maintained here and will be removed entirely some time after January 2024. Please remove this subcommand from your runtime and use the standalone CLI.".into()),
 		None => {
 			let runner = cli.create_runner(&cli.run.normalize())?;
+
+			if let Some(seal) = cli.dev_seal {
+				return runner.run_node_until_exit(|config| async move {
+					crate::service::start_dev_node(config, seal).map_err(Into::into)
+				});
+			}
+
 			let collator_options = cli.run.collator_options();
 
 			runner.run_node_until_exit(|config| async move {
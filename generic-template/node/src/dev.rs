@@ -0,0 +1,144 @@
+//! Controllable time for `--dev-seal` nodes.
+//!
+//! A dev-seal node has no relay chain and seals blocks on demand, so it also decides the time
+//! each block is stamped with. [`DevClock`] normally follows the wall clock, and the `dev_*` RPC
+//! methods move it ahead so anything timed by `pallet_timestamp`, such as ROSCA payment cutoffs,
+//! can be reached without waiting.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt,
+};
+use generic_runtime_template::{constants::SLOT_DURATION, opaque::Hash};
+use jsonrpsee::{
+    core::{async_trait, RpcResult},
+    proc_macros::rpc,
+    types::ErrorObjectOwned,
+};
+use sc_consensus_manual_seal::EngineCommand;
+
+/// The time blocks are stamped with on a dev-seal node.
+///
+/// Block timestamps are whole slots, as Aura expects, and each block is at least a slot after
+/// the previous one.
+#[derive(Clone)]
+pub struct DevClock(Arc<Mutex<ClockState>>);
+
+struct ClockState {
+    /// Milliseconds the clock runs ahead of the wall clock, or behind it if negative.
+    offset: i64,
+    /// Timestamp of the last block stamped.
+    last: u64,
+}
+
+fn wall_clock() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("current time is after the epoch; qed")
+        .as_millis() as i64
+}
+
+impl DevClock {
+    /// A clock for a chain whose best block is stamped `last`. If that is ahead of the wall
+    /// clock, because time was advanced before the node restarted, the clock carries on from it.
+    pub fn new(last: u64) -> Self {
+        let offset = (last as i64 - wall_clock()).max(0);
+        Self(Arc::new(Mutex::new(ClockState { offset, last })))
+    }
+
+    /// Timestamp of the last block stamped.
+    pub fn last(&self) -> u64 {
+        self.0.lock().expect("clock lock is never poisoned; qed").last
+    }
+
+    /// Stamps a new block, returning its timestamp.
+    pub fn next_block_timestamp(&self) -> u64 {
+        let mut state = self.0.lock().expect("clock lock is never poisoned; qed");
+        state.last = Self::next(&state);
+        state.last
+    }
+
+    /// Moves the clock ahead by `millis`.
+    pub fn advance(&self, millis: u64) {
+        let mut state = self.0.lock().expect("clock lock is never poisoned; qed");
+        state.offset = state.offset.saturating_add(millis as i64);
+    }
+
+    /// Moves the clock to `timestamp`. Time can't go back past the last block.
+    pub fn set(&self, timestamp: u64) -> Result<(), String> {
+        let mut state = self.0.lock().expect("clock lock is never poisoned; qed");
+        if timestamp <= state.last {
+            return Err(format!("{timestamp} is not after the last block's timestamp {}", state.last));
+        }
+        state.offset = timestamp as i64 - wall_clock();
+        Ok(())
+    }
+
+    fn next(state: &ClockState) -> u64 {
+        let now = wall_clock().saturating_add(state.offset).max(0) as u64;
+        let slot_start = now - now % SLOT_DURATION;
+        slot_start.max(state.last.saturating_add(SLOT_DURATION))
+    }
+}
+
+/// Time control for dev-seal nodes.
+#[rpc(server, namespace = "dev")]
+pub trait DevApi {
+    /// Moves the clock to `timestamp`, in milliseconds since the epoch, and seals a block at
+    /// that time. Returns the block's timestamp, which is rounded down to a whole slot.
+    #[method(name = "setTimestamp")]
+    async fn set_timestamp(&self, timestamp: u64) -> RpcResult<u64>;
+
+    /// Moves the clock ahead by `millis` and seals a block at the new time. Returns the block's
+    /// timestamp.
+    #[method(name = "advanceTime")]
+    async fn advance_time(&self, millis: u64) -> RpcResult<u64>;
+}
+
+/// Implements [`DevApiServer`] by moving a [`DevClock`] and asking manual seal for a block.
+pub struct Dev {
+    clock: DevClock,
+    seal: mpsc::Sender<EngineCommand<Hash>>,
+}
+
+impl Dev {
+    /// Serves `clock`, sealing blocks through `seal`.
+    pub fn new(clock: DevClock, seal: mpsc::Sender<EngineCommand<Hash>>) -> Self {
+        Self { clock, seal }
+    }
+
+    async fn seal_block(&self) -> RpcResult<u64> {
+        let (sender, receiver) = oneshot::channel();
+        let command = EngineCommand::SealNewBlock {
+            create_empty: true,
+            finalize: true,
+            parent_hash: None,
+            sender: Some(sender),
+        };
+        self.seal.clone().send(command).await.map_err(error)?;
+        receiver.await.map_err(error)?.map_err(error)?;
+        Ok(self.clock.last())
+    }
+}
+
+fn error(err: impl ToString) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(1, err.to_string(), None::<()>)
+}
+
+#[async_trait]
+impl DevApiServer for Dev {
+    async fn set_timestamp(&self, timestamp: u64) -> RpcResult<u64> {
+        self.clock.set(timestamp).map_err(error)?;
+        self.seal_block().await
+    }
+
+    async fn advance_time(&self, millis: u64) -> RpcResult<u64> {
+        self.clock.advance(millis);
+        self.seal_block().await
+    }
+}
@@ -0,0 +1,348 @@
+//! The `inspect-rosca` subcommand. It reads `pallet_rosca` storage at a block straight from the
+//! node's database, without RPC, and reports it along with the result of the pallet's
+//! consistency checks. Point `--chain` at a raw chain spec written by `export-state` to inspect
+//! a state snapshot: its genesis block is the exported state.
+//!
+//! Storage is decoded by the runtime code compiled into the node, so the report notes when the
+//! block's runtime or pallet storage version differs from the node's.
+
+use std::sync::Arc;
+
+use frame_support::traits::{GetStorageVersion, StorageVersion};
+use generic_runtime_template::{
+    constants::VERSION, opaque::Block, AccountId, Permill, Rosca, Runtime,
+};
+use pallet_rosca::{Balance, PalletStorage, PaymentAssets, RoscaId, RoscaStorage};
+use parity_scale_codec::Encode;
+use sc_cli::{
+    BlockNumberOrHash, CliConfiguration, DatabaseParams, PruningParams, Result, SharedParams,
+};
+use sc_client_api::Backend;
+use serde_json::{json, Map, Value};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::HashingFor};
+use sp_state_machine::{Ext, OverlayedChanges};
+
+use crate::service::{ParachainBackend, ParachainClient};
+
+/// Report the state of `pallet_rosca` at a block, with the result of its consistency checks.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct InspectRoscaCmd {
+    /// Block to inspect, by hash or number. Defaults to the best block.
+    #[arg(value_name = "HASH or NUMBER")]
+    pub at: Option<BlockNumberOrHash>,
+
+    /// Only report this Rosca. The consistency checks still cover every Rosca.
+    #[arg(long)]
+    pub rosca_id: Option<RoscaId>,
+
+    /// Print the report as JSON.
+    #[arg(long)]
+    pub json: bool,
+
+    #[allow(missing_docs)]
+    #[command(flatten)]
+    pub shared_params: SharedParams,
+
+    #[allow(missing_docs)]
+    #[command(flatten)]
+    pub pruning_params: PruningParams,
+
+    #[allow(missing_docs)]
+    #[command(flatten)]
+    pub database_params: DatabaseParams,
+}
+
+impl InspectRoscaCmd {
+    /// Prints the report. Fails after printing it if a consistency check failed.
+    pub async fn run(
+        &self,
+        client: Arc<ParachainClient>,
+        backend: Arc<ParachainBackend>,
+    ) -> Result<()> {
+        let hash = match self.at.as_ref().map(BlockNumberOrHash::parse::<Block>).transpose()? {
+            Some(BlockId::Hash(hash)) => hash,
+            Some(BlockId::Number(number)) =>
+                client.hash(number)?.ok_or_else(|| format!("Block #{number} not found"))?,
+            None => client.info().best_hash,
+        };
+        let number = client.number(hash)?.ok_or_else(|| format!("Block {hash} not found"))?;
+        let spec_version = client.runtime_version_at(hash)?.spec_version;
+
+        let state = backend.state_at(hash)?;
+        let mut overlay = OverlayedChanges::<HashingFor<Block>>::default();
+        let mut ext = Ext::new(&mut overlay, &state, None);
+        let (mut report, violations) =
+            sp_externalities::set_and_run_with_externalities(&mut ext, || self.report());
+
+        if spec_version != VERSION.spec_version {
+            log::warn!(
+                "Block #{number} runs spec version {spec_version} but this node decodes with {}. \
+                 The report may be wrong.",
+                VERSION.spec_version
+            );
+        }
+        report["block"] = json!({ "number": number, "hash": hash.to_string() });
+        report["runtime"]["specVersion"] = json!(spec_version);
+        report["runtime"]["nodeSpecVersion"] = json!(VERSION.spec_version);
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?);
+        } else {
+            let mut text = String::new();
+            write_text(&mut text, &report, 0);
+            print!("{text}");
+        }
+
+        match violations {
+            0 => Ok(()),
+            n => Err(format!("{n} consistency check(s) failed").into()),
+        }
+    }
+
+    /// The report, to be built with the block's state as externalities, and the number of
+    /// consistency check violations in it.
+    fn report(&self) -> (Value, usize) {
+        let on_chain = Rosca::on_chain_storage_version();
+        let in_code = Rosca::in_code_storage_version();
+        if on_chain != in_code {
+            log::warn!(
+                "pallet_rosca storage is at version {} but this node decodes version {}. The \
+                 report may be wrong.",
+                version_number(on_chain),
+                version_number(in_code)
+            );
+        }
+
+        let pallet = Rosca::pallet_storage();
+        let rosca_ids = match self.rosca_id {
+            Some(rosca_id) => rosca_id..rosca_id.saturating_add(1),
+            None => 0..pallet.next_rosca_id,
+        };
+        let roscas: Vec<Value> =
+            rosca_ids.map(|rosca_id| rosca_json(Rosca::rosca_storage(rosca_id))).collect();
+        let violations: Vec<Value> = Rosca::invariant_violations()
+            .into_iter()
+            .map(|violation| {
+                json!({ "roscaId": violation.rosca_id, "invariant": violation.invariant })
+            })
+            .collect();
+        let count = violations.len();
+
+        let report = json!({
+            "block": null,
+            "runtime": {
+                "specVersion": null,
+                "nodeSpecVersion": null,
+                "storageVersion": version_number(on_chain),
+                "nodeStorageVersion": version_number(in_code),
+            },
+            "pallet": pallet_json(pallet),
+            "roscas": roscas,
+            "consistency": { "ok": count == 0, "violations": violations },
+        });
+        (report, count)
+    }
+}
+
+impl CliConfiguration for InspectRoscaCmd {
+    fn shared_params(&self) -> &SharedParams {
+        &self.shared_params
+    }
+
+    fn pruning_params(&self) -> Option<&PruningParams> {
+        Some(&self.pruning_params)
+    }
+
+    fn database_params(&self) -> Option<&DatabaseParams> {
+        Some(&self.database_params)
+    }
+}
+
+fn version_number(version: StorageVersion) -> u16 {
+    let encoded = version.encode();
+    u16::from_le_bytes([encoded[0], encoded[1]])
+}
+
+fn account(account: &AccountId) -> Value {
+    Value::String(account.to_string())
+}
+
+fn accounts(accounts: &[AccountId]) -> Value {
+    accounts.iter().map(account).collect()
+}
+
+/// A map keyed by account as a JSON object.
+fn by_account<V>(entries: &[(AccountId, V)], value: impl Fn(&V) -> Value) -> Value {
+    let map: Map<String, Value> =
+        entries.iter().map(|(who, entry)| (who.to_string(), value(entry))).collect();
+    Value::Object(map)
+}
+
+fn percent(fee: Permill) -> Value {
+    Value::String(format!("{}%", fee.deconstruct() as f64 / 10_000.0))
+}
+
+/// Balances can exceed what JSON numbers hold exactly, so they're strings.
+fn balance(balance: Balance) -> Value {
+    Value::String(balance.to_string())
+}
+
+fn pallet_json(pallet: PalletStorage) -> Value {
+    let insurance: Map<String, Value> = PaymentAssets::ALL
+        .iter()
+        .map(|asset| {
+            let config =
+                pallet.insurance_configs.iter().find(|(configured, _)| configured == asset);
+            let pool = pallet.insurance_pools.iter().find(|(pooled, _)| pooled == asset);
+            let insurance = json!({
+                "premium": config.map(|(_, config)| percent(config.premium)),
+                "maxClaim": config.map(|(_, config)| balance(config.max_claim)),
+                "pool": balance(pool.map_or(0, |(_, pool)| *pool)),
+            });
+            (format!("{asset:?}"), insurance)
+        })
+        .collect();
+    let asset_fees: Map<String, Value> = pallet
+        .asset_platform_fees
+        .iter()
+        .map(|(asset, fee)| (format!("{asset:?}"), percent(*fee)))
+        .collect();
+    let disallowed: Vec<String> =
+        pallet.disallowed_assets.iter().map(|asset| format!("{asset:?}")).collect();
+
+    json!({
+        "nextRoscaId": pallet.next_rosca_id,
+        "platformFee": percent(pallet.platform_fee),
+        "assetPlatformFees": asset_fees,
+        "disallowedAssets": disallowed,
+        "insurance": insurance,
+    })
+}
+
+fn rosca_json(rosca: RoscaStorage<Runtime>) -> Value {
+    let details = rosca.details.as_ref().map(|details| {
+        json!({
+            "name": String::from_utf8_lossy(&details.name),
+            "randomOrder": details.random_order,
+            "numberOfParticipants": details.number_of_participants,
+            "minimumParticipantThreshold": details.minimum_participant_threshold,
+            "contributionAmount": details.contribution_amount,
+            "paymentAsset": format!("{:?}", details.payment_asset),
+            "contributionFrequency": details.contribution_frequency,
+            "startByTimestamp": details.start_by_timestamp,
+            "guarantor": details.guarantor.as_ref().map(account),
+        })
+    });
+    let proposals: Vec<Value> = rosca
+        .proposals
+        .iter()
+        .map(|(proposal_id, proposal)| {
+            let votes: Map<String, Value> = rosca
+                .proposal_votes
+                .iter()
+                .filter(|(voted_on, _, _)| voted_on == proposal_id)
+                .map(|(_, voter, aye)| (voter.to_string(), Value::Bool(*aye)))
+                .collect();
+            json!({
+                "proposalId": proposal_id,
+                "proposer": account(&proposal.proposer),
+                "action": format!("{:?}", proposal.action),
+                "threshold": format!("{:?}", proposal.threshold),
+                "ayes": proposal.ayes,
+                "nays": proposal.nays,
+                "votingEnds": proposal.voting_ends,
+                "votes": votes,
+            })
+        })
+        .collect();
+
+    json!({
+        "roscaId": rosca.rosca_id,
+        "status": rosca.status.map(|status| format!("{status:?}")),
+        "details": details,
+        "creator": rosca.creator.as_ref().map(account),
+        "account": rosca.account.as_ref().map(account),
+        "invited": accounts(&rosca.invited),
+        "participants": by_account(&rosca.participants, |position| json!(position)),
+        "participantsCount": rosca.participants_count,
+        "securityDeposits": by_account(&rosca.security_deposits, |deposit| json!(deposit)),
+        "pendingOrder": rosca.pending_order.as_ref().map(|order| {
+            order.iter().map(|slot| slot.as_ref().map_or(Value::Null, account)).collect::<Vec<_>>()
+        }),
+        "activeOrder": rosca.active_order.as_deref().map(accounts),
+        "nextPayByTimestamp": rosca.next_pay_by_timestamp,
+        "finalPayByTimestamp": rosca.final_pay_by_timestamp,
+        "eligibleClaimant": rosca.eligible_claimant.as_ref().map(account),
+        "currentContributors": accounts(&rosca.current_contributors),
+        "currentContributionCount": rosca.current_contribution_count,
+        "currentRound": rosca.current_round,
+        "potLedgers": by_account(&rosca.pot_ledgers, |ledger| json!({
+            "paidIn": balance(ledger.paid_in),
+            "received": balance(ledger.received),
+        })),
+        "defaultCounts": by_account(&rosca.default_counts, |defaults| json!(defaults)),
+        "pendingSwaps": by_account(&rosca.pending_swaps, account),
+        "pendingSubstitutions": by_account(&rosca.pending_substitutions, account),
+        "maxDefaults": rosca.max_defaults,
+        "pausedAt": rosca.paused_at,
+        "guaranteeBalance": rosca.guarantee_balance,
+        "guarantorDebts": by_account(&rosca.guarantor_debts, |debt| json!(debt)),
+        "organiserFee": rosca.organiser_fee.map(percent),
+        "sponsorshipBalance": balance(rosca.sponsorship_balance),
+        "sponsoredCalls": by_account(&rosca.sponsored_calls, |(window_start, calls)| {
+            json!({ "windowStart": window_start, "calls": calls })
+        }),
+        "payoutDestinations": by_account(&rosca.payout_destinations, |destination| {
+            json!(format!("{destination:?}"))
+        }),
+        "autopay": accounts(&rosca.autopay),
+        "nextProposalId": rosca.next_proposal_id,
+        "proposals": proposals,
+    })
+}
+
+/// Writes `value` as indented `key: value` lines. Lists of plain values stay on one line and
+/// other list items start with `- `.
+fn write_text(out: &mut String, value: &Value, indent: usize) {
+    let pad = " ".repeat(indent);
+    match value {
+        Value::Object(map) =>
+            for (key, value) in map {
+                match value {
+                    Value::Object(_) | Value::Array(_) if !is_inline(value) => {
+                        out.push_str(&format!("{pad}{key}:\n"));
+                        write_text(out, value, indent + 2);
+                    }
+                    _ => out.push_str(&format!("{pad}{key}: {}\n", inline(value))),
+                }
+            },
+        Value::Array(items) =>
+            for item in items {
+                let mut nested = String::new();
+                write_text(&mut nested, item, indent + 2);
+                out.push_str(&format!("{pad}- {}", &nested[indent + 2..]));
+            },
+        _ => out.push_str(&format!("{pad}{}\n", inline(value))),
+    }
+}
+
+/// Whether `value` fits on one line: a plain value, or an empty or plain-valued list.
+fn is_inline(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => map.is_empty(),
+        Value::Array(items) => items.iter().all(|item| !item.is_object() && !item.is_array()),
+        _ => true,
+    }
+}
+
+fn inline(value: &Value) -> String {
+    match value {
+        Value::Null => "none".into(),
+        Value::String(string) => string.clone(),
+        Value::Array(items) =>
+            format!("[{}]", items.iter().map(inline).collect::<Vec<_>>().join(", ")),
+        Value::Object(_) => "{}".into(),
+        _ => value.to_string(),
+    }
+}
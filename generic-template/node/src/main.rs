@@ -7,6 +7,8 @@ mod chain_spec;
 mod service;
 mod cli;
 mod command;
+mod dev;
+mod inspect;
 mod rpc;
 
 fn main() -> sc_cli::Result<()> {
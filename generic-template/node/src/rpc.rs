@@ -7,14 +7,21 @@
 
 use std::sync::Arc;
 
-use generic_runtime_template::{opaque::Block, AccountId, Balance, Nonce};
+use futures::channel::mpsc;
+use generic_runtime_template::{
+    opaque::{Block, Hash},
+    AccountId, Balance, Nonce,
+};
 use sc_client_api::AuxStore;
+use sc_consensus_manual_seal::EngineCommand;
 pub use sc_rpc::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 
+use crate::dev::DevClock;
+
 /// A type representing all RPC extensions.
 pub type RpcExtension = jsonrpsee::RpcModule<()>;
 
@@ -55,3 +62,33 @@ where
     module.merge(TransactionPayment::new(client).into_rpc())?;
     Ok(module)
 }
+
+/// Instantiate the RPC extensions of a `--dev-seal` node: those of a full node, manual seal's
+/// `engine_*` methods, and the `dev_*` methods that move the chain's clock.
+pub fn create_dev<C, P>(
+    deps: FullDeps<C, P>,
+    clock: DevClock,
+    seal: mpsc::Sender<EngineCommand<Hash>>,
+) -> Result<RpcExtension, Box<dyn std::error::Error + Send + Sync>>
+where
+    C: ProvideRuntimeApi<Block>
+        + HeaderBackend<Block>
+        + AuxStore
+        + HeaderMetadata<Block, Error = BlockChainError>
+        + Send
+        + Sync
+        + 'static,
+    C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+    C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
+    C::Api: BlockBuilder<Block>,
+    P: TransactionPool + Sync + Send + 'static,
+{
+    use sc_consensus_manual_seal::rpc::{ManualSeal, ManualSealApiServer};
+
+    use crate::dev::{Dev, DevApiServer};
+
+    let mut module = create_full(deps)?;
+    module.merge(ManualSeal::new(seal.clone()).into_rpc())?;
+    module.merge(Dev::new(clock, seal).into_rpc())?;
+    Ok(module)
+}
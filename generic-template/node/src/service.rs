@@ -18,29 +18,36 @@ use cumulus_primitives_core::{
     ParaId,
 };
 use cumulus_relay_chain_interface::{OverseerHandle, RelayChainInterface};
+use parity_scale_codec::{Decode, Encode};
 // Substrate Imports
 use frame_benchmarking_cli::SUBSTRATE_REFERENCE_HARDWARE;
 // Local Runtime Types
 use generic_runtime_template::{
     apis::RuntimeApi,
+    constants::RELAY_CHAIN_SLOT_DURATION_MILLIS,
     opaque::{Block, Hash},
 };
-use sc_client_api::Backend;
+use sc_client_api::{Backend, StorageProvider};
 use sc_consensus::ImportQueue;
 use sc_executor::{HeapAllocStrategy, WasmExecutor, DEFAULT_HEAP_ALLOC_STRATEGY};
 use sc_network::NetworkBlock;
 use sc_network_sync::SyncingService;
 use sc_service::{Configuration, PartialComponents, TFullBackend, TFullClient, TaskManager};
 use sc_telemetry::{Telemetry, TelemetryHandle, TelemetryWorker, TelemetryWorkerHandle};
-use sc_transaction_pool_api::OffchainTransactionPoolFactory;
+use sc_transaction_pool_api::{OffchainTransactionPoolFactory, TransactionPool};
+use sp_blockchain::HeaderBackend;
+use sp_consensus_aura::Slot;
+use sp_core::{storage::StorageKey, twox_128};
 use sp_keystore::KeystorePtr;
 use substrate_prometheus_endpoint::Registry;
 
+use crate::{chain_spec, cli::DevSeal, dev::DevClock};
+
 type ParachainExecutor = WasmExecutor<ParachainHostFunctions>;
 
-type ParachainClient = TFullClient<Block, RuntimeApi, ParachainExecutor>;
+pub(crate) type ParachainClient = TFullClient<Block, RuntimeApi, ParachainExecutor>;
 
-type ParachainBackend = TFullBackend<Block>;
+pub(crate) type ParachainBackend = TFullBackend<Block>;
 
 type ParachainBlockImport = TParachainBlockImport<Block, Arc<ParachainClient>, ParachainBackend>;
 
@@ -54,11 +61,28 @@ pub type Service = PartialComponents<
     (ParachainBlockImport, Option<Telemetry>, Option<TelemetryWorkerHandle>),
 >;
 
+/// Builds the import queue of a node.
+type ImportQueueBuilder = fn(
+    Arc<ParachainClient>,
+    ParachainBlockImport,
+    &Configuration,
+    Option<TelemetryHandle>,
+    &TaskManager,
+) -> Result<sc_consensus::DefaultImportQueue<Block>, sc_service::Error>;
+
 /// Starts a `ServiceBuilder` for a full service.
 ///
 /// Use this macro if you don't actually need the full service, but just the builder in order to
 /// be able to perform chain operations.
 pub fn new_partial(config: &Configuration) -> Result<Service, sc_service::Error> {
+    new_partial_with(config, build_import_queue)
+}
+
+/// [`new_partial`] with the import queue made by `build_import_queue`.
+fn new_partial_with(
+    config: &Configuration,
+    build_import_queue: ImportQueueBuilder,
+) -> Result<Service, sc_service::Error> {
     let telemetry = config
         .telemetry_endpoints
         .clone()
@@ -341,6 +365,22 @@ fn build_import_queue(
     ))
 }
 
+/// Build the import queue of a `--dev-seal` node. Its blocks are sealed locally, so they're
+/// imported without verification, and straight into the client so that each becomes the best.
+fn build_dev_import_queue(
+    client: Arc<ParachainClient>,
+    _block_import: ParachainBlockImport,
+    config: &Configuration,
+    _telemetry: Option<TelemetryHandle>,
+    task_manager: &TaskManager,
+) -> Result<sc_consensus::DefaultImportQueue<Block>, sc_service::Error> {
+    Ok(sc_consensus_manual_seal::import_queue(
+        Box::new(client),
+        &task_manager.spawn_essential_handle(),
+        config.prometheus_registry(),
+    ))
+}
+
 fn start_consensus(
     client: Arc<ParachainClient>,
     backend: Arc<ParachainBackend>,
@@ -418,3 +458,169 @@ pub async fn start_parachain_node(
 ) -> sc_service::error::Result<(TaskManager, Arc<ParachainClient>)> {
     start_node_impl(parachain_config, polkadot_config, collator_options, para_id, hwbench).await
 }
+
+/// Timestamp of the best block, from `pallet_timestamp`'s storage.
+fn best_block_timestamp(client: &ParachainClient) -> sc_service::error::Result<u64> {
+    let key = [twox_128(b"Timestamp"), twox_128(b"Now")].concat();
+    let best = client.chain_info().best_hash;
+    match client.storage(best, &StorageKey(key))? {
+        Some(data) => u64::decode(&mut &data.0[..])
+            .map_err(|e| sc_service::Error::Other(format!("Invalid timestamp: {e}"))),
+        None => Ok(0),
+    }
+}
+
+/// Start a standalone node that seals its own blocks, for development.
+///
+/// There's no relay chain: the parachain inherent is mocked to show the parent block included
+/// and a relay chain slot that matches the block's time. Each block is stamped by a [`DevClock`],
+/// which the `dev_setTimestamp` and `dev_advanceTime` RPC methods move ahead.
+pub fn start_dev_node(
+    config: Configuration,
+    seal: DevSeal,
+) -> sc_service::error::Result<TaskManager> {
+    use cumulus_client_parachain_inherent::{MockValidationDataInherentDataProvider, MockXcmConfig};
+    use cumulus_primitives_core::relay_chain::{well_known_keys, HeadData};
+    use futures::{channel::mpsc, StreamExt};
+    use sc_consensus_manual_seal::{
+        consensus::aura::AuraConsensusDataProvider, EngineCommand, ManualSealParams,
+    };
+
+    let params = new_partial_with(&config, build_dev_import_queue)?;
+    let (_, mut telemetry, _) = params.other;
+    let client = params.client.clone();
+    let backend = params.backend.clone();
+    let mut task_manager = params.task_manager;
+    let transaction_pool = params.transaction_pool.clone();
+    let prometheus_registry = config.prometheus_registry().cloned();
+
+    let para_id = chain_spec::Extensions::try_get(&*config.chain_spec)
+        .map(|e| ParaId::from(e.para_id))
+        .ok_or_else(|| sc_service::Error::Other("Could not find parachain ID in chain-spec.".into()))?;
+
+    let net_config = sc_network::config::FullNetworkConfiguration::new(&config.network);
+    let (network, system_rpc_tx, tx_handler_controller, start_network, sync_service) =
+        sc_service::build_network(sc_service::BuildNetworkParams {
+            config: &config,
+            net_config,
+            client: client.clone(),
+            transaction_pool: transaction_pool.clone(),
+            spawn_handle: task_manager.spawn_handle(),
+            import_queue: params.import_queue,
+            block_announce_validator_builder: None,
+            warp_sync_params: None,
+            block_relay: None,
+        })?;
+
+    let clock = DevClock::new(best_block_timestamp(&client)?);
+    let (seal_sink, seal_requests) = mpsc::channel::<EngineCommand<Hash>>(1024);
+    let commands_stream = match seal {
+        DevSeal::Manual => seal_requests.boxed(),
+        DevSeal::Instant => futures::stream::select(
+            seal_requests,
+            transaction_pool.import_notification_stream().map(|_| EngineCommand::SealNewBlock {
+                create_empty: false,
+                finalize: true,
+                parent_hash: None,
+                sender: None,
+            }),
+        )
+        .boxed(),
+    };
+
+    let create_inherent_data_providers = {
+        let client = client.clone();
+        let clock = clock.clone();
+        move |parent: Hash, ()| {
+            let client = client.clone();
+            let clock = clock.clone();
+            async move {
+                let parent_header =
+                    client.header(parent)?.ok_or("Parent block of a dev block not found")?;
+                let timestamp = clock.next_block_timestamp();
+                let relay_slot = Slot::from(timestamp / u64::from(RELAY_CHAIN_SLOT_DURATION_MILLIS));
+
+                let parachain = MockValidationDataInherentDataProvider {
+                    current_para_block: parent_header.number + 1,
+                    relay_offset: 1000,
+                    relay_blocks_per_para_block: 1,
+                    para_blocks_per_relay_epoch: 10,
+                    relay_randomness_config: (),
+                    xcm_config: MockXcmConfig::new(&*client, parent, para_id, Default::default()),
+                    raw_downward_messages: vec![],
+                    raw_horizontal_messages: vec![],
+                    additional_key_values: Some(vec![
+                        (well_known_keys::CURRENT_SLOT.to_vec(), relay_slot.encode()),
+                        (
+                            well_known_keys::para_head(para_id),
+                            HeadData(parent_header.encode()).encode(),
+                        ),
+                    ]),
+                };
+                let timestamp = sp_timestamp::InherentDataProvider::new(timestamp.into());
+                Ok::<_, Box<dyn std::error::Error + Send + Sync>>((timestamp, parachain))
+            }
+        }
+    };
+
+    let proposer = sc_basic_authorship::ProposerFactory::new(
+        task_manager.spawn_handle(),
+        client.clone(),
+        transaction_pool.clone(),
+        prometheus_registry.as_ref(),
+        telemetry.as_ref().map(|t| t.handle()),
+    );
+
+    task_manager.spawn_essential_handle().spawn_blocking(
+        "manual-seal",
+        None,
+        sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+            block_import: client.clone(),
+            env: proposer,
+            client: client.clone(),
+            pool: transaction_pool.clone(),
+            commands_stream,
+            select_chain: sc_consensus::LongestChain::new(backend.clone()),
+            consensus_data_provider: Some(Box::new(AuraConsensusDataProvider::<
+                _,
+                _,
+                sp_consensus_aura::sr25519::AuthorityPair,
+            >::new(client.clone()))),
+            create_inherent_data_providers,
+        }),
+    );
+
+    let rpc_builder = {
+        let client = client.clone();
+        let transaction_pool = transaction_pool.clone();
+
+        Box::new(move |deny_unsafe, _| {
+            let deps = crate::rpc::FullDeps {
+                client: client.clone(),
+                pool: transaction_pool.clone(),
+                deny_unsafe,
+            };
+
+            crate::rpc::create_dev(deps, clock.clone(), seal_sink.clone()).map_err(Into::into)
+        })
+    };
+
+    sc_service::spawn_tasks(sc_service::SpawnTasksParams {
+        rpc_builder,
+        client,
+        transaction_pool,
+        task_manager: &mut task_manager,
+        config,
+        keystore: params.keystore_container.keystore(),
+        backend,
+        network,
+        sync_service,
+        system_rpc_tx,
+        tx_handler_controller,
+        telemetry: telemetry.as_mut(),
+    })?;
+
+    start_network.start_network();
+
+    Ok(task_manager)
+}
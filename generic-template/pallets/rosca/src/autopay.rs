@@ -0,0 +1,95 @@
+//! Autopay. A participant can have their contribution to a Rosca paid automatically. Enabling
+//! autopay schedules a named task with the runtime's scheduler that runs every `AutopayInterval`
+//! blocks and contributes on the participant's behalf when they haven't yet paid the current round.
+//! Funds are pulled from their balance through an approval they give the autopay account with
+//! `approve_transfer` in the Assets pallet, so they keep control of how much can be taken. Failed
+//! attempts emit `AutopayFailed` and are retried at the next run. If the next run can't be
+//! scheduled, autopay is turned off for the participant.
+
+use crate::*;
+use frame_support::pallet_prelude::*;
+use frame_support::traits::Bounded;
+use frame_support::traits::fungibles::approvals::Mutate as ApprovalMutate;
+use frame_support::traits::schedule::{DispatchTime, LOWEST_PRIORITY, v3::TaskName};
+use sp_core::blake2_256;
+
+impl<T: Config> Pallet<T> {
+	/// The account participants approve to spend their contributions.
+	pub fn autopay_account_id() -> T::AccountId {
+		T::PalletId::get().into_sub_account_truncating(b"autopay")
+	}
+
+	fn autopay_task_name(rosca_id: RoscaId, participant: &AccountIdOf<T>) -> TaskName {
+		(b"rosca/autopay", rosca_id, participant).using_encoded(blake2_256)
+	}
+
+	/// Schedules the next autopay attempt for `participant`.
+	pub(crate) fn schedule_autopay(rosca_id: RoscaId, participant: &AccountIdOf<T>) -> DispatchResult {
+		let call: <T as Config>::RuntimeCall = Call::<T>::autopay_contribution {
+			rosca_id,
+			participant: participant.clone()
+		}.into();
+		let call = call.encode().try_into().map_err(|_| Error::<T>::AutopayCallTooLarge)?;
+
+		T::Scheduler::schedule_named(
+			Self::autopay_task_name(rosca_id, participant),
+			DispatchTime::After(T::AutopayInterval::get()),
+			None,
+			LOWEST_PRIORITY,
+			frame_system::RawOrigin::Root.into(),
+			Bounded::Inline(call),
+		)?;
+
+		Ok(())
+	}
+
+	/// Turns autopay off for `participant` and cancels their pending attempt.
+	pub(crate) fn cancel_autopay(rosca_id: RoscaId, participant: &AccountIdOf<T>) {
+		Autopay::<T>::remove(rosca_id, participant);
+		// Nothing is pending while the attempt itself is running.
+		let _ = T::Scheduler::cancel_named(Self::autopay_task_name(rosca_id, participant));
+	}
+
+	/// Whether `participant` still owes a contribution this round.
+	pub(crate) fn autopay_due(rosca_id: RoscaId, participant: &AccountIdOf<T>) -> bool {
+		// Nothing is owed before the Rosca starts, while it's paused or by the current recipient.
+		let Some(eligible_claimant) = Self::eligible_claimant(rosca_id) else {
+			return false;
+		};
+
+		Self::paused_at(rosca_id).is_none() &&
+			eligible_claimant != *participant &&
+			Self::current_contributors(rosca_id, participant).is_none()
+	}
+
+	/// Pulls the contribution and any insurance premium of `participant` into the autopay
+	/// account and contributes from there, returning whatever the contribution didn't spend.
+	/// Overdue rounds are processed first, and nothing is pulled if that completes the Rosca or
+	/// leaves nothing for the participant to pay.
+	pub(crate) fn do_autopay(rosca_id: RoscaId, participant: &AccountIdOf<T>) -> DispatchResult {
+		if Self::catch_up_rounds(rosca_id)? ||
+			Self::participants(rosca_id, participant).is_none() ||
+			!Self::autopay_due(rosca_id, participant)
+		{
+			return Ok(());
+		}
+
+		let rosca = Self::active_roscas(rosca_id).ok_or(Error::<T>::RoscaNotActive)?;
+		let amount: Balance = rosca.contribution_amount.into();
+		let premium = Self::insurance_config(&rosca.payment_asset)
+			.map_or(0, |config| config.premium.mul_floor(amount));
+		let total = amount.checked_add(premium).ok_or(Error::<T>::ArithmeticOverflow)?;
+
+		let autopay_account = Self::autopay_account_id();
+		T::ForeignCurrency::transfer_from(rosca.payment_asset.id(), participant, &autopay_account, &autopay_account, total)?;
+
+		// A premium too small to open the insurance pool's account is waived.
+		let charged = Self::do_contribute(autopay_account.clone(), participant.clone(), rosca_id)?;
+		let unspent = total.saturating_sub(charged);
+		if unspent > 0 {
+			T::ForeignCurrency::transfer(rosca.payment_asset.id(), &autopay_account, participant, unspent, Expendable)?;
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,269 @@
+//! Lightweight per-Rosca governance. Participants of an active Rosca can propose one of the
+//! whitelisted [`ProposalAction`]s and vote on it. A proposal is executed as soon as enough
+//! ayes are cast to meet its [`VoteThreshold`], and rejected once that is no longer possible.
+//! An action that fails is reported in an event and leaves the proposal closed.
+
+use crate::*;
+use frame_support::pallet_prelude::*;
+use frame_support::storage::with_storage_layer;
+use sp_runtime::traits::{CheckedAdd, CheckedSub};
+
+impl<T: Config> Pallet<T> {
+	pub(crate) fn do_propose(
+		rosca_id: RoscaId,
+		proposer: AccountIdOf<T>,
+		action: ProposalAction<T>,
+		threshold: VoteThreshold,
+	) -> DispatchResult {
+		ensure!(Self::participants(rosca_id, &proposer).is_some(), Error::<T>::NotAParticipant);
+		let rosca = Self::active_roscas(rosca_id).ok_or(Error::<T>::RoscaNotActive)?;
+		ensure!(threshold >= action.minimum_threshold(), Error::<T>::ThresholdTooLow);
+
+		let proposal_id = Self::next_proposal_id(rosca_id);
+		let current_timestamp = <pallet_timestamp::Pallet<T>>::get();
+		// Proposals stay open for one contribution period.
+		let voting_ends = current_timestamp.checked_add(&rosca.contribution_frequency).ok_or(Error::<T>::ArithmeticOverflow)?;
+
+		RoscaProposals::<T>::insert(rosca_id, proposal_id, Proposal {
+			proposer: proposer.clone(),
+			action: action.clone(),
+			threshold,
+			ayes: 0,
+			nays: 0,
+			voting_ends,
+		});
+		NextProposalId::<T>::insert(rosca_id, proposal_id.checked_add(1).ok_or(Error::<T>::ArithmeticOverflow)?);
+
+		Self::deposit_event(Event::<T>::ProposalCreated {
+			rosca_id,
+			proposal_id,
+			proposer: proposer.clone(),
+			action,
+			threshold,
+			voting_ends
+		});
+
+		Self::do_vote(rosca_id, proposal_id, proposer, true)
+	}
+
+	pub(crate) fn do_vote(rosca_id: RoscaId, proposal_id: ProposalId, voter: AccountIdOf<T>, aye: bool) -> DispatchResult {
+		ensure!(Self::participants(rosca_id, &voter).is_some(), Error::<T>::NotAParticipant);
+		ensure!(Self::active_roscas(rosca_id).is_some(), Error::<T>::RoscaNotActive);
+		let mut proposal = Self::proposals(rosca_id, proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
+		ensure!(<pallet_timestamp::Pallet<T>>::get() < proposal.voting_ends, Error::<T>::ProposalExpired);
+		ensure!(Self::proposal_votes((rosca_id, proposal_id, &voter)).is_none(), Error::<T>::AlreadyVoted);
+
+		ProposalVotes::<T>::insert((rosca_id, proposal_id, &voter), aye);
+		if aye {
+			proposal.ayes = proposal.ayes.checked_add(1).ok_or(Error::<T>::ArithmeticOverflow)?;
+		} else {
+			proposal.nays = proposal.nays.checked_add(1).ok_or(Error::<T>::ArithmeticOverflow)?;
+		}
+
+		Self::deposit_event(Event::<T>::Voted {
+			rosca_id,
+			proposal_id,
+			voter,
+			aye
+		});
+
+		let electorate = Self::active_rosca_participants_order(rosca_id)
+			.ok_or(Error::<T>::RoscaParticipantsNotFound)?
+			.len() as u32;
+
+		// Some actions also need the creator among the ayes, and fail outright on a creator nay.
+		// Roscas created before creators were recorded fall back to the threshold alone.
+		let creator = Self::rosca_creator(rosca_id);
+		let needs_creator = proposal.action.requires_creator_approval() && creator.is_some();
+		let creator_vote = creator.and_then(|creator| Self::proposal_votes((rosca_id, proposal_id, &creator)));
+		let creator_approved = !needs_creator || creator_vote == Some(true);
+		let creator_rejected = needs_creator && creator_vote == Some(false);
+
+		if creator_approved && proposal.threshold.is_met(proposal.ayes, electorate) {
+			// The vote stands even if the action fails; only the action's own changes are undone.
+			Self::remove_proposal(rosca_id, proposal_id);
+			match with_storage_layer(|| Self::execute_action(rosca_id, proposal.action)) {
+				Ok(()) => Self::deposit_event(Event::<T>::ProposalApproved { rosca_id, proposal_id }),
+				Err(error) => Self::deposit_event(Event::<T>::ProposalExecutionFailed { rosca_id, proposal_id, error }),
+			}
+		} else if creator_rejected || !proposal.threshold.is_met(electorate.saturating_sub(proposal.nays), electorate) {
+			Self::remove_proposal(rosca_id, proposal_id);
+			Self::deposit_event(Event::<T>::ProposalRejected { rosca_id, proposal_id });
+		} else {
+			RoscaProposals::<T>::insert(rosca_id, proposal_id, proposal);
+		}
+
+		Ok(())
+	}
+
+	pub(crate) fn do_close_proposal(rosca_id: RoscaId, proposal_id: ProposalId) -> DispatchResult {
+		let proposal = Self::proposals(rosca_id, proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
+		ensure!(<pallet_timestamp::Pallet<T>>::get() >= proposal.voting_ends, Error::<T>::ProposalStillOpen);
+
+		Self::remove_proposal(rosca_id, proposal_id);
+		Self::deposit_event(Event::<T>::ProposalExpired { rosca_id, proposal_id });
+
+		Ok(())
+	}
+
+	fn remove_proposal(rosca_id: RoscaId, proposal_id: ProposalId) {
+		RoscaProposals::<T>::remove(rosca_id, proposal_id);
+		let _ = ProposalVotes::<T>::clear_prefix((rosca_id, proposal_id), u32::MAX, None);
+	}
+
+	/// Dispatches an approved action.
+	fn execute_action(rosca_id: RoscaId, action: ProposalAction<T>) -> DispatchResult {
+		match action {
+			ProposalAction::ExtendDeadline { extension } => {
+				let next_pay_by_timestamp = Self::next_pay_by_timestamp(rosca_id)
+					.ok_or(Error::<T>::NoNextPayByTimestamp)?
+					.checked_add(&extension)
+					.ok_or(Error::<T>::ArithmeticOverflow)?;
+				let final_pay_by_timestamp = Self::final_pay_by_timestamp(rosca_id)
+					.ok_or(Error::<T>::FinalPayByTimestampNotFound)?
+					.checked_add(&extension)
+					.ok_or(Error::<T>::ArithmeticOverflow)?;
+				NextPayByTimestamp::<T>::insert(rosca_id, next_pay_by_timestamp);
+				FinalPayByTimestamp::<T>::insert(rosca_id, final_pay_by_timestamp);
+
+				Self::deposit_event(Event::<T>::DeadlineExtended {
+					rosca_id,
+					next_payment_cutoff: next_pay_by_timestamp,
+					final_payment_cutoff: final_pay_by_timestamp
+				});
+			},
+			ProposalAction::ForgiveDefault { participant } => {
+				ensure!(Self::participants(rosca_id, &participant).is_some(), Error::<T>::NotAParticipant);
+				let defaults = Self::default_count(rosca_id, &participant);
+				ensure!(defaults > 0, Error::<T>::NoDefaultsToForgive);
+				let remaining_defaults = defaults - 1;
+				DefaultCount::<T>::insert(rosca_id, &participant, remaining_defaults);
+
+				Self::deposit_event(Event::<T>::DefaultForgiven {
+					rosca_id,
+					participant,
+					remaining_defaults
+				});
+			},
+			ProposalAction::EndEarly => {
+				Self::settle_early_end(rosca_id)?;
+				// Pulling the final cutoff forward lets participants claim their deposits.
+				FinalPayByTimestamp::<T>::insert(rosca_id, <pallet_timestamp::Pallet<T>>::get());
+				Self::mark_complete(rosca_id);
+				PausedAt::<T>::remove(rosca_id);
+
+				Self::deposit_event(Event::<T>::RoscaManuallyEnded { rosca_id });
+				Self::deposit_event(Event::<T>::RoscaComplete { rosca_id });
+			},
+			ProposalAction::SubstituteMember { departing, substitute } => {
+				Self::do_nominate_substitute(rosca_id, departing, substitute)?;
+			},
+			ProposalAction::Pause => {
+				ensure!(Self::paused_at(rosca_id).is_none(), Error::<T>::RoscaPaused);
+				let paused_at = <pallet_timestamp::Pallet<T>>::get();
+				let next_pay_by_timestamp = Self::next_pay_by_timestamp(rosca_id).ok_or(Error::<T>::NoNextPayByTimestamp)?;
+				ensure!(paused_at < next_pay_by_timestamp, Error::<T>::RoundCutoffPassed);
+				PausedAt::<T>::insert(rosca_id, paused_at);
+
+				Self::deposit_event(Event::<T>::RoscaPaused { rosca_id, paused_at });
+			},
+			ProposalAction::Resume => {
+				let paused_at = PausedAt::<T>::take(rosca_id).ok_or(Error::<T>::RoscaNotPaused)?;
+				let paused_duration = <pallet_timestamp::Pallet<T>>::get()
+					.checked_sub(&paused_at)
+					.ok_or(Error::<T>::ArithmeticUnderflow)?;
+				let next_pay_by_timestamp = Self::next_pay_by_timestamp(rosca_id)
+					.ok_or(Error::<T>::NoNextPayByTimestamp)?
+					.checked_add(&paused_duration)
+					.ok_or(Error::<T>::ArithmeticOverflow)?;
+				let final_pay_by_timestamp = Self::final_pay_by_timestamp(rosca_id)
+					.ok_or(Error::<T>::FinalPayByTimestampNotFound)?
+					.checked_add(&paused_duration)
+					.ok_or(Error::<T>::ArithmeticOverflow)?;
+				NextPayByTimestamp::<T>::insert(rosca_id, next_pay_by_timestamp);
+				FinalPayByTimestamp::<T>::insert(rosca_id, final_pay_by_timestamp);
+
+				Self::deposit_event(Event::<T>::RoscaResumed {
+					rosca_id,
+					paused_duration,
+					rounds: Self::remaining_schedule(rosca_id)?
+				});
+				// A round paid in full by ejections while paused moves on now.
+				Self::advance_if_round_paid(rosca_id)?;
+			},
+			ProposalAction::EjectMember { participant } => {
+				Self::do_eject(rosca_id, participant)?;
+				Self::advance_if_round_paid(rosca_id)?;
+			},
+		}
+
+		Ok(())
+	}
+
+	/// Settles a Rosca that is ended early, before its deposits become claimable. Going by the
+	/// pot payments recorded for each member, members who have received more than they paid in
+	/// owe the difference. It is taken from their security deposits, as far as they go and no
+	/// further than the total owed, and shared out between the members who paid in more than
+	/// they received, pro rata to what they're owed. The round state is then cleared.
+	fn settle_early_end(rosca_id: RoscaId) -> DispatchResult {
+		let rosca = Self::active_roscas(rosca_id).ok_or(Error::<T>::RoscaNotActive)?;
+		let active_order = Self::active_rosca_participants_order(rosca_id).ok_or(Error::<T>::RoscaParticipantsNotFound)?;
+
+		let mut debts = Vec::new();
+		let mut credits = Vec::new();
+		for member in active_order.iter() {
+			let ledger = Self::pot_ledger(rosca_id, member);
+			if ledger.received > ledger.paid_in {
+				debts.push((member.clone(), ledger.received - ledger.paid_in));
+			} else if ledger.paid_in > ledger.received {
+				credits.push((member.clone(), ledger.paid_in - ledger.received));
+			}
+		}
+		let total_credit: Balance = credits.iter().map(|(_, credit)| credit).sum();
+
+		// Never take more than is owed, so nothing is forfeited when nobody is owed.
+		let mut pool: Balance = 0;
+		for (member, debt) in debts {
+			let deposit = Self::security_deposit(rosca_id, &member).unwrap_or(0);
+			let forfeited = debt.min(deposit.into()).min(total_credit - pool);
+			if forfeited > 0 {
+				RoscaSecurityDeposits::<T>::insert(rosca_id, &member, deposit - forfeited as u32);
+				pool = pool.saturating_add(forfeited);
+			}
+		}
+
+		let mut undistributed = pool;
+		for (index, (member, credit)) in credits.iter().enumerate() {
+			// The last member owed also takes the rounding remainder.
+			let refund = if index == credits.len() - 1 {
+				undistributed
+			} else {
+				pool.checked_mul(*credit).ok_or(Error::<T>::ArithmeticOverflow)? / total_credit
+			};
+			if refund == 0 {
+				continue;
+			}
+			T::ForeignCurrency::transfer(
+				rosca.payment_asset.id(),
+				&Self::rosca_account_id(rosca_id),
+				member,
+				refund,
+				Expendable
+			)?;
+			undistributed -= refund;
+			Self::deposit_event(Event::<T>::EarlyEndRefund {
+				rosca_id,
+				member: member.clone(),
+				amount: refund
+			});
+		}
+
+		EligibleClaimant::<T>::remove(rosca_id);
+		NextPayByTimestamp::<T>::remove(rosca_id);
+		ActiveRoscaParticipantsOrder::<T>::remove(rosca_id);
+		let _ = CurrentContributors::<T>::clear_prefix(rosca_id, u32::MAX, None);
+		CurrentContributionCount::<T>::remove(rosca_id);
+
+		Ok(())
+	}
+}
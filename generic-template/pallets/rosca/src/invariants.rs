@@ -0,0 +1,154 @@
+//! State invariants. The pallet keeps a Rosca's lifecycle, membership and round progress in
+//! several parallel maps, and holds the funds behind deposits, guarantees, insurance pools and
+//! fee sponsorships in its own accounts. `do_try_state` checks that all of these agree. It runs
+//! from the `try_state` hook under try-runtime, after every unit test and in the fuzzer.
+//! `invariant_violations` runs the same checks but reports every violation, for the node's
+//! offline state inspection.
+
+use crate::*;
+use frame_support::traits::fungible::Inspect as NativeInspect;
+use frame_support::traits::fungibles::Inspect as AssetInspect;
+use scale_info::prelude::collections::BTreeSet;
+
+/// A broken invariant, naming the Rosca it was found in and what failed.
+#[derive(PartialEq, Eq, RuntimeDebug)]
+pub struct InvariantViolation {
+	/// The Rosca whose state is inconsistent, `None` for pallet-wide state
+	pub rosca_id: Option<RoscaId>,
+	pub invariant: &'static str,
+}
+
+impl From<InvariantViolation> for DispatchError {
+	fn from(violation: InvariantViolation) -> Self {
+		DispatchError::Other(violation.invariant)
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Checks every invariant of the pallet's state, stopping at the first violation.
+	pub fn do_try_state() -> Result<(), InvariantViolation> {
+		match Self::invariant_violations().into_iter().next() {
+			Some(violation) => Err(violation),
+			None => Ok(()),
+		}
+	}
+
+	/// Checks every invariant of the pallet's state, returning every violation found. Checking a
+	/// Rosca stops at its first violation.
+	pub fn invariant_violations() -> Vec<InvariantViolation> {
+		let mut violations = Vec::new();
+
+		let next_rosca_id = Self::next_rosca_id();
+		let unknown = PendingRoscaDetails::<T>::iter_keys()
+			.chain(ActiveRoscas::<T>::iter_keys())
+			.chain(CompletedRoscas::<T>::iter_keys())
+			.filter(|rosca_id| *rosca_id >= next_rosca_id);
+		for rosca_id in unknown {
+			violations.push(InvariantViolation {
+				rosca_id: Some(rosca_id),
+				invariant: "Rosca ids are below NextRoscaId"
+			});
+		}
+
+		for rosca_id in 0..next_rosca_id {
+			if let Err(invariant) = Self::try_state_rosca(rosca_id) {
+				violations.push(InvariantViolation { rosca_id: Some(rosca_id), invariant });
+			}
+		}
+
+		for (asset, pool) in InsurancePools::<T>::iter() {
+			if T::ForeignCurrency::balance(asset.id(), &Self::insurance_account_id(&asset)) < pool {
+				violations.push(InvariantViolation {
+					rosca_id: None,
+					invariant: "the insurance account holds the insurance pool"
+				});
+			}
+		}
+
+		violations
+	}
+
+	fn try_state_rosca(rosca_id: RoscaId) -> Result<(), &'static str> {
+		let pending = Self::rosca_details(rosca_id);
+		let active = Self::active_roscas(rosca_id);
+		let completed = Self::completed_roscas(rosca_id).is_some();
+		let completed_details = Self::completed_rosca_details(rosca_id);
+
+		let states = pending.is_some() as u8 + active.is_some() as u8 + completed as u8;
+		ensure!(states == 1, "a Rosca is exactly one of pending, active or completed");
+		// Roscas completed before storage version 1 kept no details.
+		ensure!(completed || completed_details.is_none(), "only completed Roscas have CompletedRoscaDetails");
+		ensure!(active.is_some() || Self::paused_at(rosca_id).is_none(), "only active Roscas are paused");
+
+		let participants: Vec<(AccountIdOf<T>, u32)> = RoscaParticipants::<T>::iter_prefix(rosca_id).collect();
+		ensure!(Self::participants_count(rosca_id) == Some(participants.len() as u32), "RoscaParticipantsCount matches RoscaParticipants");
+		ensure!(
+			participants.iter().all(|(who, _)| Self::invited_preverified_participants(rosca_id, who).is_some()),
+			"participants are invited"
+		);
+
+		if let Some(rosca) = &pending {
+			let order = Self::pending_rosca_participants_order(rosca_id).ok_or("pending Roscas have a participant order")?;
+			ensure!(order.len() as u32 == rosca.number_of_participants, "the pending order has a slot per possible participant");
+			ensure!(order.iter().flatten().count() == participants.len(), "the pending order holds only participants");
+			ensure!(
+				participants.iter().all(|(who, position)| order.get(*position as usize) == Some(&Some(who.clone()))),
+				"participants sit at their position in the pending order"
+			);
+			ensure!(Self::eligible_claimant(rosca_id).is_none(), "pending Roscas have no recipient");
+			ensure!(Self::current_contribution_count(rosca_id) == 0, "pending Roscas have no contributions");
+		}
+
+		if active.is_some() {
+			let order = Self::active_rosca_participants_order(rosca_id).ok_or("active Roscas have a participant order")?;
+			let members: BTreeSet<&AccountIdOf<T>> = order.iter().collect();
+			ensure!(
+				members.len() == order.len() &&
+					order.len() == participants.len() &&
+					order.iter().all(|who| Self::participants(rosca_id, who).is_some()),
+				"the active order holds each participant once"
+			);
+
+			let eligible_claimant = Self::eligible_claimant(rosca_id).ok_or("active Roscas have a recipient")?;
+			ensure!(Self::current_round(rosca_id) > 0, "active Roscas count their rounds from 1");
+			ensure!(order.first() == Some(&eligible_claimant), "the recipient heads the active order");
+
+			let next_pay_by_timestamp = Self::next_pay_by_timestamp(rosca_id).ok_or("active Roscas have a next cutoff")?;
+			let final_pay_by_timestamp = Self::final_pay_by_timestamp(rosca_id).ok_or("active Roscas have a final cutoff")?;
+			ensure!(next_pay_by_timestamp <= final_pay_by_timestamp, "the next cutoff is no later than the final cutoff");
+
+			let contributors: Vec<AccountIdOf<T>> = CurrentContributors::<T>::iter_key_prefix(rosca_id).collect();
+			ensure!(
+				Self::current_contribution_count(rosca_id) == contributors.len() as u32,
+				"CurrentContributionCount matches CurrentContributors"
+			);
+			ensure!(
+				contributors.iter().all(|who| Self::participants(rosca_id, who).is_some() && *who != eligible_claimant),
+				"contributors are participants other than the recipient"
+			);
+		}
+
+		// Deposits and the guarantee are held in the Rosca account.
+		let mut held: Balance = Self::guarantee_balance(rosca_id).into();
+		for (who, deposit) in RoscaSecurityDeposits::<T>::iter_prefix(rosca_id) {
+			ensure!(deposit == 0 || Self::participants(rosca_id, &who).is_some(), "security deposits belong to participants");
+			held = held.saturating_add(deposit.into());
+		}
+		if let Some(rosca) = pending.or(active).or(completed_details) {
+			ensure!(
+				T::ForeignCurrency::balance(rosca.payment_asset.id(), &Self::rosca_account_id(rosca_id)) >= held,
+				"the Rosca account holds the security deposits and guarantee"
+			);
+		}
+
+		let sponsored = Self::sponsorship_balance(rosca_id);
+		ensure!(
+			sponsored == 0 ||
+				T::NativeCurrency::balance(&Self::sponsorship_account_id(rosca_id)) >=
+					sponsored.saturating_add(T::NativeCurrency::minimum_balance()),
+			"the sponsorship account holds the sponsorship balance and its existential deposit"
+		);
+
+		Ok(())
+	}
+}
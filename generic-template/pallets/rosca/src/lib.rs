@@ -9,32 +9,51 @@ pub use pallet::*;
 
 pub mod types;
 
+mod autopay;
+mod governance;
+mod invariants;
+pub mod migrations;
+mod sponsorship;
+mod views;
+
+pub use invariants::InvariantViolation;
+
 #[cfg(test)]
 mod mock;
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(test)]
+mod simulation;
+
 
 use frame_support::pallet_prelude::DispatchResult;
 use scale_info::prelude::vec::Vec;
+use scale_info::prelude::boxed::Box;
+use frame_support::storage::with_storage_layer;
 
-use sp_runtime::traits::{SaturatedConversion, AccountIdConversion, CheckedAdd, CheckedMul};
+use sp_runtime::traits::{SaturatedConversion, AccountIdConversion, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Saturating};
+use sp_runtime::Permill;
+use xcm::VersionedLocation;
 use frame_support::traits::{Currency, ReservableCurrency, WithdrawReasons, ExistenceRequirement, fungible, fungibles};
 use frame_support::traits::fungible::Mutate;
 use frame_support::traits::fungibles::Mutate as FungibleMutate;
 use frame_support::PalletId;
-use frame_support::traits::tokens::Preservation::Expendable;
+use frame_support::traits::tokens::Preservation::{Expendable, Preserve};
 use frame_support::ensure;
 
 use sp_core::blake2_128;
 
 use frame_support::traits::Randomness;
+use frame_support::traits::schedule::v3::Named as ScheduleNamed;
 
 pub use types::*;
 
 type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 
+const LOG_TARGET: &str = "runtime::rosca";
+
 pub type Balance = u128;
 
 use frame_support::traits::Get;
@@ -52,9 +71,67 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// Payment assets Roscas may be created in. Every asset is allowed if this is empty.
+		pub allowed_assets: Vec<PaymentAssets>,
+		/// Time the Roscas below are created and started at. The chain's first block must not
+		/// come before it. Must be set if there are any Roscas.
+		pub genesis_timestamp: u64,
+		/// Roscas set up at genesis, in id order
+		pub roscas: Vec<GenesisRosca<AccountIdOf<T>>>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			if !self.allowed_assets.is_empty() {
+				for asset in PaymentAssets::ALL {
+					if !self.allowed_assets.contains(&asset) {
+						DisallowedAssets::<T>::insert(&asset, ());
+					}
+				}
+			}
+
+			// Roscas are set up through their calls, at the genesis time. Left at zero, their
+			// start-by times and cutoffs would all have passed by the first block.
+			assert!(
+				self.roscas.is_empty() || self.genesis_timestamp != 0,
+				"genesis_timestamp must be set to set up genesis Roscas"
+			);
+			let now = pallet_timestamp::Now::<T>::get();
+			pallet_timestamp::Now::<T>::put(self.genesis_timestamp.saturated_into::<T::Moment>());
+			for rosca in &self.roscas {
+				Pallet::<T>::build_genesis_rosca(rosca).expect("genesis Roscas are valid");
+			}
+			pallet_timestamp::Now::<T>::put(now);
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			Self::do_try_state().map_err(|violation| {
+				log::error!(
+					target: LOG_TARGET,
+					"Rosca {:?} broke an invariant: {}",
+					violation.rosca_id,
+					violation.invariant
+				);
+				violation.into()
+			})
+		}
+	}
+
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	pub trait Config: frame_system::Config + pallet_timestamp::Config
@@ -66,7 +143,8 @@ pub mod pallet {
 			+ fungibles::metadata::Inspect<AccountIdOf<Self>, AssetId = u32>
 			+ fungibles::metadata::Mutate<AccountIdOf<Self>, AssetId = u32>
 			+ fungibles::Mutate<AccountIdOf<Self>, Balance = Balance>
-			+ fungibles::Inspect<AccountIdOf<Self>, Balance = Balance>;
+			+ fungibles::Inspect<AccountIdOf<Self>, Balance = Balance>
+			+ fungibles::approvals::Mutate<AccountIdOf<Self>>;
 
 		/// Maximum number of participants in a single ROSCA
 		#[pallet::constant]
@@ -80,6 +158,44 @@ pub mod pallet {
 
 		#[pallet::constant]
 		type StringLimit: Get<u32>;
+
+		/// Origin allowed to change pallet-wide settings such as the insurance pools
+		type AdminOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
+
+		/// Account that receives the platform fee taken from contributions, e.g. the Treasury
+		type FeeDestination: Get<AccountIdOf<Self>>;
+
+		/// Highest organiser fee a Rosca creator may charge
+		#[pallet::constant]
+		type MaxOrganiserFee: Get<Permill>;
+
+		/// Highest share of a contribution taken by the platform and organiser fees combined
+		#[pallet::constant]
+		type MaxFee: Get<Permill>;
+
+		/// Native currency that transaction fees, and so fee sponsorships, are paid in
+		type NativeCurrency: fungible::Mutate<AccountIdOf<Self>, Balance = Balance>;
+
+		/// Number of calls a member can have sponsored in a single round
+		#[pallet::constant]
+		type MaxSponsoredCallsPerRound: Get<u32>;
+
+		/// Delivers payouts to participants who chose a destination on another chain
+		type PayoutSender: PayoutSender<AccountIdOf<Self>>;
+
+		/// The overarching call type, used to schedule autopay contributions
+		type RuntimeCall: From<Call<Self>> + Encode;
+
+		/// The caller origin of the runtime, used to schedule autopay contributions
+		type PalletsOrigin: From<frame_system::RawOrigin<AccountIdOf<Self>>>;
+
+		/// Scheduler that runs autopay contributions
+		type Scheduler: ScheduleNamed<BlockNumberFor<Self>, <Self as Config>::RuntimeCall, Self::PalletsOrigin>;
+
+		/// Blocks between autopay attempts. Should be well below the contribution frequency so
+		/// every round is paid before its cutoff.
+		#[pallet::constant]
+		type AutopayInterval: Get<BlockNumberFor<Self>>;
 	}
 
 	/// The next Rosca id
@@ -97,6 +213,11 @@ pub mod pallet {
 	#[pallet::getter(fn completed_roscas)]
 	pub(super) type CompletedRoscas<T> = StorageMap<_, Blake2_128Concat, RoscaId, (), OptionQuery>;
 
+	// Details of a completed Rosca, kept for claims made after completion
+	#[pallet::storage]
+	#[pallet::getter(fn completed_rosca_details)]
+	pub(super) type CompletedRoscaDetails<T: Config> = StorageMap<_, Blake2_128Concat, RoscaId, RoscaDetails<T>, OptionQuery>;
+
 
 	// Mapping of Rosca Id and AccountId returning their prestart position index. This index could become inaccurate once the Rosca starts if 
 	// the Rosca starts with less than the max of participants. Once the Rosca is active this Map should be used for membership checks only.
@@ -166,11 +287,126 @@ pub mod pallet {
 	#[pallet::getter(fn current_contribution_count)]
 	pub type CurrentContributionCount<T: Config> = StorageMap<_, Blake2_128Concat, RoscaId, u32, ValueQuery>;
 
+	// Number of the round an active Rosca is in, counting from 1. Ejecting a participant who was
+	// already paid out shortens the order but not the rounds, so round numbers come from here.
+	#[pallet::storage]
+	#[pallet::getter(fn current_round)]
+	pub type CurrentRound<T: Config> = StorageMap<_, Blake2_128Concat, RoscaId, u32, ValueQuery>;
+
+	// Double Map of rosca_id, participant => what they've paid into and received from the pots
+	#[pallet::storage]
+	#[pallet::getter(fn pot_ledger)]
+	pub type PotLedgers<T: Config> = StorageDoubleMap<_, Blake2_128Concat, RoscaId, Blake2_128Concat, AccountIdOf<T>, PotLedger, ValueQuery>;
+
 	// Counter for number of defaults by a participant
 	#[pallet::storage]
 	#[pallet::getter(fn default_count)]
 	pub type DefaultCount<T: Config> = StorageDoubleMap<_, Blake2_128Concat, RoscaId, Blake2_128Concat, AccountIdOf<T>, u32, ValueQuery>;
 
+	// Double Map of rosca_id, proposer => counterparty for payout position swaps awaiting acceptance
+	#[pallet::storage]
+	#[pallet::getter(fn pending_swaps)]
+	pub type PendingSwaps<T: Config> = StorageDoubleMap<_, Blake2_128Concat, RoscaId, Blake2_128Concat, AccountIdOf<T>, AccountIdOf<T>, OptionQuery>;
+
+	// Double Map of rosca_id, departing participant => nominated substitute awaiting acceptance
+	#[pallet::storage]
+	#[pallet::getter(fn pending_substitutions)]
+	pub type PendingSubstitutions<T: Config> = StorageDoubleMap<_, Blake2_128Concat, RoscaId, Blake2_128Concat, AccountIdOf<T>, AccountIdOf<T>, OptionQuery>;
+
+	// The account that created a given rosca id
+	#[pallet::storage]
+	#[pallet::getter(fn rosca_creator)]
+	pub type RoscaCreator<T: Config> = StorageMap<_, Blake2_128Concat, RoscaId, AccountIdOf<T>, OptionQuery>;
+
+	// Number of defaults a participant may accumulate before being ejected. No limit when unset
+	#[pallet::storage]
+	#[pallet::getter(fn max_defaults)]
+	pub type MaxDefaults<T: Config> = StorageMap<_, Blake2_128Concat, RoscaId, u32, OptionQuery>;
+
+	// The timestamp at which a paused Rosca was paused
+	#[pallet::storage]
+	#[pallet::getter(fn paused_at)]
+	pub type PausedAt<T: Config> = StorageMap<_, Blake2_128Concat, RoscaId, <T as pallet_timestamp::Config>::Moment, OptionQuery>;
+
+	// Funds the guarantor of a Rosca has pre-funded into the Rosca account and not yet drawn or claimed
+	#[pallet::storage]
+	#[pallet::getter(fn guarantee_balance)]
+	pub type GuaranteeBalances<T: Config> = StorageMap<_, Blake2_128Concat, RoscaId, u32, ValueQuery>;
+
+	// Amount a defaulter owes the guarantor of a Rosca for covered contributions
+	#[pallet::storage]
+	#[pallet::getter(fn guarantor_debt)]
+	pub type GuarantorDebts<T: Config> = StorageDoubleMap<_, Blake2_128Concat, RoscaId, Blake2_128Concat, AccountIdOf<T>, u32, ValueQuery>;
+
+	// Insurance premium rate and coverage cap per payment asset. Insurance is off for assets without one
+	#[pallet::storage]
+	#[pallet::getter(fn insurance_config)]
+	pub type InsuranceConfigs<T: Config> = StorageMap<_, Blake2_128Concat, PaymentAssets, InsuranceConfig, OptionQuery>;
+
+	// Funds available in the insurance pool of a payment asset
+	#[pallet::storage]
+	#[pallet::getter(fn insurance_pool)]
+	pub type InsurancePools<T: Config> = StorageMap<_, Blake2_128Concat, PaymentAssets, Balance, ValueQuery>;
+
+	// Platform fee taken from every contribution, unless its asset has an override
+	#[pallet::storage]
+	#[pallet::getter(fn platform_fee)]
+	pub type PlatformFee<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+	// Per-asset overrides of the platform fee
+	#[pallet::storage]
+	#[pallet::getter(fn asset_platform_fee)]
+	pub type AssetPlatformFees<T: Config> = StorageMap<_, Blake2_128Concat, PaymentAssets, Permill, OptionQuery>;
+
+	// Fee the creator of a Rosca takes from every contribution as organiser
+	#[pallet::storage]
+	#[pallet::getter(fn organiser_fee)]
+	pub type OrganiserFees<T: Config> = StorageMap<_, Blake2_128Concat, RoscaId, Permill, OptionQuery>;
+
+	// Native balance the creator of a Rosca prepaid to cover its members' transaction fees
+	#[pallet::storage]
+	#[pallet::getter(fn sponsorship_balance)]
+	pub type SponsorshipBalances<T: Config> = StorageMap<_, Blake2_128Concat, RoscaId, Balance, ValueQuery>;
+
+	// Sponsored calls of a member and the payment cutoff of the round they were made in
+	#[pallet::storage]
+	#[pallet::getter(fn sponsored_calls)]
+	pub type SponsoredCalls<T: Config> = StorageDoubleMap<_, Blake2_128Concat, RoscaId, Blake2_128Concat, AccountIdOf<T>, (Option<<T as pallet_timestamp::Config>::Moment>, u32), OptionQuery>;
+
+	// Where a participant of a Rosca wants their payouts delivered, if not to their account here
+	#[pallet::storage]
+	#[pallet::getter(fn payout_destination)]
+	pub type PayoutDestinations<T: Config> = StorageDoubleMap<_, Blake2_128Concat, RoscaId, Blake2_128Concat, AccountIdOf<T>, VersionedLocation, OptionQuery>;
+
+	// Participants of a Rosca whose contributions are paid automatically
+	#[pallet::storage]
+	#[pallet::getter(fn autopay)]
+	pub type Autopay<T: Config> = StorageDoubleMap<_, Blake2_128Concat, RoscaId, Blake2_128Concat, AccountIdOf<T>, (), OptionQuery>;
+
+	// Payment assets new Roscas can't be created in
+	#[pallet::storage]
+	#[pallet::getter(fn disallowed_asset)]
+	pub type DisallowedAssets<T: Config> = StorageMap<_, Blake2_128Concat, PaymentAssets, (), OptionQuery>;
+
+	// The next proposal id for a given rosca id
+	#[pallet::storage]
+	#[pallet::getter(fn next_proposal_id)]
+	pub type NextProposalId<T: Config> = StorageMap<_, Blake2_128Concat, RoscaId, ProposalId, ValueQuery>;
+
+	// Open governance proposals of a Rosca
+	#[pallet::storage]
+	#[pallet::getter(fn proposals)]
+	pub type RoscaProposals<T: Config> = StorageDoubleMap<_, Blake2_128Concat, RoscaId, Blake2_128Concat, ProposalId, Proposal<T>, OptionQuery>;
+
+	// Votes cast on an open proposal, aye = true
+	#[pallet::storage]
+	#[pallet::getter(fn proposal_votes)]
+	pub type ProposalVotes<T: Config> = StorageNMap<_, (
+		NMapKey<Blake2_128Concat, RoscaId>,
+		NMapKey<Blake2_128Concat, ProposalId>,
+		NMapKey<Blake2_128Concat, AccountIdOf<T>>,
+	), bool, OptionQuery>;
+
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -195,12 +431,15 @@ pub mod pallet {
 			unpaid_recipient: AccountIdOf<T>,
 			defaulter: AccountIdOf<T>
 		},
-		/// Participant made a contribution
+		/// Participant made a contribution. `payer` differs from `contributor` when paid on their behalf
 		ContributionMade {
 			rosca_id: RoscaId,
 			contributor: AccountIdOf<T>,
 			recipient: AccountIdOf<T>,
 			amount: Balance,
+			payer: AccountIdOf<T>,
+			platform_fee: Balance,
+			organiser_fee: Balance,
 		},
 		/// A Rosca deposit was deducted from
 		DepositDeducted {
@@ -208,7 +447,9 @@ pub mod pallet {
 			contributor: AccountIdOf<T>,
 			recipient: AccountIdOf<T>,
 			amount: Balance,
-			sufficient: bool
+			sufficient: bool,
+			platform_fee: Balance,
+			organiser_fee: Balance,
 		},
 		/// Participant joined the Rosca
 		JoinedRosca {
@@ -232,11 +473,12 @@ pub mod pallet {
 		RoscaComplete {
 			rosca_id: RoscaId,
 		},
-		/// A Security Deposit was added to
+		/// A Security Deposit was added to. `payer` differs from `depositor` when paid on their behalf
 		SecurityDepositContribution {
 			rosca_id: RoscaId,
 			depositor: AccountIdOf<T>,
-			amount: Balance
+			amount: Balance,
+			payer: AccountIdOf<T>
 		},
 		/// A Security Deposit was claimed back
 		SecurityDepositClaimed {
@@ -253,6 +495,245 @@ pub mod pallet {
 			rosca_id: RoscaId,
 			new_eligible_recipient: AccountIdOf<T>,
 			payment_cutoff: T::Moment
+		},
+		/// A participant proposed to swap payout positions
+		SwapProposed {
+			rosca_id: RoscaId,
+			proposer: AccountIdOf<T>,
+			counterparty: AccountIdOf<T>
+		},
+		/// Two participants swapped payout positions. `rounds` holds the updated data of the affected rounds
+		PositionsSwapped {
+			rosca_id: RoscaId,
+			proposer: AccountIdOf<T>,
+			counterparty: AccountIdOf<T>,
+			rounds: RoscaRounds<T>
+		},
+		/// A departing participant nominated a substitute
+		SubstituteNominated {
+			rosca_id: RoscaId,
+			departing: AccountIdOf<T>,
+			substitute: AccountIdOf<T>
+		},
+		/// A substitute took over a participant's position, obligations and deposit.
+		/// `inherited_debt` is the value of the contributions still owed for a pot that was already paid out,
+		/// and `debt_deposit` what the substitute added to the transferred deposit to secure it
+		ParticipantSubstituted {
+			rosca_id: RoscaId,
+			departing: AccountIdOf<T>,
+			substitute: AccountIdOf<T>,
+			already_received: bool,
+			outstanding_contributions: u32,
+			inherited_debt: Balance,
+			deposit_transferred: Balance,
+			debt_deposit: Balance
+		},
+		/// A governance proposal was created
+		ProposalCreated {
+			rosca_id: RoscaId,
+			proposal_id: ProposalId,
+			proposer: AccountIdOf<T>,
+			action: ProposalAction<T>,
+			threshold: VoteThreshold,
+			voting_ends: T::Moment
+		},
+		/// A participant voted on a proposal
+		Voted {
+			rosca_id: RoscaId,
+			proposal_id: ProposalId,
+			voter: AccountIdOf<T>,
+			aye: bool
+		},
+		/// A proposal reached its threshold and its action was executed
+		ProposalApproved {
+			rosca_id: RoscaId,
+			proposal_id: ProposalId
+		},
+		/// A proposal met its threshold but its action failed, so it was closed without effect
+		ProposalExecutionFailed {
+			rosca_id: RoscaId,
+			proposal_id: ProposalId,
+			error: DispatchError
+		},
+		/// A proposal can no longer reach its threshold
+		ProposalRejected {
+			rosca_id: RoscaId,
+			proposal_id: ProposalId
+		},
+		/// A proposal was closed after its voting period ended
+		ProposalExpired {
+			rosca_id: RoscaId,
+			proposal_id: ProposalId
+		},
+		/// The current and remaining payment cutoffs were pushed back
+		DeadlineExtended {
+			rosca_id: RoscaId,
+			next_payment_cutoff: T::Moment,
+			final_payment_cutoff: T::Moment
+		},
+		/// A recorded default was forgiven
+		DefaultForgiven {
+			rosca_id: RoscaId,
+			participant: AccountIdOf<T>,
+			remaining_defaults: u32
+		},
+		/// A Rosca was paused, contributions and default processing are frozen
+		RoscaPaused {
+			rosca_id: RoscaId,
+			paused_at: T::Moment
+		},
+		/// A Rosca was resumed. `rounds` holds the shifted schedule of the remaining rounds
+		RoscaResumed {
+			rosca_id: RoscaId,
+			paused_duration: T::Moment,
+			rounds: RoscaRounds<T>
+		},
+		/// The maximum number of defaults before ejection was set
+		MaxDefaultsSet {
+			rosca_id: RoscaId,
+			max_defaults: Option<u32>
+		},
+		/// A participant was ejected. `forfeited_to` holds each recipient's share of the forfeited
+		/// deposit and `rounds` the recomputed schedule of the remaining rounds
+		ParticipantEjected {
+			rosca_id: RoscaId,
+			participant: AccountIdOf<T>,
+			defaults: u32,
+			forfeited_deposit: Balance,
+			forfeited_to: Vec<(AccountIdOf<T>, Balance)>,
+			slot_dropped: bool,
+			final_payment_cutoff: T::Moment,
+			rounds: RoscaRounds<T>
+		},
+		/// The insurance settings of a payment asset were changed
+		InsuranceConfigSet {
+			asset: PaymentAssets,
+			config: Option<InsuranceConfig>
+		},
+		/// An insurance premium was paid into the pool alongside a contribution
+		InsurancePremiumPaid {
+			rosca_id: RoscaId,
+			payer: AccountIdOf<T>,
+			asset: PaymentAssets,
+			amount: Balance
+		},
+		/// The insurance pool topped up a recipient after a defaulter's deposit fell short
+		InsuranceClaimPaid {
+			rosca_id: RoscaId,
+			defaulter: AccountIdOf<T>,
+			recipient: AccountIdOf<T>,
+			asset: PaymentAssets,
+			shortfall: Balance,
+			amount: Balance
+		},
+		/// A guarantor was set for a pending Rosca
+		GuarantorSet {
+			rosca_id: RoscaId,
+			guarantor: Option<AccountIdOf<T>>
+		},
+		/// The guarantor pre-funded the guarantee
+		GuaranteeFunded {
+			rosca_id: RoscaId,
+			guarantor: AccountIdOf<T>,
+			amount: Balance
+		},
+		/// The guarantee covered a defaulted contribution, which the defaulter now owes the guarantor
+		GuaranteeDrawn {
+			rosca_id: RoscaId,
+			guarantor: AccountIdOf<T>,
+			defaulter: AccountIdOf<T>,
+			recipient: AccountIdOf<T>,
+			amount: Balance
+		},
+		/// A defaulter repaid the guarantor
+		GuarantorRepaid {
+			rosca_id: RoscaId,
+			guarantor: AccountIdOf<T>,
+			debtor: AccountIdOf<T>,
+			amount: Balance,
+			remaining_debt: Balance
+		},
+		/// The undrawn guarantee was returned to the guarantor, on their claim or on being replaced
+		GuaranteeClaimed {
+			rosca_id: RoscaId,
+			guarantor: AccountIdOf<T>,
+			amount: Balance
+		},
+		/// The platform fee was changed, for one asset or as the default when `asset` is `None`
+		PlatformFeeSet {
+			asset: Option<PaymentAssets>,
+			fee: Option<Permill>
+		},
+		/// The creator of a Rosca set its organiser fee
+		OrganiserFeeSet {
+			rosca_id: RoscaId,
+			fee: Option<Permill>
+		},
+		/// The creator of a Rosca added to its fee sponsorship
+		SponsorshipFunded {
+			rosca_id: RoscaId,
+			amount: Balance,
+			balance: Balance
+		},
+		/// The creator of a Rosca withdrew from its fee sponsorship
+		SponsorshipWithdrawn {
+			rosca_id: RoscaId,
+			amount: Balance,
+			balance: Balance
+		},
+		/// The fee sponsorship of a Rosca paid a participant's transaction fee
+		FeeSponsored {
+			rosca_id: RoscaId,
+			participant: AccountIdOf<T>,
+			fee: Balance
+		},
+		/// A participant set or cleared where their payouts are delivered
+		PayoutDestinationSet {
+			rosca_id: RoscaId,
+			participant: AccountIdOf<T>,
+			destination: Option<VersionedLocation>
+		},
+		/// A payout was sent to the recipient's destination on another chain
+		RemotePayoutSent {
+			rosca_id: RoscaId,
+			recipient: AccountIdOf<T>,
+			destination: VersionedLocation,
+			amount: Balance
+		},
+		/// A payout couldn't be sent to the recipient's destination and was paid to their account here
+		RemotePayoutFailed {
+			rosca_id: RoscaId,
+			recipient: AccountIdOf<T>,
+			amount: Balance,
+			error: DispatchError
+		},
+		/// A participant turned on autopay for their contributions
+		AutopayEnabled {
+			rosca_id: RoscaId,
+			participant: AccountIdOf<T>
+		},
+		/// Autopay was turned off, by the participant or because they no longer take part in the Rosca
+		AutopayDisabled {
+			rosca_id: RoscaId,
+			participant: AccountIdOf<T>
+		},
+		/// Autopay couldn't pay a participant's contribution. It's retried at the next attempt.
+		AutopayFailed {
+			rosca_id: RoscaId,
+			participant: AccountIdOf<T>,
+			error: DispatchError
+		},
+		/// New Roscas may or may no longer be created in a payment asset
+		AssetAllowedSet {
+			asset: PaymentAssets,
+			allowed: bool
+		},
+		/// A member owed contributions when the Rosca was ended early was refunded from the
+		/// deposits of members who had received more than they paid in
+		EarlyEndRefund {
+			rosca_id: RoscaId,
+			member: AccountIdOf<T>,
+			amount: Balance
 		}
 	}
 	// Errors inform users that something went wrong.
@@ -323,7 +804,65 @@ pub mod pallet {
 		/// Rosca still active - can't claim security deposit
 		RoscaStillActive,
 		/// Too many Rosca Rounds
-		TooManyRounds
+		TooManyRounds,
+		/// Can't swap positions with self
+		CantSwapWithSelf,
+		/// No swap proposed between these participants
+		SwapNotProposed,
+		/// Participant has already received, or is currently receiving, the pot
+		PositionAlreadyPaidOut,
+		/// The current round's cutoff has passed and the Rosca must be advanced first
+		RoundCutoffPassed,
+		/// Substitute is already a participant in this Rosca
+		SubstituteAlreadyParticipant,
+		/// No substitution nominated for this participant
+		SubstitutionNotNominated,
+		/// Proposal threshold is lower than the action requires
+		ThresholdTooLow,
+		/// Proposal with given id not found
+		ProposalNotFound,
+		/// Participant already voted on this proposal
+		AlreadyVoted,
+		/// Proposal voting period has ended
+		ProposalExpired,
+		/// Proposal voting period has not ended yet
+		ProposalStillOpen,
+		/// Participant has no defaults to forgive
+		NoDefaultsToForgive,
+		/// Rosca is paused
+		RoscaPaused,
+		/// Rosca is not paused
+		RoscaNotPaused,
+		/// Only the Rosca creator can do this
+		NotRoscaCreator,
+		/// The current recipient can't be ejected
+		CantEjectRecipient,
+		/// Rosca has no guarantor
+		NoGuarantor,
+		/// Only the Rosca guarantor can do this
+		NotGuarantor,
+		/// Guarantee balance is zero
+		GuaranteeIsZero,
+		/// Organiser fee is above the allowed maximum
+		OrganiserFeeTooHigh,
+		/// The fee sponsorship can't pay for this call
+		SponsorshipUnavailable,
+		/// Not enough funds in the fee sponsorship
+		InsufficientSponsorship,
+		/// Nothing is owed to the guarantor
+		NoDebtToGuarantor,
+		/// Autopay is already on for this participant
+		AutopayAlreadyEnabled,
+		/// Autopay is not on for this participant
+		AutopayNotEnabled,
+		/// The autopay call is too large to schedule
+		AutopayCallTooLarge,
+		/// Roscas can't be created in this payment asset
+		AssetNotAllowed,
+		/// The current recipient can't be substituted until their round is over
+		CantSubstituteRecipient,
+		/// The platform and organiser fees together are above the allowed maximum
+		FeeTooHigh
 	}
 
 
@@ -336,6 +875,7 @@ pub mod pallet {
 			ensure!(contribution_frequency > T::Moment::from(0u32), Error::<T>::FrequencyMustBePositive);
 			ensure!(T::MaxInvitedParticipants::get() < T::MaxParticipants::get(), Error::<T>::ArithmeticError);
 			ensure!(!invited_pre_verified_participants.contains(&signer), Error::<T>::CantInviteSelf);
+			ensure!(Self::disallowed_asset(&payment_asset).is_none(), Error::<T>::AssetNotAllowed);
 			let mut invited_pre_verified_participants = invited_pre_verified_participants.into_inner();
 			invited_pre_verified_participants.sort();
 			invited_pre_verified_participants.dedup();
@@ -371,6 +911,7 @@ pub mod pallet {
 			PendingRoscaParticipantsOrder::<T>::insert(new_rosca_id, rosca_participants);
 			RoscaParticipants::<T>::insert(new_rosca_id, &signer, position);
 			RoscaParticipantsCount::<T>::insert(new_rosca_id, 1);
+			RoscaCreator::<T>::insert(new_rosca_id, &signer);
 
 			PendingRoscaDetails::<T>::insert(new_rosca_id, RoscaDetails {
 				random_order,
@@ -380,7 +921,8 @@ pub mod pallet {
 				payment_asset: payment_asset.clone(),
 				contribution_frequency,
 				start_by_timestamp,
-				name: name.clone()
+				name: name.clone(),
+				guarantor: None
 			});
 
 			<NextRoscaId<T>>::put(new_rosca_id + 1);
@@ -540,6 +1082,7 @@ pub mod pallet {
 
 			NextPayByTimestamp::<T>::insert(rosca_id, next_pay_by_timestamp);
 			FinalPayByTimestamp::<T>::insert(rosca_id, final_pay_by_timestamp);
+			CurrentRound::<T>::insert(rosca_id, 1);
 
 			ActiveRoscas::<T>::insert(rosca_id, pending_rosca);	
 			PendingRoscaDetails::<T>::remove(rosca_id);
@@ -559,97 +1102,15 @@ pub mod pallet {
 
 		#[pallet::call_index(4)]
 		pub fn contribute_to_rosca(origin: OriginFor<T>, rosca_id: RoscaId) -> DispatchResult {
-
 			let signer = ensure_signed(origin)?;
-			ensure!(Self::participants(rosca_id, &signer).is_some(), Error::<T>::NotAParticipant);
-			ensure!(Self::completed_roscas(rosca_id).is_none(), Error::<T>::RoscaAlreadyCompleted);
-			let rosca = Self::active_roscas(rosca_id).ok_or(Error::<T>::RoscaNotActive)?;
-			let mut eligible_claimant = Self::eligible_claimant(rosca_id).ok_or(Error::<T>::NoEligbleClaimant)?;
-			
-			ensure!(eligible_claimant != signer, Error::<T>::CantContributeToSelf);
-			ensure!(Self::current_contributors(rosca_id, &signer).is_none(), Error::<T>::AlreadyContributed);
-
-			let current_timestamp = <pallet_timestamp::Pallet<T>>::get();
-			let final_pay_by_timestamp = Self::final_pay_by_timestamp(rosca_id).ok_or(Error::<T>::FinalPayByTimestampNotFound)?;
-			let mut next_pay_by_timestamp = Self::next_pay_by_timestamp(rosca_id).ok_or(Error::<T>::NoNextPayByTimestamp)?;
-
-			let mut active_rosca_participants_order = Self::active_rosca_participants_order(rosca_id).ok_or(Error::<T>::RoscaParticipantsNotFound)?;
-
-			let rosca_account_id = Self::rosca_account_id(rosca_id);
-
-
-			while current_timestamp >= next_pay_by_timestamp {
-				// Process any missed contributions for the current round.
-				Self::process_defaulters(rosca_id)?;
-				// Advance the round: update timing, rotate the order, and clear contributions.
-				Self::advance_rosca_round(rosca_id)?;
-				// Check if, after advancing, the ROSCA should be marked complete.
-				if Self::check_and_complete_rosca(rosca_id)? {
-					return Ok(());
-				}
-				// Update local variable for the loop condition.
-				next_pay_by_timestamp = Self::next_pay_by_timestamp(rosca_id)
-					.ok_or(Error::<T>::NoNextPayByTimestamp)?;
-				active_rosca_participants_order = Self::active_rosca_participants_order(rosca_id)
-					.ok_or(Error::<T>::RoscaParticipantsNotFound)?;
-				eligible_claimant = Self::eligible_claimant(rosca_id)
-					.ok_or(Error::<T>::NoEligbleClaimant)?;
-			}
-
-			// If we are here we must have caught up to the current round
-			
-			T::ForeignCurrency::transfer(rosca.payment_asset.id(), &signer, &eligible_claimant, rosca.contribution_amount.into(), Expendable)?;
-			CurrentContributors::<T>::insert(rosca_id, &signer, ());
-			let current_contribution_count = Self::current_contribution_count(rosca_id).checked_add(1).ok_or(Error::<T>::ArithmeticOverflow)?;
-			CurrentContributionCount::<T>::insert(rosca_id, current_contribution_count);
-
-			Self::deposit_event(Event::<T>::ContributionMade {
-				rosca_id,
-				contributor: signer.clone(),
-				recipient: eligible_claimant.clone(),
-				amount: rosca.contribution_amount.into(),
-			});
-
-
-			if current_contribution_count == (active_rosca_participants_order.len() - 1) as u32 {
-				// This means it's the final contribution for the round so we can progress
-
-				next_pay_by_timestamp = next_pay_by_timestamp.checked_add(&rosca.contribution_frequency).ok_or(Error::<T>::ArithmeticOverflow)?;
-
-				if next_pay_by_timestamp > final_pay_by_timestamp {
-					// Means it was the final contribution of the final round
-					CompletedRoscas::<T>::insert(rosca_id, ());
-					ActiveRoscas::<T>::remove(rosca_id);
-					Self::deposit_event(Event::<T>::RoscaComplete {
-						rosca_id,
-					});
-					return Ok(());
-				}
-
-				NextPayByTimestamp::<T>::insert(rosca_id, next_pay_by_timestamp);
-
-				eligible_claimant = active_rosca_participants_order[active_rosca_participants_order.len() - 1 as usize].clone();
-				EligibleClaimant::<T>::insert(rosca_id, &eligible_claimant);
-				active_rosca_participants_order.try_rotate_right(1).map_err(|_| Error::<T>::ArithmeticError)?;
-				ActiveRoscaParticipantsOrder::<T>::insert(rosca_id, active_rosca_participants_order.clone());
-				CurrentContributors::<T>::clear_prefix(rosca_id, (active_rosca_participants_order.len() - 1) as u32, None);
-				CurrentContributionCount::<T>::insert(rosca_id, 0);
-
-				Self::deposit_event(Event::<T>::NewRoundStarted {
-					rosca_id,
-					new_eligible_recipient: eligible_claimant.clone(),
-					payment_cutoff: next_pay_by_timestamp
-				});
-			}
-
-			
-			Ok(())
+			Self::do_contribute(signer.clone(), signer, rosca_id).map(|_| ())
 		}
 
 		#[pallet::call_index(5)]
 		pub fn manually_end_rosca(origin: OriginFor<T>, rosca_id: RoscaId) -> DispatchResult {
 			let _signer = ensure_signed(origin)?;
-			let rosca = Self::active_roscas(rosca_id).ok_or(Error::<T>::RoscaNotActive)?;
+			ensure!(Self::active_roscas(rosca_id).is_some(), Error::<T>::RoscaNotActive);
+			ensure!(Self::paused_at(rosca_id).is_none(), Error::<T>::RoscaPaused);
 			let current_timestamp = <pallet_timestamp::Pallet<T>>::get();
 			let final_pay_by_timestamp = Self::final_pay_by_timestamp(rosca_id)
 				.ok_or(Error::<T>::FinalPayByTimestampNotFound)?;
@@ -657,30 +1118,14 @@ pub mod pallet {
 			// Ensure the final pay-by timestamp is in the past.
 			ensure!(current_timestamp > final_pay_by_timestamp, Error::<T>::FinalPayByTimestampMustBePast);
 
-			// Process rounds until the next payment timestamp exceeds the final pay-by timestamp.
-			while let Some(next_pay_by) = Self::next_pay_by_timestamp(rosca_id) {
-				if next_pay_by <= final_pay_by_timestamp {
-					// Process missed contributions and update round state.
-					Self::process_defaulters(rosca_id)?;
-					Self::advance_rosca_round(rosca_id)?;
-				} else {
-					break;
-				}
+			// Process rounds until the next payment timestamp passes the final one, which marks
+			// the ROSCA complete. Ejecting a defaulter can bring the final cutoff forward, so it
+			// is read again after every round.
+			while !Self::check_and_complete_rosca(rosca_id)? {
+				// Process missed contributions and update round state.
+				Self::process_defaulters(rosca_id)?;
+				Self::advance_rosca_round(rosca_id)?;
 			}
-
-			// Check that we've processed the final round:
-			let next_pay_by = Self::next_pay_by_timestamp(rosca_id)
-				.ok_or(Error::<T>::NoNextPayByTimestamp)?;
-			ensure!(
-				next_pay_by == final_pay_by_timestamp.checked_add(&rosca.contribution_frequency)
-					.ok_or(Error::<T>::ArithmeticOverflow)?,
-				Error::<T>::ArithmeticError
-			);
-
-			// Mark the ROSCA as complete.
-			CompletedRoscas::<T>::insert(rosca_id, ());
-			ActiveRoscas::<T>::remove(rosca_id);
-			Self::deposit_event(Event::<T>::RoscaComplete { rosca_id });
 			Ok(())
 		}
 
@@ -717,51 +1162,526 @@ pub mod pallet {
 		#[pallet::call_index(7)]
 		pub fn add_to_security_deposit(origin: OriginFor<T>, rosca_id: RoscaId, amount: u32) -> DispatchResult {
 			let signer = ensure_signed(origin)?;
-			ensure!(Self::participants(rosca_id, &signer).is_some(), Error::<T>::NotAParticipant);
-			ensure!(Self::completed_roscas(rosca_id).is_none(), Error::<T>::RoscaAlreadyCompleted);
-			let rosca_account_id = Self::rosca_account_id(rosca_id);
-			let rosca = Self::active_roscas(rosca_id)
-				.or_else(|| Self::rosca_details(rosca_id))
-				.ok_or_else(|| Error::<T>::RoscaNotFound)?;
-			T::ForeignCurrency::transfer(
-					rosca.payment_asset.id(), 
-					&signer, 
-					&rosca_account_id, 
-					amount.into(), 
-					Expendable
-				)?;
-			let mut participant_deposit = Self::security_deposit(rosca_id, &signer).unwrap_or(0);
-			let new_deposit_balance = participant_deposit.checked_add(amount).ok_or(Error::<T>::ArithmeticOverflow)?;
-			RoscaSecurityDeposits::<T>::insert(rosca_id, &signer, new_deposit_balance);
-			Self::deposit_event(Event::<T>::SecurityDepositContribution {
-				rosca_id,
-				depositor: signer,
-				amount: amount.into()
-			});
-			Ok(())
+			Self::do_add_to_security_deposit(signer.clone(), signer, rosca_id, amount)
 		}
-	}
-}
 
-impl<T: Config> Pallet<T> {
-	pub fn rosca_account_id(rosca_id: RoscaId) -> T::AccountId {
-		T::PalletId::get().into_sub_account_truncating(rosca_id)
-	}
+		/// Pay the current round contribution on behalf of another participant.
+		/// The contribution is credited to `participant`, the signer pays for it.
+		#[pallet::call_index(8)]
+		pub fn contribute_for(origin: OriginFor<T>, rosca_id: RoscaId, participant: AccountIdOf<T>) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			Self::do_contribute(signer, participant, rosca_id).map(|_| ())
+		}
 
-	pub fn shuffle_participants(participants: &mut BoundedVec<AccountIdOf<T>, T::MaxParticipants>) {
-		// Fisher-Yates Shuffle
-		let current_block = <frame_system::Pallet<T>>::block_number();
-		let block_hash = <frame_system::Pallet<T>>::block_hash(current_block);
-		let truncated_seed = blake2_128(block_hash.as_ref()); // Truncate to 16 bytes for the shuffle
-		
-		let mut rng_seed = truncated_seed;
+		/// Top up the security deposit of another participant.
+		/// The deposit is credited to `participant`, the signer pays for it.
+		#[pallet::call_index(9)]
+		pub fn add_to_security_deposit_for(origin: OriginFor<T>, rosca_id: RoscaId, participant: AccountIdOf<T>, amount: u32) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			Self::do_add_to_security_deposit(signer, participant, rosca_id, amount)
+		}
 
-		let mut available_indices: Vec<usize> = (0..participants.len()).collect(); 
-		let mut shuffled_indices = Vec::with_capacity(participants.len());
+		/// Propose to swap payout positions with `counterparty`.
+		/// Both participants must still be waiting to receive the pot.
+		#[pallet::call_index(10)]
+		pub fn propose_swap(origin: OriginFor<T>, rosca_id: RoscaId, counterparty: AccountIdOf<T>) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			ensure!(signer != counterparty, Error::<T>::CantSwapWithSelf);
+			Self::future_swap_positions(rosca_id, &signer, &counterparty)?;
 
-		while !available_indices.is_empty() {
+			PendingSwaps::<T>::insert(rosca_id, &signer, &counterparty);
 
-			let mut rng_seed = &blake2_128(&rng_seed[..]);
+			Self::deposit_event(Event::<T>::SwapProposed {
+				rosca_id,
+				proposer: signer,
+				counterparty
+			});
+
+			Ok(())
+		}
+
+		/// Accept a swap proposed by `proposer`, exchanging both payout positions.
+		#[pallet::call_index(11)]
+		pub fn accept_swap(origin: OriginFor<T>, rosca_id: RoscaId, proposer: AccountIdOf<T>) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			let counterparty = Self::pending_swaps(rosca_id, &proposer).ok_or(Error::<T>::SwapNotProposed)?;
+			ensure!(counterparty == signer, Error::<T>::SwapNotProposed);
+			let (proposer_index, counterparty_index) = Self::future_swap_positions(rosca_id, &proposer, &signer)?;
+
+			let mut active_order = Self::active_rosca_participants_order(rosca_id).ok_or(Error::<T>::RoscaParticipantsNotFound)?;
+			let proposer_account = active_order[proposer_index].clone();
+			active_order[proposer_index] = active_order[counterparty_index].clone();
+			active_order[counterparty_index] = proposer_account;
+			ActiveRoscaParticipantsOrder::<T>::insert(rosca_id, active_order.clone());
+			PendingSwaps::<T>::remove(rosca_id, &proposer);
+
+			// Higher indices in the order are paid out first.
+			let mut rounds: RoscaRounds<T> = BoundedVec::new();
+			for index in [proposer_index.max(counterparty_index), proposer_index.min(counterparty_index)] {
+				let round = Self::round_info_at(rosca_id, &active_order, index)?;
+				rounds.try_push(round).map_err(|_| Error::<T>::TooManyRounds)?;
+			}
+
+			Self::deposit_event(Event::<T>::PositionsSwapped {
+				rosca_id,
+				proposer,
+				counterparty: signer,
+				rounds
+			});
+
+			Ok(())
+		}
+
+		/// Nominate `substitute` to take over the signer's position in an active Rosca.
+		#[pallet::call_index(12)]
+		pub fn nominate_substitute(origin: OriginFor<T>, rosca_id: RoscaId, substitute: AccountIdOf<T>) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			Self::do_nominate_substitute(rosca_id, signer, substitute)
+		}
+
+		/// Accept a nomination from `departing`, taking over their position, obligations and deposit.
+		/// If the position was already paid out, the signer tops up its deposit to cover what it still owes.
+		#[pallet::call_index(13)]
+		pub fn accept_substitution(origin: OriginFor<T>, rosca_id: RoscaId, departing: AccountIdOf<T>) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			let substitute = Self::pending_substitutions(rosca_id, &departing).ok_or(Error::<T>::SubstitutionNotNominated)?;
+			ensure!(substitute == signer, Error::<T>::SubstitutionNotNominated);
+			Self::do_substitute(rosca_id, departing, signer)
+		}
+
+		/// Propose an admin action for an active Rosca. The proposer votes aye automatically.
+		#[pallet::call_index(14)]
+		pub fn propose(origin: OriginFor<T>, rosca_id: RoscaId, action: ProposalAction<T>, threshold: VoteThreshold) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			Self::do_propose(rosca_id, signer, action, threshold)
+		}
+
+		/// Vote on an open proposal. The action is executed as soon as the threshold is met.
+		#[pallet::call_index(15)]
+		pub fn vote(origin: OriginFor<T>, rosca_id: RoscaId, proposal_id: ProposalId, aye: bool) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			Self::do_vote(rosca_id, proposal_id, signer, aye)
+		}
+
+		/// Remove a proposal whose voting period has ended without reaching its threshold.
+		#[pallet::call_index(16)]
+		pub fn close_proposal(origin: OriginFor<T>, rosca_id: RoscaId, proposal_id: ProposalId) -> DispatchResult {
+			let _signer = ensure_signed(origin)?;
+			Self::do_close_proposal(rosca_id, proposal_id)
+		}
+
+		/// Set how many defaults a participant may accumulate before being ejected.
+		/// Only the creator can set it, and only before the Rosca starts.
+		#[pallet::call_index(17)]
+		pub fn set_max_defaults(origin: OriginFor<T>, rosca_id: RoscaId, max_defaults: Option<u32>) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			ensure!(Self::active_roscas(rosca_id).is_none(), Error::<T>::RoscaAlreadyActive);
+			ensure!(Self::rosca_details(rosca_id).is_some(), Error::<T>::RoscaNotFound);
+			ensure!(Self::rosca_creator(rosca_id) == Some(signer), Error::<T>::NotRoscaCreator);
+
+			match max_defaults {
+				Some(max_defaults) => MaxDefaults::<T>::insert(rosca_id, max_defaults),
+				None => MaxDefaults::<T>::remove(rosca_id),
+			}
+
+			Self::deposit_event(Event::<T>::MaxDefaultsSet {
+				rosca_id,
+				max_defaults
+			});
+
+			Ok(())
+		}
+
+		/// Set or clear the insurance premium rate and coverage cap of a payment asset.
+		#[pallet::call_index(18)]
+		pub fn set_insurance_config(origin: OriginFor<T>, asset: PaymentAssets, config: Option<InsuranceConfig>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			match config.clone() {
+				Some(config) => InsuranceConfigs::<T>::insert(&asset, config),
+				None => InsuranceConfigs::<T>::remove(&asset),
+			}
+
+			Self::deposit_event(Event::<T>::InsuranceConfigSet {
+				asset,
+				config
+			});
+
+			Ok(())
+		}
+
+		/// Set or clear the guarantor of a pending Rosca. Only the creator can do this. A guarantor
+		/// who is replaced gets back what they funded.
+		#[pallet::call_index(19)]
+		pub fn set_guarantor(origin: OriginFor<T>, rosca_id: RoscaId, guarantor: Option<AccountIdOf<T>>) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			ensure!(Self::active_roscas(rosca_id).is_none(), Error::<T>::RoscaAlreadyActive);
+			let mut pending_rosca = Self::rosca_details(rosca_id).ok_or(Error::<T>::RoscaNotFound)?;
+			ensure!(Self::rosca_creator(rosca_id) == Some(signer), Error::<T>::NotRoscaCreator);
+
+			let replaced = pending_rosca.guarantor.clone().filter(|previous| guarantor.as_ref() != Some(previous));
+			if let Some(previous) = replaced {
+				if Self::guarantee_balance(rosca_id) > 0 {
+					Self::return_guarantee(rosca_id, &pending_rosca, &previous)?;
+				}
+			}
+
+			pending_rosca.guarantor = guarantor.clone();
+			PendingRoscaDetails::<T>::insert(rosca_id, pending_rosca);
+
+			Self::deposit_event(Event::<T>::GuarantorSet {
+				rosca_id,
+				guarantor
+			});
+
+			Ok(())
+		}
+
+		/// Pre-fund the guarantee of a Rosca. Only its guarantor can do this.
+		#[pallet::call_index(20)]
+		pub fn fund_guarantee(origin: OriginFor<T>, rosca_id: RoscaId, amount: u32) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			ensure!(Self::completed_roscas(rosca_id).is_none(), Error::<T>::RoscaAlreadyCompleted);
+			let rosca = Self::active_roscas(rosca_id)
+				.or_else(|| Self::rosca_details(rosca_id))
+				.ok_or(Error::<T>::RoscaNotFound)?;
+			ensure!(rosca.guarantor.as_ref() == Some(&signer), Error::<T>::NotGuarantor);
+
+			T::ForeignCurrency::transfer(rosca.payment_asset.id(), &signer, &Self::rosca_account_id(rosca_id), amount.into(), Expendable)?;
+			let new_balance = Self::guarantee_balance(rosca_id).checked_add(amount).ok_or(Error::<T>::ArithmeticOverflow)?;
+			GuaranteeBalances::<T>::insert(rosca_id, new_balance);
+
+			Self::deposit_event(Event::<T>::GuaranteeFunded {
+				rosca_id,
+				guarantor: signer,
+				amount: amount.into()
+			});
+
+			Ok(())
+		}
+
+		/// Repay the guarantor for contributions the guarantee covered for the signer.
+		#[pallet::call_index(21)]
+		pub fn repay_guarantor(origin: OriginFor<T>, rosca_id: RoscaId, amount: u32) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			let rosca = Self::active_roscas(rosca_id)
+				.or_else(|| Self::rosca_details(rosca_id))
+				.or_else(|| Self::completed_rosca_details(rosca_id))
+				.ok_or(Error::<T>::RoscaNotFound)?;
+			let guarantor = rosca.guarantor.ok_or(Error::<T>::NoGuarantor)?;
+			let debt = Self::guarantor_debt(rosca_id, &signer);
+			ensure!(debt > 0, Error::<T>::NoDebtToGuarantor);
+			let amount = amount.min(debt);
+
+			T::ForeignCurrency::transfer(rosca.payment_asset.id(), &signer, &guarantor, amount.into(), Expendable)?;
+			let remaining_debt = debt - amount;
+			if remaining_debt == 0 {
+				GuarantorDebts::<T>::remove(rosca_id, &signer);
+			} else {
+				GuarantorDebts::<T>::insert(rosca_id, &signer, remaining_debt);
+			}
+
+			Self::deposit_event(Event::<T>::GuarantorRepaid {
+				rosca_id,
+				guarantor,
+				debtor: signer,
+				amount: amount.into(),
+				remaining_debt: remaining_debt.into()
+			});
+
+			Ok(())
+		}
+
+		/// Claim back the undrawn guarantee once the Rosca has completed, or while it hasn't
+		/// started, including after its start-by time has passed.
+		#[pallet::call_index(22)]
+		pub fn claim_guarantee(origin: OriginFor<T>, rosca_id: RoscaId) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			ensure!(Self::active_roscas(rosca_id).is_none(), Error::<T>::RoscaNotCompleted);
+			let rosca = Self::rosca_details(rosca_id)
+				.or_else(|| Self::completed_rosca_details(rosca_id))
+				.ok_or(Error::<T>::RoscaNotFound)?;
+			ensure!(rosca.guarantor.as_ref() == Some(&signer), Error::<T>::NotGuarantor);
+			ensure!(Self::guarantee_balance(rosca_id) > 0, Error::<T>::GuaranteeIsZero);
+
+			Self::return_guarantee(rosca_id, &rosca, &signer)
+		}
+
+		/// Set the platform fee taken from contributions. With an `asset` this sets or clears that
+		/// asset's override, otherwise it sets the default for all assets.
+		#[pallet::call_index(23)]
+		pub fn set_platform_fee(origin: OriginFor<T>, asset: Option<PaymentAssets>, fee: Option<Permill>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			if let Some(fee) = fee {
+				ensure!(fee <= T::MaxFee::get(), Error::<T>::FeeTooHigh);
+			}
+
+			match (&asset, fee) {
+				(Some(asset), Some(fee)) => AssetPlatformFees::<T>::insert(asset, fee),
+				(Some(asset), None) => AssetPlatformFees::<T>::remove(asset),
+				(None, Some(fee)) => PlatformFee::<T>::put(fee),
+				(None, None) => PlatformFee::<T>::kill(),
+			}
+
+			Self::deposit_event(Event::<T>::PlatformFeeSet {
+				asset,
+				fee
+			});
+
+			Ok(())
+		}
+
+		/// Set the organiser fee the creator takes from every contribution to a pending Rosca.
+		#[pallet::call_index(24)]
+		pub fn set_organiser_fee(origin: OriginFor<T>, rosca_id: RoscaId, fee: Option<Permill>) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			ensure!(Self::active_roscas(rosca_id).is_none(), Error::<T>::RoscaAlreadyActive);
+			let rosca = Self::rosca_details(rosca_id).ok_or(Error::<T>::RoscaNotFound)?;
+			ensure!(Self::rosca_creator(rosca_id) == Some(signer), Error::<T>::NotRoscaCreator);
+
+			match fee {
+				Some(fee) => {
+					ensure!(fee <= T::MaxOrganiserFee::get(), Error::<T>::OrganiserFeeTooHigh);
+					ensure!(
+						Self::platform_fee_for(&rosca.payment_asset).saturating_add(fee) <= T::MaxFee::get(),
+						Error::<T>::FeeTooHigh
+					);
+					OrganiserFees::<T>::insert(rosca_id, fee);
+				},
+				None => OrganiserFees::<T>::remove(rosca_id),
+			}
+
+			Self::deposit_event(Event::<T>::OrganiserFeeSet {
+				rosca_id,
+				fee
+			});
+
+			Ok(())
+		}
+
+		/// Prepay transaction fees for the members of a Rosca. Only the creator can do this.
+		/// The first funding keeps the existential deposit in the sponsorship account, outside the budget.
+		#[pallet::call_index(25)]
+		pub fn fund_sponsorship(origin: OriginFor<T>, rosca_id: RoscaId, amount: Balance) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			ensure!(Self::completed_roscas(rosca_id).is_none(), Error::<T>::RoscaAlreadyCompleted);
+			ensure!(Self::rosca_creator(rosca_id) == Some(signer.clone()), Error::<T>::NotRoscaCreator);
+
+			let account = Self::sponsorship_account_id(rosca_id);
+			let kept_alive = T::NativeCurrency::minimum_balance().saturating_sub(T::NativeCurrency::balance(&account));
+			T::NativeCurrency::transfer(&signer, &account, amount, Expendable)?;
+			let balance = Self::sponsorship_balance(rosca_id)
+				.checked_add(amount.saturating_sub(kept_alive))
+				.ok_or(Error::<T>::ArithmeticOverflow)?;
+			SponsorshipBalances::<T>::insert(rosca_id, balance);
+
+			Self::deposit_event(Event::<T>::SponsorshipFunded {
+				rosca_id,
+				amount,
+				balance
+			});
+
+			Ok(())
+		}
+
+		/// Withdraw unused funds from the fee sponsorship of a Rosca. Only the creator can do this.
+		#[pallet::call_index(26)]
+		pub fn withdraw_sponsorship(origin: OriginFor<T>, rosca_id: RoscaId, amount: Balance) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			ensure!(Self::rosca_creator(rosca_id) == Some(signer.clone()), Error::<T>::NotRoscaCreator);
+			let balance = Self::sponsorship_balance(rosca_id)
+				.checked_sub(amount)
+				.ok_or(Error::<T>::InsufficientSponsorship)?;
+
+			T::NativeCurrency::transfer(&Self::sponsorship_account_id(rosca_id), &signer, amount, Preserve)?;
+			SponsorshipBalances::<T>::insert(rosca_id, balance);
+
+			Self::deposit_event(Event::<T>::SponsorshipWithdrawn {
+				rosca_id,
+				amount,
+				balance
+			});
+
+			Ok(())
+		}
+
+		/// Choose a location on another chain, e.g. an Asset Hub account, to receive payouts at.
+		/// Payouts that can't be sent there are paid to the participant's account on this chain.
+		#[pallet::call_index(27)]
+		pub fn set_payout_destination(origin: OriginFor<T>, rosca_id: RoscaId, destination: Option<Box<VersionedLocation>>) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			ensure!(Self::completed_roscas(rosca_id).is_none(), Error::<T>::RoscaAlreadyCompleted);
+			ensure!(Self::participants(rosca_id, &signer).is_some(), Error::<T>::NotAParticipant);
+
+			let destination = destination.map(|destination| *destination);
+			match &destination {
+				Some(destination) => PayoutDestinations::<T>::insert(rosca_id, &signer, destination),
+				None => PayoutDestinations::<T>::remove(rosca_id, &signer),
+			}
+
+			Self::deposit_event(Event::<T>::PayoutDestinationSet {
+				rosca_id,
+				participant: signer,
+				destination
+			});
+
+			Ok(())
+		}
+
+		/// Have your contribution to a Rosca paid automatically every round. Funds are taken through
+		/// an approval for the autopay account, given with `approve_transfer` in the Assets pallet.
+		#[pallet::call_index(28)]
+		pub fn enable_autopay(origin: OriginFor<T>, rosca_id: RoscaId) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			ensure!(Self::completed_roscas(rosca_id).is_none(), Error::<T>::RoscaAlreadyCompleted);
+			ensure!(Self::participants(rosca_id, &signer).is_some(), Error::<T>::NotAParticipant);
+			ensure!(Self::autopay(rosca_id, &signer).is_none(), Error::<T>::AutopayAlreadyEnabled);
+
+			Self::schedule_autopay(rosca_id, &signer)?;
+			Autopay::<T>::insert(rosca_id, &signer, ());
+
+			Self::deposit_event(Event::<T>::AutopayEnabled {
+				rosca_id,
+				participant: signer
+			});
+
+			Ok(())
+		}
+
+		/// Stop paying your contribution to a Rosca automatically.
+		#[pallet::call_index(29)]
+		pub fn disable_autopay(origin: OriginFor<T>, rosca_id: RoscaId) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			ensure!(Self::autopay(rosca_id, &signer).is_some(), Error::<T>::AutopayNotEnabled);
+
+			Self::cancel_autopay(rosca_id, &signer);
+
+			Self::deposit_event(Event::<T>::AutopayDisabled {
+				rosca_id,
+				participant: signer
+			});
+
+			Ok(())
+		}
+
+		/// Pays the current contribution of a participant on autopay, then schedules the next
+		/// attempt, or turns autopay off if it can't be scheduled. Dispatched by the scheduler.
+		#[pallet::call_index(30)]
+		pub fn autopay_contribution(origin: OriginFor<T>, rosca_id: RoscaId, participant: AccountIdOf<T>) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(Self::autopay(rosca_id, &participant).is_some(), Error::<T>::AutopayNotEnabled);
+
+			// Autopay ends with the participant's membership or with the Rosca itself.
+			if Self::completed_roscas(rosca_id).is_some() || Self::participants(rosca_id, &participant).is_none() {
+				Self::cancel_autopay(rosca_id, &participant);
+				Self::deposit_event(Event::<T>::AutopayDisabled {
+					rosca_id,
+					participant
+				});
+				return Ok(());
+			}
+
+			if Self::autopay_due(rosca_id, &participant) {
+				// A failed attempt leaves no funds behind in the autopay account.
+				if let Err(error) = with_storage_layer(|| Self::do_autopay(rosca_id, &participant)) {
+					Self::deposit_event(Event::<T>::AutopayFailed {
+						rosca_id,
+						participant: participant.clone(),
+						error
+					});
+				}
+			}
+
+			// If the next attempt can't be scheduled, e.g. because the agenda is full, autopay is
+			// turned off rather than undoing the attempt just made.
+			if let Err(error) = Self::schedule_autopay(rosca_id, &participant) {
+				Autopay::<T>::remove(rosca_id, &participant);
+				Self::deposit_event(Event::<T>::AutopayFailed {
+					rosca_id,
+					participant: participant.clone(),
+					error
+				});
+				Self::deposit_event(Event::<T>::AutopayDisabled {
+					rosca_id,
+					participant
+				});
+			}
+
+			Ok(())
+		}
+
+		/// Allow or disallow creating new Roscas in a payment asset. Roscas already created in it
+		/// are unaffected.
+		#[pallet::call_index(31)]
+		pub fn set_asset_allowed(origin: OriginFor<T>, asset: PaymentAssets, allowed: bool) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			if allowed {
+				DisallowedAssets::<T>::remove(&asset);
+			} else {
+				DisallowedAssets::<T>::insert(&asset, ());
+			}
+
+			Self::deposit_event(Event::<T>::AssetAllowedSet {
+				asset,
+				allowed
+			});
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Creates a Rosca from the genesis config, has its participants join and starts it if asked.
+	fn build_genesis_rosca(rosca: &GenesisRosca<AccountIdOf<T>>) -> DispatchResult {
+		let rosca_id = Self::next_rosca_id();
+		let start_by_timestamp = <pallet_timestamp::Pallet<T>>::get().saturated_into::<u64>().saturating_add(rosca.starts_within);
+		let creator: OriginFor<T> = frame_system::RawOrigin::Signed(rosca.creator.clone()).into();
+
+		Self::create_rosca(
+			creator.clone(),
+			rosca.random_order,
+			rosca.invited.clone().try_into().map_err(|_| Error::<T>::TooManyProposedParticipants)?,
+			rosca.minimum_participant_threshold,
+			rosca.contribution_amount,
+			rosca.payment_asset.clone(),
+			rosca.contribution_frequency.saturated_into(),
+			start_by_timestamp.saturated_into(),
+			None,
+			rosca.name.clone().try_into().map_err(|_| Error::<T>::ConversionError)?
+		)?;
+		for participant in &rosca.joined {
+			Self::join_rosca(frame_system::RawOrigin::Signed(participant.clone()).into(), rosca_id, None)?;
+		}
+		if rosca.start {
+			Self::start_rosca(creator, rosca_id)?;
+		}
+		Ok(())
+	}
+
+	pub fn rosca_account_id(rosca_id: RoscaId) -> T::AccountId {
+		T::PalletId::get().into_sub_account_truncating(rosca_id)
+	}
+
+	pub fn insurance_account_id(asset: &PaymentAssets) -> T::AccountId {
+		T::PalletId::get().into_sub_account_truncating((b"insurance", asset.id()))
+	}
+
+	pub fn shuffle_participants(participants: &mut BoundedVec<AccountIdOf<T>, T::MaxParticipants>) {
+		// Fisher-Yates Shuffle
+		let current_block = <frame_system::Pallet<T>>::block_number();
+		let block_hash = <frame_system::Pallet<T>>::block_hash(current_block);
+		let truncated_seed = blake2_128(block_hash.as_ref()); // Truncate to 16 bytes for the shuffle
+		
+		let mut rng_seed = truncated_seed;
+
+		let mut available_indices: Vec<usize> = (0..participants.len()).collect(); 
+		let mut shuffled_indices = Vec::with_capacity(participants.len());
+
+		while !available_indices.is_empty() {
+
+			let mut rng_seed = &blake2_128(&rng_seed[..]);
 
 
 			let random_index = (rng_seed[0] as usize) % available_indices.len();
@@ -784,6 +1704,320 @@ impl<T: Config> Pallet<T> {
 }
 
 impl<T: Config> Pallet<T> {
+	/// Covers up to `shortfall` of a defaulted contribution from the guarantee, recording the
+	/// drawn amount as owed by the defaulter to the guarantor. Returns the amount drawn.
+	fn draw_guarantee(rosca_id: RoscaId, rosca: &RoscaDetails<T>, defaulter: &AccountIdOf<T>, recipient: &AccountIdOf<T>, shortfall: u32) -> Result<u32, DispatchError> {
+		let Some(guarantor) = rosca.guarantor.clone() else {
+			return Ok(0);
+		};
+		let guarantee = Self::guarantee_balance(rosca_id);
+		let amount = shortfall.min(guarantee);
+		if amount == 0 {
+			return Ok(0);
+		}
+
+		T::ForeignCurrency::transfer(rosca.payment_asset.id(), &Self::rosca_account_id(rosca_id), recipient, amount.into(), Expendable)?;
+		GuaranteeBalances::<T>::insert(rosca_id, guarantee - amount);
+		Self::record_pot_payment(rosca_id, defaulter, recipient, amount.into());
+		GuarantorDebts::<T>::try_mutate(rosca_id, defaulter, |debt| -> DispatchResult {
+			*debt = debt.checked_add(amount).ok_or(Error::<T>::ArithmeticOverflow)?;
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::<T>::GuaranteeDrawn {
+			rosca_id,
+			guarantor,
+			defaulter: defaulter.clone(),
+			recipient: recipient.clone(),
+			amount: amount.into()
+		});
+
+		Ok(amount)
+	}
+
+	/// Pays the undrawn guarantee back to `guarantor`.
+	fn return_guarantee(rosca_id: RoscaId, rosca: &RoscaDetails<T>, guarantor: &AccountIdOf<T>) -> DispatchResult {
+		let remainder = GuaranteeBalances::<T>::take(rosca_id);
+		T::ForeignCurrency::transfer(rosca.payment_asset.id(), &Self::rosca_account_id(rosca_id), guarantor, remainder.into(), Expendable)?;
+
+		Self::deposit_event(Event::<T>::GuaranteeClaimed {
+			rosca_id,
+			guarantor: guarantor.clone(),
+			amount: remainder.into()
+		});
+
+		Ok(())
+	}
+
+	/// Records `amount` as paid into the pots by `payer` and received by `recipient`.
+	pub(crate) fn record_pot_payment(rosca_id: RoscaId, payer: &AccountIdOf<T>, recipient: &AccountIdOf<T>, amount: Balance) {
+		PotLedgers::<T>::mutate(rosca_id, payer, |ledger| ledger.paid_in = ledger.paid_in.saturating_add(amount));
+		PotLedgers::<T>::mutate(rosca_id, recipient, |ledger| ledger.received = ledger.received.saturating_add(amount));
+	}
+
+	/// The platform fee rate for `asset`, preferring its override over the default.
+	pub fn platform_fee_for(asset: &PaymentAssets) -> Permill {
+		Self::asset_platform_fee(asset).unwrap_or_else(Self::platform_fee)
+	}
+
+	/// Pays a contribution of `amount` from `from` to the `recipient`, less the platform fee
+	/// sent to the fee destination and the organiser fee sent to the creator.
+	/// The organiser fee is trimmed so the two never exceed `MaxFee`, even if the platform fee
+	/// was raised after the organiser fee was set. A fee too small to leave its payee with the
+	/// asset's minimum balance is not taken and goes to the recipient instead.
+	/// Returns the platform and organiser fees taken.
+	fn transfer_contribution(rosca_id: RoscaId, asset: &PaymentAssets, from: &AccountIdOf<T>, recipient: &AccountIdOf<T>, amount: Balance) -> Result<(Balance, Balance), DispatchError> {
+		let platform_rate = Self::platform_fee_for(asset).min(T::MaxFee::get());
+		let platform_fee = Self::collectable_fee(asset, &T::FeeDestination::get(), platform_rate.mul_floor(amount));
+		let organiser = Self::rosca_creator(rosca_id);
+		let organiser_fee = match (Self::organiser_fee(rosca_id), &organiser) {
+			(Some(fee), Some(organiser)) => {
+				let rate = fee.min(T::MaxFee::get().saturating_sub(platform_rate));
+				Self::collectable_fee(asset, organiser, rate.mul_floor(amount))
+			},
+			_ => 0,
+		};
+		let net = amount
+			.checked_sub(platform_fee)
+			.and_then(|net| net.checked_sub(organiser_fee))
+			.ok_or(Error::<T>::ArithmeticError)?;
+
+		Self::pay_out(rosca_id, asset, from, recipient, net)?;
+		if platform_fee > 0 {
+			T::ForeignCurrency::transfer(asset.id(), from, &T::FeeDestination::get(), platform_fee, Expendable)?;
+		}
+		if let (Some(organiser), true) = (organiser, organiser_fee > 0) {
+			T::ForeignCurrency::transfer(asset.id(), from, &organiser, organiser_fee, Expendable)?;
+		}
+
+		Ok((platform_fee, organiser_fee))
+	}
+
+	/// The part of `fee` that can be paid to `payee`: nothing if it would leave them below the
+	/// asset's minimum balance, since that transfer would fail.
+	fn collectable_fee(asset: &PaymentAssets, payee: &AccountIdOf<T>, fee: Balance) -> Balance {
+		let balance = T::ForeignCurrency::balance(asset.id(), payee);
+		if balance.saturating_add(fee) < T::ForeignCurrency::minimum_balance(asset.id()) {
+			0
+		} else {
+			fee
+		}
+	}
+
+	/// Pays `amount` from `from` to the `recipient`, at their payout destination if they chose one.
+	/// A payout that can't be sent there is paid to their account on this chain instead.
+	fn pay_out(rosca_id: RoscaId, asset: &PaymentAssets, from: &AccountIdOf<T>, recipient: &AccountIdOf<T>, amount: Balance) -> DispatchResult {
+		if let Some(destination) = Self::payout_destination(rosca_id, recipient) {
+			let sent = with_storage_layer(|| T::PayoutSender::send_payout(from, asset.id(), amount, destination.clone()));
+			match sent {
+				Ok(()) => {
+					Self::deposit_event(Event::<T>::RemotePayoutSent {
+						rosca_id,
+						recipient: recipient.clone(),
+						destination,
+						amount
+					});
+					return Ok(());
+				},
+				Err(error) => Self::deposit_event(Event::<T>::RemotePayoutFailed {
+					rosca_id,
+					recipient: recipient.clone(),
+					amount,
+					error
+				}),
+			}
+		}
+
+		T::ForeignCurrency::transfer(asset.id(), from, recipient, amount, Expendable)?;
+		Ok(())
+	}
+
+	/// Moves the insurance premium due on a contribution of `amount` from the `payer` into the pool.
+	fn charge_insurance_premium(rosca_id: RoscaId, asset: &PaymentAssets, payer: &AccountIdOf<T>, amount: Balance) -> Result<Balance, DispatchError> {
+		let Some(config) = Self::insurance_config(asset) else {
+			return Ok(0);
+		};
+		let premium = config.premium.mul_floor(amount);
+		let insurance_account_id = Self::insurance_account_id(asset);
+		// A premium too small to open the pool's account is waived rather than failing the contribution.
+		let pool_balance = T::ForeignCurrency::balance(asset.id(), &insurance_account_id);
+		if premium == 0 || pool_balance.saturating_add(premium) < T::ForeignCurrency::minimum_balance(asset.id()) {
+			return Ok(0);
+		}
+
+		T::ForeignCurrency::transfer(asset.id(), payer, &insurance_account_id, premium, Expendable)?;
+		InsurancePools::<T>::try_mutate(asset, |pool| -> DispatchResult {
+			*pool = pool.checked_add(premium).ok_or(Error::<T>::ArithmeticOverflow)?;
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::<T>::InsurancePremiumPaid {
+			rosca_id,
+			payer: payer.clone(),
+			asset: asset.clone(),
+			amount: premium
+		});
+
+		Ok(premium)
+	}
+
+	/// Covers up to `shortfall` of a defaulted contribution from the insurance pool,
+	/// limited by the asset's coverage cap and the funds in the pool. The asset's minimum
+	/// balance stays in the pool so its account is never reaped.
+	fn pay_insurance_claim(rosca_id: RoscaId, asset: &PaymentAssets, defaulter: &AccountIdOf<T>, recipient: &AccountIdOf<T>, shortfall: Balance) -> DispatchResult {
+		let Some(config) = Self::insurance_config(asset) else {
+			return Ok(());
+		};
+		let pool = Self::insurance_pool(asset);
+		let claimable = pool.saturating_sub(T::ForeignCurrency::minimum_balance(asset.id()));
+		let amount = shortfall.min(config.max_claim).min(claimable);
+		if amount == 0 {
+			return Ok(());
+		}
+
+		T::ForeignCurrency::transfer(asset.id(), &Self::insurance_account_id(asset), recipient, amount, Preserve)?;
+		InsurancePools::<T>::insert(asset, pool - amount);
+
+		Self::deposit_event(Event::<T>::InsuranceClaimPaid {
+			rosca_id,
+			defaulter: defaulter.clone(),
+			recipient: recipient.clone(),
+			asset: asset.clone(),
+			shortfall,
+			amount
+		});
+
+		Ok(())
+	}
+
+	/// Pays the current round contribution of `participant` from the `payer` account,
+	/// catching up on any elapsed rounds first. Returns what the payer was charged, the
+	/// contribution and any insurance premium.
+	pub(crate) fn do_contribute(payer: AccountIdOf<T>, participant: AccountIdOf<T>, rosca_id: RoscaId) -> Result<Balance, DispatchError> {
+		ensure!(Self::participants(rosca_id, &participant).is_some(), Error::<T>::NotAParticipant);
+		ensure!(Self::completed_roscas(rosca_id).is_none(), Error::<T>::RoscaAlreadyCompleted);
+		let rosca = Self::active_roscas(rosca_id).ok_or(Error::<T>::RoscaNotActive)?;
+		ensure!(Self::paused_at(rosca_id).is_none(), Error::<T>::RoscaPaused);
+		let eligible_claimant = Self::eligible_claimant(rosca_id).ok_or(Error::<T>::NoEligbleClaimant)?;
+		
+		ensure!(eligible_claimant != participant, Error::<T>::CantContributeToSelf);
+		ensure!(Self::current_contributors(rosca_id, &participant).is_none(), Error::<T>::AlreadyContributed);
+
+		// Catching up may complete the Rosca or eject the participant; keep the processed rounds.
+		if Self::catch_up_rounds(rosca_id)? || Self::participants(rosca_id, &participant).is_none() {
+			return Ok(0);
+		}
+
+		let final_pay_by_timestamp = Self::final_pay_by_timestamp(rosca_id).ok_or(Error::<T>::FinalPayByTimestampNotFound)?;
+		let mut next_pay_by_timestamp = Self::next_pay_by_timestamp(rosca_id).ok_or(Error::<T>::NoNextPayByTimestamp)?;
+		let mut active_rosca_participants_order = Self::active_rosca_participants_order(rosca_id).ok_or(Error::<T>::RoscaParticipantsNotFound)?;
+		let mut eligible_claimant = Self::eligible_claimant(rosca_id).ok_or(Error::<T>::NoEligbleClaimant)?;
+
+		// If we are here we must have caught up to the current round, which may be the
+		// participant's own; there is nothing to pay then.
+		if eligible_claimant == participant {
+			return Ok(0);
+		}
+		ensure!(Self::current_contributors(rosca_id, &participant).is_none(), Error::<T>::AlreadyContributed);
+		
+		let (platform_fee, organiser_fee) = Self::transfer_contribution(rosca_id, &rosca.payment_asset, &payer, &eligible_claimant, rosca.contribution_amount.into())?;
+		let premium = Self::charge_insurance_premium(rosca_id, &rosca.payment_asset, &payer, rosca.contribution_amount.into())?;
+		let charged = Balance::from(rosca.contribution_amount).checked_add(premium).ok_or(Error::<T>::ArithmeticOverflow)?;
+		Self::record_pot_payment(rosca_id, &participant, &eligible_claimant, rosca.contribution_amount.into());
+		CurrentContributors::<T>::insert(rosca_id, &participant, ());
+		let current_contribution_count = Self::current_contribution_count(rosca_id).checked_add(1).ok_or(Error::<T>::ArithmeticOverflow)?;
+		CurrentContributionCount::<T>::insert(rosca_id, current_contribution_count);
+
+		Self::deposit_event(Event::<T>::ContributionMade {
+			rosca_id,
+			contributor: participant.clone(),
+			recipient: eligible_claimant.clone(),
+			amount: rosca.contribution_amount.into(),
+			payer: payer.clone(),
+			platform_fee,
+			organiser_fee,
+		});
+
+
+		if current_contribution_count == (active_rosca_participants_order.len() - 1) as u32 {
+			// This means it's the final contribution for the round so we can progress
+
+			next_pay_by_timestamp = next_pay_by_timestamp.checked_add(&rosca.contribution_frequency).ok_or(Error::<T>::ArithmeticOverflow)?;
+
+			if next_pay_by_timestamp > final_pay_by_timestamp {
+				// Means it was the final contribution of the final round
+				Self::mark_complete(rosca_id);
+				Self::deposit_event(Event::<T>::RoscaComplete {
+					rosca_id,
+				});
+				return Ok(charged);
+			}
+
+			NextPayByTimestamp::<T>::insert(rosca_id, next_pay_by_timestamp);
+
+			eligible_claimant = active_rosca_participants_order[active_rosca_participants_order.len() - 1 as usize].clone();
+			EligibleClaimant::<T>::insert(rosca_id, &eligible_claimant);
+			active_rosca_participants_order.try_rotate_right(1).map_err(|_| Error::<T>::ArithmeticError)?;
+			ActiveRoscaParticipantsOrder::<T>::insert(rosca_id, active_rosca_participants_order.clone());
+			CurrentContributors::<T>::clear_prefix(rosca_id, (active_rosca_participants_order.len() - 1) as u32, None);
+			CurrentContributionCount::<T>::insert(rosca_id, 0);
+			CurrentRound::<T>::mutate(rosca_id, |round| *round = round.saturating_add(1));
+
+			Self::deposit_event(Event::<T>::NewRoundStarted {
+				rosca_id,
+				new_eligible_recipient: eligible_claimant.clone(),
+				payment_cutoff: next_pay_by_timestamp
+			});
+		}
+
+		
+		Ok(charged)
+	}
+
+	/// Processes every round whose payment cutoff has passed: collects from defaulters and
+	/// moves on to the next round. Returns whether that completed the Rosca.
+	pub(crate) fn catch_up_rounds(rosca_id: RoscaId) -> Result<bool, DispatchError> {
+		let current_timestamp = <pallet_timestamp::Pallet<T>>::get();
+
+		while current_timestamp >= Self::next_pay_by_timestamp(rosca_id).ok_or(Error::<T>::NoNextPayByTimestamp)? {
+			Self::process_defaulters(rosca_id)?;
+			Self::advance_rosca_round(rosca_id)?;
+			if Self::check_and_complete_rosca(rosca_id)? {
+				return Ok(true);
+			}
+		}
+
+		Ok(false)
+	}
+
+	/// Moves `amount` from the `payer` into the Rosca account and credits it to the
+	/// security deposit of `participant`.
+	fn do_add_to_security_deposit(payer: AccountIdOf<T>, participant: AccountIdOf<T>, rosca_id: RoscaId, amount: u32) -> DispatchResult {
+		ensure!(Self::participants(rosca_id, &participant).is_some(), Error::<T>::NotAParticipant);
+		ensure!(Self::completed_roscas(rosca_id).is_none(), Error::<T>::RoscaAlreadyCompleted);
+		let rosca_account_id = Self::rosca_account_id(rosca_id);
+		let rosca = Self::active_roscas(rosca_id)
+			.or_else(|| Self::rosca_details(rosca_id))
+			.ok_or_else(|| Error::<T>::RoscaNotFound)?;
+		T::ForeignCurrency::transfer(
+				rosca.payment_asset.id(), 
+				&payer, 
+				&rosca_account_id, 
+				amount.into(), 
+				Expendable
+			)?;
+		let mut participant_deposit = Self::security_deposit(rosca_id, &participant).unwrap_or(0);
+		let new_deposit_balance = participant_deposit.checked_add(amount).ok_or(Error::<T>::ArithmeticOverflow)?;
+		RoscaSecurityDeposits::<T>::insert(rosca_id, &participant, new_deposit_balance);
+		Self::deposit_event(Event::<T>::SecurityDepositContribution {
+			rosca_id,
+			depositor: participant,
+			amount: amount.into(),
+			payer
+		});
+		Ok(())
+	}
+
     /// Processes missed contributions for the current round.
     /// For each participant (other than the eligible claimant) who has not contributed,
     /// check their security deposit and, if insufficient, mark them as defaulters.
@@ -794,6 +2028,7 @@ impl<T: Config> Pallet<T> {
         let active_order = Self::active_rosca_participants_order(rosca_id)
             .ok_or(Error::<T>::RoscaParticipantsNotFound)?;
         let rosca_account_id = Self::rosca_account_id(rosca_id);
+        let mut chronic_defaulters: Vec<AccountIdOf<T>> = Vec::new();
 
         // Iterate through each participant.
         for participant in active_order.iter() {
@@ -810,56 +2045,75 @@ impl<T: Config> Pallet<T> {
                     defaulter = true;
                     if participant_deposit > 0 {
                         // Transfer whatever deposit is available.
-						T::ForeignCurrency::transfer(
-							rosca.payment_asset.id(), 
-							&rosca_account_id, 
-							&eligible_claimant, 
-							participant_deposit.into(), 
-							Expendable
+						let (platform_fee, organiser_fee) = Self::transfer_contribution(
+							rosca_id,
+							&rosca.payment_asset,
+							&rosca_account_id,
+							&eligible_claimant,
+							participant_deposit.into()
 						)?;
                         RoscaSecurityDeposits::<T>::insert(rosca_id, participant, 0);
+                        Self::record_pot_payment(rosca_id, participant, &eligible_claimant, participant_deposit.into());
                         Self::deposit_event(Event::<T>::DepositDeducted {
                             rosca_id,
                             contributor: participant.clone(),
                             recipient: eligible_claimant.clone(),
                             amount: participant_deposit.into(),
                             sufficient: false,
+                            platform_fee,
+                            organiser_fee,
                         });
                     }
+                    // Cover the shortfall from the guarantee first, then the insurance pool.
+                    let mut shortfall = rosca.contribution_amount.saturating_sub(participant_deposit);
+                    shortfall = shortfall.saturating_sub(Self::draw_guarantee(rosca_id, &rosca, participant, &eligible_claimant, shortfall)?);
+                    Self::pay_insurance_claim(rosca_id, &rosca.payment_asset, participant, &eligible_claimant, shortfall.into())?;
                 } else {
                     // Sufficient deposit: deduct the fixed contribution amount.
-					T::ForeignCurrency::transfer(
-						rosca.payment_asset.id(), 
-						&rosca_account_id, 
-						&eligible_claimant, 
-						rosca.contribution_amount.into(),
-						Expendable
+					let (platform_fee, organiser_fee) = Self::transfer_contribution(
+						rosca_id,
+						&rosca.payment_asset,
+						&rosca_account_id,
+						&eligible_claimant,
+						rosca.contribution_amount.into()
 					)?;
                     let remaining = participant_deposit
                         .checked_sub(rosca.contribution_amount)
                         .unwrap_or(0);
                     RoscaSecurityDeposits::<T>::insert(rosca_id, participant, remaining);
+                    Self::record_pot_payment(rosca_id, participant, &eligible_claimant, rosca.contribution_amount.into());
                     Self::deposit_event(Event::<T>::DepositDeducted {
                         rosca_id,
                         contributor: participant.clone(),
                         recipient: eligible_claimant.clone(),
                         amount: rosca.contribution_amount.into(),
                         sufficient: true,
+                        platform_fee,
+                        organiser_fee,
                     });
                 }
 
                 if defaulter {
-                    DefaultCount::<T>::mutate(rosca_id, participant, |count| {
-                        *count = count.saturating_add(1)
+                    let defaults = DefaultCount::<T>::mutate(rosca_id, participant, |count| {
+                        *count = count.saturating_add(1);
+                        *count
                     });
                     Self::deposit_event(Event::<T>::ParticipantDefaulted {
                         rosca_id,
 						unpaid_recipient: eligible_claimant.clone(),
                         defaulter: participant.clone()
                     });
+                    if Self::max_defaults(rosca_id).is_some_and(|max_defaults| defaults > max_defaults) {
+                        chronic_defaulters.push(participant.clone());
+                    }
                 }
             }
         }
+
+        // Eject once the whole round is settled so the order isn't changed mid-iteration.
+        for defaulter in chronic_defaulters {
+            Self::do_eject(rosca_id, defaulter)?;
+        }
         Ok(())
     }
 
@@ -894,6 +2148,7 @@ impl<T: Config> Pallet<T> {
         let count_to_clear = (active_order.len() - 1) as u32;
         CurrentContributors::<T>::clear_prefix(rosca_id, count_to_clear, None);
         CurrentContributionCount::<T>::insert(rosca_id, 0);
+        CurrentRound::<T>::mutate(rosca_id, |round| *round = round.saturating_add(1));
 
         // Emit an event to signal the start of a new round.
         Self::deposit_event(Event::<T>::NewRoundStarted {
@@ -904,6 +2159,14 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// Moves an active ROSCA's details over to the completed set.
+    pub(crate) fn mark_complete(rosca_id: RoscaId) {
+        CompletedRoscas::<T>::insert(rosca_id, ());
+        if let Some(rosca) = ActiveRoscas::<T>::take(rosca_id) {
+            CompletedRoscaDetails::<T>::insert(rosca_id, rosca);
+        }
+    }
+
     /// Checks if the ROSCA should be completed and, if so, finalizes it.
     fn check_and_complete_rosca(rosca_id: RoscaId) -> Result<bool, DispatchError> {
         let rosca = Self::active_roscas(rosca_id).ok_or(Error::<T>::RoscaNotActive)?;
@@ -914,8 +2177,7 @@ impl<T: Config> Pallet<T> {
 
         if next_pay_by_timestamp > final_pay_by_timestamp {
             // If the next payment time is past the final deadline, complete the ROSCA.
-            CompletedRoscas::<T>::insert(rosca_id, ());
-            ActiveRoscas::<T>::remove(rosca_id);
+            Self::mark_complete(rosca_id);
             Self::deposit_event(Event::<T>::RoscaComplete { rosca_id });
 			return Ok(true)
         }
@@ -959,5 +2221,308 @@ impl<T: Config> Pallet<T> {
 	
 		Ok(rounds)
 	}
+
+	/// Number of rounds still to be paid out after the current one.
+	pub fn remaining_rounds(rosca_id: RoscaId) -> Result<u32, DispatchError> {
+		let rosca = Self::active_roscas(rosca_id).ok_or(Error::<T>::RoscaNotActive)?;
+		let final_pay_by_timestamp = Self::final_pay_by_timestamp(rosca_id).ok_or(Error::<T>::FinalPayByTimestampNotFound)?;
+		let next_pay_by_timestamp = Self::next_pay_by_timestamp(rosca_id).ok_or(Error::<T>::NoNextPayByTimestamp)?;
+		let remaining = final_pay_by_timestamp
+			.checked_sub(&next_pay_by_timestamp)
+			.ok_or(Error::<T>::ArithmeticUnderflow)?
+			.checked_div(&rosca.contribution_frequency)
+			.ok_or(Error::<T>::DivisionError)?;
+		Ok(remaining.saturated_into::<u32>())
+	}
+
+	/// Builds the round data for the participant at `index` of the active order.
+	/// The current recipient sits at index 0 and the next recipient at the end of the order.
+	pub fn round_info_at(
+		rosca_id: RoscaId,
+		active_order: &BoundedVec<AccountIdOf<T>, T::MaxParticipants>,
+		index: usize,
+	) -> Result<RoundInfo<T>, DispatchError> {
+		let rosca = Self::active_roscas(rosca_id).ok_or(Error::<T>::RoscaNotActive)?;
+		let next_pay_by_timestamp = Self::next_pay_by_timestamp(rosca_id).ok_or(Error::<T>::NoNextPayByTimestamp)?;
+		let participants = active_order.len() as u32;
+		let rounds_ahead = if index == 0 {
+			0
+		} else {
+			participants.checked_sub(index as u32).ok_or(Error::<T>::ArithmeticUnderflow)?
+		};
+		let current_round = Self::current_round(rosca_id);
+
+		let payment_cutoff = rosca.contribution_frequency
+			.checked_mul(&T::Moment::from(rounds_ahead))
+			.and_then(|offset| next_pay_by_timestamp.checked_add(&offset))
+			.ok_or(Error::<T>::ArithmeticOverflow)?;
+
+		let recipient = active_order.get(index).ok_or(Error::<T>::RoscaParticipantsNotFound)?.clone();
+		let others: Vec<_> = active_order.iter().filter(|x| **x != recipient).cloned().collect();
+		let expected_contributors: BoundedVec<AccountIdOf<T>, T::MaxInvitedParticipants> =
+			BoundedVec::try_from(others).map_err(|_| Error::<T>::TooManyProposedParticipants)?;
+
+		Ok(RoundInfo::<T> {
+			round_number: current_round.checked_add(rounds_ahead).ok_or(Error::<T>::ArithmeticOverflow)?,
+			payment_cutoff,
+			expected_contributors,
+			recipient,
+		})
+	}
+
+	/// Records `substitute` as the nominated replacement of `departing`.
+	fn do_nominate_substitute(rosca_id: RoscaId, departing: AccountIdOf<T>, substitute: AccountIdOf<T>) -> DispatchResult {
+		ensure!(Self::participants(rosca_id, &departing).is_some(), Error::<T>::NotAParticipant);
+		ensure!(Self::participants(rosca_id, &substitute).is_none(), Error::<T>::SubstituteAlreadyParticipant);
+		ensure!(Self::active_roscas(rosca_id).is_some(), Error::<T>::RoscaNotActive);
+
+		PendingSubstitutions::<T>::insert(rosca_id, &departing, &substitute);
+
+		Self::deposit_event(Event::<T>::SubstituteNominated {
+			rosca_id,
+			departing,
+			substitute
+		});
+
+		Ok(())
+	}
+
+	/// Moves every piece of per-participant state from `departing` to `substitute`.
+	/// If the departing participant already received the pot, the substitute inherits
+	/// the contributions still owed for it and secures them as security deposit, so any
+	/// they don't pay are deducted from it as they fall due.
+	fn do_substitute(rosca_id: RoscaId, departing: AccountIdOf<T>, substitute: AccountIdOf<T>) -> DispatchResult {
+		let position = Self::participants(rosca_id, &departing).ok_or(Error::<T>::NotAParticipant)?;
+		ensure!(Self::participants(rosca_id, &substitute).is_none(), Error::<T>::SubstituteAlreadyParticipant);
+		let rosca = Self::active_roscas(rosca_id).ok_or(Error::<T>::RoscaNotActive)?;
+		let next_pay_by_timestamp = Self::next_pay_by_timestamp(rosca_id).ok_or(Error::<T>::NoNextPayByTimestamp)?;
+		ensure!(<pallet_timestamp::Pallet<T>>::get() < next_pay_by_timestamp, Error::<T>::RoundCutoffPassed);
+		// The pot of the round in progress belongs to its recipient, so they can't hand it over.
+		ensure!(Self::eligible_claimant(rosca_id).as_ref() != Some(&departing), Error::<T>::CantSubstituteRecipient);
+
+		let mut active_order = Self::active_rosca_participants_order(rosca_id).ok_or(Error::<T>::RoscaParticipantsNotFound)?;
+		let index = active_order.iter().position(|p| *p == departing).ok_or(Error::<T>::NotAParticipant)?;
+		let remaining_rounds = Self::remaining_rounds(rosca_id)?;
+		let first_future_index = active_order.len()
+			.checked_sub(remaining_rounds as usize)
+			.ok_or(Error::<T>::ArithmeticUnderflow)?
+			.max(1);
+		let already_received = index < first_future_index;
+
+		// Contributions the position still owes: one per remaining round except its own payout
+		// round, plus the current round if it has not paid yet.
+		let owes_current_round = Self::current_contributors(rosca_id, &departing).is_none();
+		let outstanding_contributions = remaining_rounds
+			.saturating_add(owes_current_round as u32)
+			.saturating_sub((!already_received) as u32);
+		let inherited_debt: Balance = if already_received {
+			(rosca.contribution_amount as Balance).checked_mul(outstanding_contributions as Balance).ok_or(Error::<T>::ArithmeticOverflow)?
+		} else {
+			0
+		};
+
+		let deposit_transferred = RoscaSecurityDeposits::<T>::take(rosca_id, &departing).unwrap_or(0);
+		let debt_deposit: u32 = inherited_debt
+			.saturating_sub(deposit_transferred.into())
+			.try_into()
+			.map_err(|_| Error::<T>::ArithmeticOverflow)?;
+		if debt_deposit > 0 {
+			T::ForeignCurrency::transfer(
+				rosca.payment_asset.id(),
+				&substitute,
+				&Self::rosca_account_id(rosca_id),
+				debt_deposit.into(),
+				Expendable
+			)?;
+		}
+		let deposit = deposit_transferred.checked_add(debt_deposit).ok_or(Error::<T>::ArithmeticOverflow)?;
+		if deposit > 0 {
+			RoscaSecurityDeposits::<T>::insert(rosca_id, &substitute, deposit);
+		}
+
+		active_order[index] = substitute.clone();
+		ActiveRoscaParticipantsOrder::<T>::insert(rosca_id, active_order);
+
+		RoscaParticipants::<T>::remove(rosca_id, &departing);
+		RoscaParticipants::<T>::insert(rosca_id, &substitute, position);
+		RoscaInvitedPreverifiedParticipants::<T>::remove(rosca_id, &departing);
+		RoscaInvitedPreverifiedParticipants::<T>::insert(rosca_id, &substitute, ());
+
+		if CurrentContributors::<T>::take(rosca_id, &departing).is_some() {
+			CurrentContributors::<T>::insert(rosca_id, &substitute, ());
+		}
+		let defaults = DefaultCount::<T>::take(rosca_id, &departing);
+		if defaults > 0 {
+			DefaultCount::<T>::insert(rosca_id, &substitute, defaults);
+		}
+		PotLedgers::<T>::insert(rosca_id, &substitute, PotLedgers::<T>::take(rosca_id, &departing));
+		PendingSubstitutions::<T>::remove(rosca_id, &departing);
+		PendingSwaps::<T>::remove(rosca_id, &departing);
+
+		Self::deposit_event(Event::<T>::ParticipantSubstituted {
+			rosca_id,
+			departing,
+			substitute,
+			already_received,
+			outstanding_contributions,
+			inherited_debt,
+			deposit_transferred: deposit_transferred.into(),
+			debt_deposit: debt_deposit.into()
+		});
+
+		Ok(())
+	}
+
+	/// The data of the current round followed by every round still to be paid out.
+	pub fn remaining_schedule(rosca_id: RoscaId) -> Result<RoscaRounds<T>, DispatchError> {
+		let active_order = Self::active_rosca_participants_order(rosca_id).ok_or(Error::<T>::RoscaParticipantsNotFound)?;
+		let remaining_rounds = Self::remaining_rounds(rosca_id)? as usize;
+		let mut rounds: RoscaRounds<T> = BoundedVec::new();
+		rounds.try_push(Self::round_info_at(rosca_id, &active_order, 0)?).map_err(|_| Error::<T>::TooManyRounds)?;
+		for index in (1..active_order.len()).rev().take(remaining_rounds) {
+			rounds.try_push(Self::round_info_at(rosca_id, &active_order, index)?).map_err(|_| Error::<T>::TooManyRounds)?;
+		}
+		Ok(rounds)
+	}
+
+	/// Removes `participant` from an active Rosca. Any remaining deposit is forfeited and shared
+	/// between the recipients of the rounds they no longer pay into. If they were still waiting
+	/// for the pot, their payout slot is dropped and the final cutoff brought forward by one round.
+	pub(crate) fn do_eject(rosca_id: RoscaId, participant: AccountIdOf<T>) -> DispatchResult {
+		ensure!(Self::participants(rosca_id, &participant).is_some(), Error::<T>::NotAParticipant);
+		let rosca = Self::active_roscas(rosca_id).ok_or(Error::<T>::RoscaNotActive)?;
+		let eligible_claimant = Self::eligible_claimant(rosca_id).ok_or(Error::<T>::NoEligbleClaimant)?;
+		ensure!(eligible_claimant != participant, Error::<T>::CantEjectRecipient);
+
+		let mut active_order = Self::active_rosca_participants_order(rosca_id).ok_or(Error::<T>::RoscaParticipantsNotFound)?;
+		let index = active_order.iter().position(|p| *p == participant).ok_or(Error::<T>::NotAParticipant)?;
+		let first_future_index = active_order.len()
+			.checked_sub(Self::remaining_rounds(rosca_id)? as usize)
+			.ok_or(Error::<T>::ArithmeticUnderflow)?
+			.max(1);
+		let slot_dropped = index >= first_future_index;
+
+		// The recipients of the rounds the participant won't pay into: the current one unless
+		// they already paid it, then every later one but their own, in payout order.
+		let mut affected_recipients = Vec::new();
+		if Self::current_contributors(rosca_id, &participant).is_none() {
+			affected_recipients.push(eligible_claimant.clone());
+		}
+		affected_recipients.extend(
+			active_order[first_future_index..].iter().rev().filter(|p| **p != participant).cloned()
+		);
+		if affected_recipients.is_empty() {
+			affected_recipients.push(eligible_claimant.clone());
+		}
+
+		let forfeited_deposit = RoscaSecurityDeposits::<T>::take(rosca_id, &participant).unwrap_or(0);
+		let mut forfeited_to = Vec::new();
+		if forfeited_deposit > 0 {
+			// Shared evenly, with the remainder going to the first recipient.
+			let share = forfeited_deposit / affected_recipients.len() as u32;
+			let remainder = forfeited_deposit % affected_recipients.len() as u32;
+			for (i, recipient) in affected_recipients.into_iter().enumerate() {
+				let amount = if i == 0 { share + remainder } else { share };
+				if amount == 0 {
+					continue;
+				}
+				T::ForeignCurrency::transfer(
+					rosca.payment_asset.id(),
+					&Self::rosca_account_id(rosca_id),
+					&recipient,
+					amount.into(),
+					Expendable
+				)?;
+				Self::record_pot_payment(rosca_id, &participant, &recipient, amount.into());
+				forfeited_to.push((recipient, amount.into()));
+			}
+		}
+
+		active_order.remove(index);
+		ActiveRoscaParticipantsOrder::<T>::insert(rosca_id, active_order);
+
+		let mut final_pay_by_timestamp = Self::final_pay_by_timestamp(rosca_id).ok_or(Error::<T>::FinalPayByTimestampNotFound)?;
+		if slot_dropped {
+			final_pay_by_timestamp = final_pay_by_timestamp
+				.checked_sub(&rosca.contribution_frequency)
+				.ok_or(Error::<T>::ArithmeticUnderflow)?;
+			FinalPayByTimestamp::<T>::insert(rosca_id, final_pay_by_timestamp);
+		}
+
+		if CurrentContributors::<T>::take(rosca_id, &participant).is_some() {
+			CurrentContributionCount::<T>::mutate(rosca_id, |count| *count = count.saturating_sub(1));
+		}
+		RoscaParticipants::<T>::remove(rosca_id, &participant);
+		RoscaInvitedPreverifiedParticipants::<T>::remove(rosca_id, &participant);
+		RoscaParticipantsCount::<T>::mutate(rosca_id, |count| {
+			if let Some(count) = count {
+				*count = count.saturating_sub(1);
+			}
+		});
+		PendingSwaps::<T>::remove(rosca_id, &participant);
+		PendingSubstitutions::<T>::remove(rosca_id, &participant);
+
+		Self::deposit_event(Event::<T>::ParticipantEjected {
+			rosca_id,
+			participant: participant.clone(),
+			defaults: Self::default_count(rosca_id, &participant),
+			forfeited_deposit: forfeited_deposit.into(),
+			forfeited_to,
+			slot_dropped,
+			final_payment_cutoff: final_pay_by_timestamp,
+			rounds: Self::remaining_schedule(rosca_id)?
+		});
+
+		Ok(())
+	}
+
+	/// Moves on to the next round, or completes the Rosca after the final one, if everyone but
+	/// the recipient has paid into the current round. Contributions only do this for the
+	/// payment that completes a round, so it's needed when ejecting the last participant
+	/// still to pay. A paused Rosca stays in its round until it's resumed.
+	pub(crate) fn advance_if_round_paid(rosca_id: RoscaId) -> DispatchResult {
+		if Self::paused_at(rosca_id).is_some() {
+			return Ok(());
+		}
+		let rosca = Self::active_roscas(rosca_id).ok_or(Error::<T>::RoscaNotActive)?;
+		let active_order = Self::active_rosca_participants_order(rosca_id).ok_or(Error::<T>::RoscaParticipantsNotFound)?;
+		if Self::current_contribution_count(rosca_id) < (active_order.len() as u32).saturating_sub(1) {
+			return Ok(());
+		}
+
+		let next_pay_by_timestamp = Self::next_pay_by_timestamp(rosca_id)
+			.ok_or(Error::<T>::NoNextPayByTimestamp)?
+			.checked_add(&rosca.contribution_frequency)
+			.ok_or(Error::<T>::ArithmeticOverflow)?;
+		let final_pay_by_timestamp = Self::final_pay_by_timestamp(rosca_id).ok_or(Error::<T>::FinalPayByTimestampNotFound)?;
+		if next_pay_by_timestamp > final_pay_by_timestamp {
+			Self::mark_complete(rosca_id);
+			Self::deposit_event(Event::<T>::RoscaComplete { rosca_id });
+			return Ok(());
+		}
+		Self::advance_rosca_round(rosca_id)
+	}
+
+	/// Returns the active order indices of two participants, ensuring neither has
+	/// received the pot yet and that the stored round is still current.
+	fn future_swap_positions(rosca_id: RoscaId, first: &AccountIdOf<T>, second: &AccountIdOf<T>) -> Result<(usize, usize), DispatchError> {
+		ensure!(Self::participants(rosca_id, first).is_some(), Error::<T>::NotAParticipant);
+		ensure!(Self::participants(rosca_id, second).is_some(), Error::<T>::NotAParticipant);
+		ensure!(Self::active_roscas(rosca_id).is_some(), Error::<T>::RoscaNotActive);
+		let next_pay_by_timestamp = Self::next_pay_by_timestamp(rosca_id).ok_or(Error::<T>::NoNextPayByTimestamp)?;
+		ensure!(<pallet_timestamp::Pallet<T>>::get() < next_pay_by_timestamp, Error::<T>::RoundCutoffPassed);
+
+		let active_order = Self::active_rosca_participants_order(rosca_id).ok_or(Error::<T>::RoscaParticipantsNotFound)?;
+		// Future recipients occupy the last `remaining_rounds` slots of the order.
+		let first_future_index = active_order.len()
+			.checked_sub(Self::remaining_rounds(rosca_id)? as usize)
+			.ok_or(Error::<T>::ArithmeticUnderflow)?
+			.max(1);
+		let first_index = active_order.iter().position(|p| p == first).ok_or(Error::<T>::NotAParticipant)?;
+		let second_index = active_order.iter().position(|p| p == second).ok_or(Error::<T>::NotAParticipant)?;
+		ensure!(first_index >= first_future_index && second_index >= first_future_index, Error::<T>::PositionAlreadyPaidOut);
+
+		Ok((first_index, second_index))
+	}
 }
 
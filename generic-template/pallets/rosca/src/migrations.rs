@@ -0,0 +1,147 @@
+//! Storage migrations. Each storage version has a module with the migration to it, wrapped in a
+//! `VersionedMigration` so it only runs on chains at the version before. The runtime lists them
+//! in its `Migrations`.
+
+use crate::*;
+use frame_support::{migrations::VersionedMigration, traits::OnRuntimeUpgrade, weights::Weight};
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+
+/// Layouts of storage version 0.
+pub mod v0 {
+	use super::*;
+
+	/// `RoscaDetails` before guarantors were added.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct RoscaDetails<T: Config> {
+		pub random_order: bool,
+		pub number_of_participants: u32,
+		pub minimum_participant_threshold: u32,
+		pub contribution_amount: u32,
+		pub payment_asset: PaymentAssets,
+		pub contribution_frequency: <T as pallet_timestamp::Config>::Moment,
+		pub start_by_timestamp: <T as pallet_timestamp::Config>::Moment,
+		pub name: BoundedVec<u8, <T as Config>::StringLimit>
+	}
+
+	impl<T: Config> RoscaDetails<T> {
+		/// The same details, without a guarantor.
+		pub fn upgrade(self) -> crate::RoscaDetails<T> {
+			crate::RoscaDetails {
+				random_order: self.random_order,
+				number_of_participants: self.number_of_participants,
+				minimum_participant_threshold: self.minimum_participant_threshold,
+				contribution_amount: self.contribution_amount,
+				payment_asset: self.payment_asset,
+				contribution_frequency: self.contribution_frequency,
+				start_by_timestamp: self.start_by_timestamp,
+				name: self.name,
+				guarantor: None
+			}
+		}
+	}
+}
+
+/// Version 1 adds a guarantor to `RoscaDetails` and records the round each active Rosca is in and
+/// what its members have paid into and received from its pots.
+pub mod v1 {
+	use super::*;
+
+	/// Rewrites the details of pending and active Roscas in the new layout, and sets the
+	/// `CurrentRound` and `PotLedgers` of active Roscas. Completed Roscas kept no details before
+	/// version 1.
+	pub struct UncheckedMigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for UncheckedMigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated: u64 = 0;
+			ActiveRoscas::<T>::translate::<v0::RoscaDetails<T>, _>(|_, rosca| {
+				translated += 1;
+				Some(rosca.upgrade())
+			});
+			PendingRoscaDetails::<T>::translate::<v0::RoscaDetails<T>, _>(|_, rosca| {
+				translated += 1;
+				Some(rosca.upgrade())
+			});
+
+			// Version 0 numbered rounds from the length of the order, which is the best record of
+			// the round reached there.
+			let mut rounds_set: u64 = 0;
+			let mut ledgers_set: u64 = 0;
+			for (rosca_id, order) in ActiveRoscaParticipantsOrder::<T>::iter() {
+				let remaining_rounds = Pallet::<T>::remaining_rounds(rosca_id).unwrap_or_default();
+				let current_round = (order.len() as u32).saturating_sub(remaining_rounds).max(1);
+				CurrentRound::<T>::insert(rosca_id, current_round);
+				rounds_set += 1;
+
+				// Version 0 kept no record of pot payments, so take every completed round as paid
+				// in full. The recipient heads the order, followed by the members paid out.
+				let contribution = ActiveRoscas::<T>::get(rosca_id).map_or(0, |rosca| rosca.contribution_amount as Balance);
+				let members = order.len() as Balance;
+				let completed_rounds = (current_round - 1) as Balance;
+				let current_contributions = Pallet::<T>::current_contribution_count(rosca_id) as Balance;
+				for (index, member) in order.iter().enumerate() {
+					let paid_this_round = Pallet::<T>::current_contributors(rosca_id, member).is_some() as Balance;
+					let (received, paid) = if index == 0 {
+						(current_contributions, completed_rounds)
+					} else if index < current_round as usize {
+						(members - 1, completed_rounds - 1 + paid_this_round)
+					} else {
+						(0, completed_rounds + paid_this_round)
+					};
+					PotLedgers::<T>::insert(rosca_id, member, PotLedger {
+						paid_in: paid * contribution,
+						received: received * contribution
+					});
+					ledgers_set += 1;
+				}
+			}
+
+			log::info!(
+				target: LOG_TARGET,
+				"Migrated {} Rosca details and set {} current rounds and {} pot ledgers for v1",
+				translated,
+				rounds_set,
+				ledgers_set
+			);
+			T::DbWeight::get().reads_writes(translated + 5 * rounds_set + ledgers_set, translated + rounds_set + ledgers_set)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let active = ActiveRoscas::<T>::iter_keys().count() as u32;
+			let pending = PendingRoscaDetails::<T>::iter_keys().count() as u32;
+			Ok((active, pending).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let (active, pending): (u32, u32) = Decode::decode(&mut &state[..])
+				.map_err(|_| "Failed to decode the pre-upgrade state")?;
+
+			// Iterating skips values that don't decode, so every Rosca must still be counted.
+			ensure!(ActiveRoscas::<T>::iter_values().count() as u32 == active, "Active Roscas were lost in the migration");
+			ensure!(PendingRoscaDetails::<T>::iter_values().count() as u32 == pending, "Pending Roscas were lost in the migration");
+			ensure!(
+				ActiveRoscas::<T>::iter_values().chain(PendingRoscaDetails::<T>::iter_values()).all(|rosca| rosca.guarantor.is_none()),
+				"Migrated Roscas have a guarantor"
+			);
+			ensure!(
+				ActiveRoscas::<T>::iter_keys().all(|rosca_id| Pallet::<T>::current_round(rosca_id) > 0),
+				"Active Roscas have no current round"
+			);
+
+			Ok(())
+		}
+	}
+
+	/// [`UncheckedMigrateToV1`], run only on chains at storage version 0.
+	pub type MigrateV0ToV1<T> = VersionedMigration<
+		0,
+		1,
+		UncheckedMigrateToV1<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight
+	>;
+}
@@ -1,14 +1,17 @@
 use crate as pallet_rosca;
 use frame_support::{
 	derive_impl, weights::constants::RocksDbWeight, parameter_types,
-	traits::AsEnsureOriginWithArg,
+	traits::{AsEnsureOriginWithArg, fungibles, tokens::Preservation},
+	pallet_prelude::{DispatchError, DispatchResult},
+	weights::Weight,
 };
 use frame_system::{mocking::MockBlock, GenesisConfig, EnsureSigned, EnsureRoot};
 use sp_runtime::{
 	traits::{ConstU64, ConstU32, ConstU128, IdentifyAccount, Verify, BlakeTwo256}, 
-	BuildStorage, MultiSignature,
+	BuildStorage, MultiSignature, Permill,
 };
 use sp_runtime::traits::IdentityLookup;
+use xcm::{latest::{Junction::Parachain, Location}, VersionedLocation};
 
 type Balance = u128;
 // pub type BlockNumber = u32;
@@ -28,6 +31,8 @@ frame_support::construct_runtime!(
 		RoscaPallet: pallet_rosca,
 		Timestamp: pallet_timestamp,
 		Assets: pallet_assets,
+		Preimage: pallet_preimage,
+		Scheduler: pallet_scheduler,
 	}
 );
 
@@ -108,6 +113,9 @@ impl pallet_assets::Config for Test {
 
 parameter_types! {
 	pub const RoscaPalletId: PalletId = PalletId(*b"py/rosca");
+	pub const FeeDestinationAccount: u64 = 100;
+	pub const MaxOrganiserFee: Permill = Permill::from_percent(10);
+	pub const MaxFee: Permill = Permill::from_percent(12);
 }
 
 impl crate::Config for Test {
@@ -117,6 +125,61 @@ impl crate::Config for Test {
 	type MaxInvitedParticipants = ConstU32<149>;
 	type PalletId = RoscaPalletId;
 	type StringLimit = ConstU32<50>;
+	type AdminOrigin = EnsureRoot<u64>;
+	type FeeDestination = FeeDestinationAccount;
+	type MaxOrganiserFee = MaxOrganiserFee;
+	type MaxFee = MaxFee;
+	type NativeCurrency = Balances;
+	type MaxSponsoredCallsPerRound = ConstU32<2>;
+	type PayoutSender = MockPayoutSender;
+	type RuntimeCall = RuntimeCall;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	type AutopayInterval = ConstU64<5>;
+}
+
+impl pallet_preimage::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<u64>;
+	type Consideration = ();
+}
+
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Weight::from_parts(1_000_000_000_000, u64::MAX);
+}
+
+impl pallet_scheduler::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type PalletsOrigin = OriginCaller;
+	type RuntimeCall = RuntimeCall;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<u64>;
+	type OriginPrivilegeCmp = frame_support::traits::EqualPrivilegeOnly;
+	type MaxScheduledPerBlock = ConstU32<50>;
+	type WeightInfo = ();
+	type Preimages = Preimage;
+}
+
+parameter_types! {
+	// Stands in for this chain's sovereign account on Asset Hub
+	pub const AssetHubSovereign: u64 = 1000;
+}
+
+/// Sends payouts to Asset Hub by moving them to `AssetHubSovereign`. Any other destination
+/// is unroutable.
+pub struct MockPayoutSender;
+impl pallet_rosca::PayoutSender<u64> for MockPayoutSender {
+	fn send_payout(from: &u64, asset: u32, amount: Balance, destination: VersionedLocation) -> DispatchResult {
+		let destination = Location::try_from(destination).map_err(|_| DispatchError::Other("BadVersion"))?;
+		if !matches!(destination.unpack(), (1, [Parachain(1000), ..])) {
+			return Err(DispatchError::Other("Unroutable"));
+		}
+		<Assets as fungibles::Mutate<u64>>::transfer(asset, from, &AssetHubSovereign::get(), amount, Preservation::Expendable)?;
+		Ok(())
+	}
 }
 
 parameter_types! {
@@ -136,8 +199,26 @@ impl pallet_timestamp::Config for Test {
 }
 
 
+/// Test externalities that check the pallet's invariants once the test body has run.
+pub struct TestExt(sp_io::TestExternalities);
+
+impl TestExt {
+	pub fn execute_with<R>(mut self, test: impl FnOnce() -> R) -> R {
+		self.0.execute_with(|| {
+			let result = test();
+			assert_eq!(RoscaPallet::do_try_state(), Ok(()));
+			result
+		})
+	}
+}
+
 // Build genesis storage according to the mock runtime.
-pub fn new_test_ext() -> sp_io::TestExternalities {
+pub fn new_test_ext() -> TestExt {
+	new_test_ext_with(Default::default())
+}
+
+// Build genesis storage with the given Rosca genesis config
+pub fn new_test_ext_with(rosca: pallet_rosca::GenesisConfig<Test>) -> TestExt {
 	let mut test = GenesisConfig::<Test>::default().build_storage().unwrap();
 
 	pallet_assets::GenesisConfig::<Test> {
@@ -168,6 +249,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	.assimilate_storage(&mut test)
 	.unwrap();
 
-	test.into()
-}
+	rosca.assimilate_storage(&mut test).unwrap();
 
+	TestExt(test.into())
+}
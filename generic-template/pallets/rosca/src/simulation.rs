@@ -0,0 +1,395 @@
+//! Economic simulation of Rosca configurations. Thousands of Roscas run through the pallet on
+//! the mock runtime, with participants who pay late, miss payments or stop paying once they've
+//! received their payout, and what that cost is written out as CSV.
+//!
+//! Each CSV row is one configuration: a participant count, a deposit policy and a grace period.
+//! The pallet has no grace period of its own, so one is modelled the way a product would offer
+//! it: participants are told their payment is due `grace` before the on-chain cutoff, so a
+//! payment that's late by less than that still lands in time.
+//!
+//! The sweep is an ignored test. Run it in release mode, optionally setting the number of Roscas
+//! per configuration, the seed and a file to write the CSV to instead of stdout:
+//!
+//! ```text
+//! ROSCA_SIMULATION_RUNS=5000 ROSCA_SIMULATION_CSV=sweep.csv \
+//!     cargo test --release -p pallet-rosca simulation::sweep -- --ignored --nocapture
+//! ```
+
+use std::{collections::BTreeMap, fmt::Write as _};
+
+use frame_support::{assert_ok, traits::fungibles::Mutate};
+use sp_runtime::traits::Dispatchable;
+
+use crate::{mock::*, Event, PaymentAssets, RoscaStatus};
+
+const USDT: u32 = 1984;
+const CONTRIBUTION: u32 = 100;
+const FREQUENCY: u64 = 1_000;
+/// First simulated account. Lower accounts have balances in the mock genesis.
+const FIRST_ACCOUNT: u64 = 10;
+const SEED: u64 = 0x5EED;
+
+/// How participants behave. Each participant decides independently every round.
+#[derive(Clone, Copy)]
+struct Behaviour {
+    /// Chance of paying at or after the due date, by less than half a round
+    late: f64,
+    /// Chance of skipping a round's payment altogether
+    miss: f64,
+    /// Chance of paying nothing more once the participant has received their payout
+    run: f64,
+}
+
+const BEHAVIOUR: Behaviour = Behaviour { late: 0.15, miss: 0.05, run: 0.05 };
+
+/// The security deposit each participant posts before the Rosca starts.
+#[derive(Clone, Copy)]
+enum DepositPolicy {
+    None,
+    /// This many contributions
+    Contributions(u32),
+    /// Everything a participant owes once they've received their payout
+    Full,
+}
+
+impl DepositPolicy {
+    fn deposit(&self, participants: u32) -> u32 {
+        match self {
+            DepositPolicy::None => 0,
+            DepositPolicy::Contributions(count) => count * CONTRIBUTION,
+            DepositPolicy::Full => (participants - 1) * CONTRIBUTION,
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            DepositPolicy::None => "none".into(),
+            DepositPolicy::Contributions(count) => format!("{count}x"),
+            DepositPolicy::Full => "full".into(),
+        }
+    }
+}
+
+struct Configuration {
+    participants: u32,
+    deposit: DepositPolicy,
+    grace: u64,
+}
+
+/// What happened to the Roscas of one configuration. Amounts are in the asset's smallest unit.
+#[derive(Default)]
+struct Outcome {
+    roscas: u32,
+    completed: u32,
+    /// Completed Roscas in which every recipient received the full pot
+    fully_paid: u32,
+    /// Payments that landed after the on-chain cutoff of the round they were meant for
+    late_payments: u32,
+    /// Contributions neither paid nor covered in full by the defaulter's deposit
+    defaults: u32,
+    expected: u128,
+    shortfall: u128,
+    /// Pots owed to, and missing from, recipients in the second half of the payout order
+    late_expected: u128,
+    late_shortfall: u128,
+    deposit_posted: u128,
+    deposit_consumed: u128,
+}
+
+impl Outcome {
+    const CSV_HEADER: &'static str = concat!(
+        "participants,deposit_policy,grace_period_ms,roscas,completion_rate,full_payout_rate,",
+        "late_payments_per_rosca,defaults_per_rosca,loss_rate,late_recipient_loss_rate,",
+        "deposit_posted,deposit_consumed,deposit_consumed_rate",
+    );
+
+    fn csv_row(&self, configuration: &Configuration) -> String {
+        let rate =
+            |part: u128, whole: u128| if whole == 0 { 0.0 } else { part as f64 / whole as f64 };
+        let roscas = self.roscas as u128;
+        format!(
+            "{},{},{},{},{:.4},{:.4},{:.4},{:.4},{:.4},{:.4},{},{},{:.4}",
+            configuration.participants,
+            configuration.deposit.name(),
+            configuration.grace,
+            self.roscas,
+            rate(self.completed.into(), roscas),
+            rate(self.fully_paid.into(), roscas),
+            rate(self.late_payments.into(), roscas),
+            rate(self.defaults.into(), roscas),
+            rate(self.shortfall, self.expected),
+            rate(self.late_shortfall, self.late_expected),
+            self.deposit_posted,
+            self.deposit_consumed,
+            rate(self.deposit_consumed, self.deposit_posted),
+        )
+    }
+}
+
+/// SplitMix64, so runs are reproducible from their seed.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn chance(&mut self, probability: f64) -> bool {
+        ((self.next() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+}
+
+/// Dispatches a call, rolling back its changes if it fails as it would on chain.
+fn dispatch(who: u64, call: crate::Call<Test>) -> bool {
+    RuntimeCall::RoscaPallet(call).dispatch(RuntimeOrigin::signed(who)).is_ok()
+}
+
+/// Runs `runs` Roscas of a configuration one after another in a single externality.
+fn simulate(configuration: &Configuration, behaviour: &Behaviour, runs: u32, seed: u64) -> Outcome {
+    let mut outcome = Outcome::default();
+    let mut rng = Rng(seed);
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        for who in FIRST_ACCOUNT..FIRST_ACCOUNT + configuration.participants as u64 {
+            assert_ok!(Assets::mint_into(USDT, &who, u64::MAX as u128));
+        }
+        for _ in 0..runs {
+            simulate_rosca(configuration, behaviour, &mut rng, &mut outcome);
+        }
+    });
+    outcome
+}
+
+fn simulate_rosca(
+    configuration: &Configuration,
+    behaviour: &Behaviour,
+    rng: &mut Rng,
+    outcome: &mut Outcome,
+) {
+    let participants = configuration.participants;
+    let accounts: Vec<u64> = (FIRST_ACCOUNT..FIRST_ACCOUNT + participants as u64).collect();
+    let rosca_id = RoscaPallet::next_rosca_id();
+    let start = Timestamp::get();
+    System::reset_events();
+
+    assert_ok!(RoscaPallet::create_rosca(
+        RuntimeOrigin::signed(accounts[0]),
+        false,
+        accounts[1..].to_vec().try_into().unwrap(),
+        participants,
+        CONTRIBUTION,
+        PaymentAssets::USDT,
+        FREQUENCY,
+        start + FREQUENCY,
+        Some(0),
+        b"Simulated".to_vec().try_into().unwrap(),
+    ));
+    for (position, &who) in accounts.iter().enumerate().skip(1) {
+        assert_ok!(RoscaPallet::join_rosca(
+            RuntimeOrigin::signed(who),
+            rosca_id,
+            Some(position as u32)
+        ));
+    }
+    let deposit = configuration.deposit.deposit(participants);
+    if deposit > 0 {
+        for &who in &accounts {
+            assert_ok!(RoscaPallet::add_to_security_deposit(
+                RuntimeOrigin::signed(who),
+                rosca_id,
+                deposit
+            ));
+        }
+    }
+    assert_ok!(RoscaPallet::start_rosca(RuntimeOrigin::signed(accounts[0]), rosca_id));
+
+    // Round `k` pays out to position `k`, with its cutoff `k + 1` rounds after the start.
+    let runners: Vec<bool> = accounts.iter().map(|_| rng.chance(behaviour.run)).collect();
+    let mut payments = Vec::new();
+    for round in 0..participants as usize {
+        let cutoff = start + (round as u64 + 1) * FREQUENCY;
+        let due = cutoff - configuration.grace;
+        for (position, &who) in accounts.iter().enumerate() {
+            if position == round
+                || (runners[position] && position < round)
+                || rng.chance(behaviour.miss)
+            {
+                continue;
+            }
+            let time = if rng.chance(behaviour.late) {
+                due + rng.below(FREQUENCY / 2)
+            } else {
+                due - 1 - rng.below(FREQUENCY / 2)
+            };
+            payments.push((time, who, cutoff));
+        }
+    }
+    payments.sort();
+
+    for (time, who, cutoff) in payments {
+        // A payment counts towards whichever round is open when it lands, and nobody pays
+        // into the round they receive.
+        let open_round = ((time - start) / FREQUENCY) as usize;
+        if accounts.get(open_round) == Some(&who) {
+            continue;
+        }
+        Timestamp::set_timestamp(time);
+        if dispatch(who, crate::Call::contribute_to_rosca { rosca_id }) && time >= cutoff {
+            outcome.late_payments += 1;
+        }
+    }
+
+    let end = start + (participants as u64) * FREQUENCY + 1;
+    Timestamp::set_timestamp(end);
+    dispatch(accounts[0], crate::Call::manually_end_rosca { rosca_id });
+
+    let mut received: BTreeMap<u64, u128> = BTreeMap::new();
+    for record in System::events() {
+        let RuntimeEvent::RoscaPallet(event) = record.event else { continue };
+        match event {
+            Event::ContributionMade { recipient, amount, platform_fee, organiser_fee, .. } => {
+                *received.entry(recipient).or_default() += amount - platform_fee - organiser_fee;
+            }
+            Event::DepositDeducted { recipient, amount, platform_fee, organiser_fee, .. } => {
+                *received.entry(recipient).or_default() += amount - platform_fee - organiser_fee;
+                outcome.deposit_consumed += amount;
+            }
+            Event::GuaranteeDrawn { recipient, amount, .. }
+            | Event::InsuranceClaimPaid { recipient, amount, .. } => {
+                *received.entry(recipient).or_default() += amount;
+            }
+            Event::ParticipantDefaulted { .. } => outcome.defaults += 1,
+            _ => {}
+        }
+    }
+
+    let pot = (participants - 1) as u128 * CONTRIBUTION as u128;
+    let mut fully_paid = true;
+    for (position, who) in accounts.iter().enumerate() {
+        let shortfall = pot.saturating_sub(received.get(who).copied().unwrap_or(0));
+        outcome.expected += pot;
+        outcome.shortfall += shortfall;
+        if position * 2 >= accounts.len() {
+            outcome.late_expected += pot;
+            outcome.late_shortfall += shortfall;
+        }
+        fully_paid &= shortfall == 0;
+    }
+
+    let completed = RoscaPallet::rosca_status(rosca_id) == Some(RoscaStatus::Completed);
+    outcome.roscas += 1;
+    outcome.completed += completed as u32;
+    outcome.fully_paid += (completed && fully_paid) as u32;
+    outcome.deposit_posted += deposit as u128 * participants as u128;
+
+    Timestamp::set_timestamp(end + 1);
+}
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name).ok().and_then(|value| value.parse().ok()).unwrap_or(default)
+}
+
+#[test]
+#[ignore = "sweeps thousands of Roscas; run in release mode with --ignored"]
+fn sweep() {
+    let runs = env_or("ROSCA_SIMULATION_RUNS", 1_000);
+    let seed = env_or("ROSCA_SIMULATION_SEED", SEED);
+    let policies = [
+        DepositPolicy::None,
+        DepositPolicy::Contributions(1),
+        DepositPolicy::Contributions(2),
+        DepositPolicy::Full,
+    ];
+
+    let mut csv = format!("{}\n", Outcome::CSV_HEADER);
+    for participants in [3, 6, 10] {
+        for deposit in policies {
+            for grace in [0, FREQUENCY / 10, FREQUENCY / 4] {
+                let configuration = Configuration { participants, deposit, grace };
+                let outcome = simulate(&configuration, &BEHAVIOUR, runs, seed);
+                writeln!(csv, "{}", outcome.csv_row(&configuration)).unwrap();
+            }
+        }
+    }
+
+    match std::env::var("ROSCA_SIMULATION_CSV") {
+        Ok(path) => std::fs::write(path, csv).unwrap(),
+        Err(_) => print!("{csv}"),
+    }
+}
+
+#[test]
+fn punctual_participants_are_paid_in_full() {
+    let configuration =
+        Configuration { participants: 4, deposit: DepositPolicy::Contributions(1), grace: 0 };
+    let punctual = Behaviour { late: 0.0, miss: 0.0, run: 0.0 };
+    let outcome = simulate(&configuration, &punctual, 20, SEED);
+
+    assert_eq!(outcome.roscas, 20);
+    assert_eq!(outcome.completed, 20);
+    assert_eq!(outcome.fully_paid, 20);
+    assert_eq!(outcome.late_payments, 0);
+    assert_eq!(outcome.defaults, 0);
+    assert_eq!(outcome.shortfall, 0);
+    assert_eq!(outcome.deposit_consumed, 0);
+    assert_eq!(outcome.deposit_posted, 20 * 4 * CONTRIBUTION as u128);
+}
+
+#[test]
+fn full_deposits_cover_every_missed_payment() {
+    let careless = Behaviour { late: 0.0, miss: 0.3, run: 0.0 };
+    let unsecured = simulate(
+        &Configuration { participants: 6, deposit: DepositPolicy::None, grace: 0 },
+        &careless,
+        30,
+        SEED,
+    );
+    let secured = simulate(
+        &Configuration { participants: 6, deposit: DepositPolicy::Full, grace: 0 },
+        &careless,
+        30,
+        SEED,
+    );
+
+    assert_eq!(unsecured.completed, 30);
+    assert!(unsecured.defaults > 0);
+    assert_eq!(unsecured.shortfall, unsecured.defaults as u128 * CONTRIBUTION as u128);
+    assert_eq!(unsecured.deposit_consumed, 0);
+
+    assert_eq!(secured.completed, 30);
+    assert_eq!(secured.defaults, 0);
+    assert_eq!(secured.shortfall, 0);
+    assert_eq!(secured.fully_paid, 30);
+    assert!(secured.deposit_consumed > 0 && secured.deposit_consumed <= secured.deposit_posted);
+}
+
+#[test]
+fn a_grace_period_turns_late_payments_into_timely_ones() {
+    let tardy = Behaviour { late: 0.5, miss: 0.0, run: 0.0 };
+    let strict = simulate(
+        &Configuration { participants: 5, deposit: DepositPolicy::None, grace: 0 },
+        &tardy,
+        30,
+        SEED,
+    );
+    let lenient = simulate(
+        &Configuration { participants: 5, deposit: DepositPolicy::None, grace: FREQUENCY / 2 },
+        &tardy,
+        30,
+        SEED,
+    );
+
+    assert!(strict.late_payments > 0);
+    assert!(strict.shortfall > 0);
+    assert_eq!(lenient.late_payments, 0);
+    assert_eq!(lenient.shortfall, 0);
+    assert_eq!(lenient.fully_paid, 30);
+}
@@ -0,0 +1,79 @@
+//! Fee sponsorship. The creator of a Rosca can prepay a budget in the native token that covers
+//! the transaction fees of its members' `join_rosca`, `contribute_to_rosca` and
+//! `add_to_security_deposit` calls. The runtime's transaction payment extension asks this pallet
+//! whether a call is sponsored, charges the estimated fee against the budget before dispatch and
+//! settles the actual fee afterwards. Each member gets at most `MaxSponsoredCallsPerRound`
+//! sponsored calls per round. The sponsorship account always keeps the existential deposit on
+//! top of the budget, so paying fees never reaps it.
+
+use crate::*;
+use frame_support::pallet_prelude::*;
+use frame_support::traits::fungible::Mutate;
+
+impl<T: Config> Pallet<T> {
+	pub fn sponsorship_account_id(rosca_id: RoscaId) -> T::AccountId {
+		T::PalletId::get().into_sub_account_truncating((b"sponsor", rosca_id))
+	}
+
+	/// The Rosca whose sponsorship may pay for `call`, if it is one of the sponsorable calls.
+	pub fn sponsored_rosca(call: &Call<T>) -> Option<RoscaId> {
+		match call {
+			Call::join_rosca { rosca_id, .. } |
+			Call::contribute_to_rosca { rosca_id } |
+			Call::add_to_security_deposit { rosca_id, .. } => Some(*rosca_id),
+			_ => None,
+		}
+	}
+
+	/// Whether the sponsorship of `rosca_id` can pay a `fee` for a call by `who`. The caller must
+	/// be a participant or invitee, be under the per-round limit, and the budget must cover the fee.
+	pub fn can_sponsor(rosca_id: RoscaId, who: &AccountIdOf<T>, fee: Balance) -> bool {
+		let is_member = Self::participants(rosca_id, who).is_some() ||
+			Self::invited_preverified_participants(rosca_id, who).is_some();
+
+		is_member &&
+			Self::sponsored_calls_this_round(rosca_id, who) < T::MaxSponsoredCallsPerRound::get() &&
+			Self::sponsorship_balance(rosca_id) >= fee
+	}
+
+	/// Reserves `fee` from the sponsorship of `rosca_id` ahead of dispatching a call by `who`,
+	/// counting the call against their limit for the round.
+	pub fn charge_sponsorship(rosca_id: RoscaId, who: &AccountIdOf<T>, fee: Balance) -> DispatchResult {
+		ensure!(Self::can_sponsor(rosca_id, who, fee), Error::<T>::SponsorshipUnavailable);
+
+		SponsorshipBalances::<T>::mutate(rosca_id, |balance| *balance = balance.saturating_sub(fee));
+		let calls = Self::sponsored_calls_this_round(rosca_id, who).saturating_add(1);
+		SponsoredCalls::<T>::insert(rosca_id, who, (Self::next_pay_by_timestamp(rosca_id), calls));
+
+		Ok(())
+	}
+
+	/// Settles a sponsored call once its `actual_fee` is known. The fee goes to the fee
+	/// destination and the rest of the `charged` amount returns to the sponsorship. Nothing
+	/// changes if the fee can't be paid.
+	pub fn settle_sponsorship(rosca_id: RoscaId, who: &AccountIdOf<T>, charged: Balance, actual_fee: Balance) -> DispatchResult {
+		let actual_fee = actual_fee.min(charged);
+		if actual_fee > 0 {
+			T::NativeCurrency::transfer(&Self::sponsorship_account_id(rosca_id), &T::FeeDestination::get(), actual_fee, Preserve)?;
+		}
+
+		SponsorshipBalances::<T>::mutate(rosca_id, |balance| *balance = balance.saturating_add(charged - actual_fee));
+
+		Self::deposit_event(Event::<T>::FeeSponsored {
+			rosca_id,
+			participant: who.clone(),
+			fee: actual_fee
+		});
+
+		Ok(())
+	}
+
+	/// Sponsored calls `who` made in the current round of `rosca_id`. Rounds are told apart by
+	/// their payment cutoff, and a pending Rosca counts as a single round.
+	pub fn sponsored_calls_this_round(rosca_id: RoscaId, who: &AccountIdOf<T>) -> u32 {
+		match Self::sponsored_calls(rosca_id, who) {
+			Some((round, calls)) if round == Self::next_pay_by_timestamp(rosca_id) => calls,
+			_ => 0,
+		}
+	}
+}
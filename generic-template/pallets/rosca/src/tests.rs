@@ -1,6 +1,6 @@
 #![allow(warnings)]
 
-use crate::{mock::*, Error, Event, RoundInfo};
+use crate::{mock::*, Error, Event, RoundInfo, ProposalAction, VoteThreshold, InsuranceConfig, PaymentAssets, RoscaStatus, InvariantViolation, CurrentContributionCount, RoscaSecurityDeposits, PotLedger};
 use frame_support::{
     assert_noop, assert_ok, assert_err,
     traits::{OnFinalize, OnInitialize},
@@ -8,10 +8,11 @@ use frame_support::{
 };
 use frame_support::pallet_prelude::DispatchError::Token;
 use frame_support::testing_prelude::bounded_vec;
-use sp_runtime::{traits::BadOrigin, DispatchError, ModuleError};
+use sp_runtime::{traits::BadOrigin, DispatchError, ModuleError, Permill};
 use sp_runtime::TokenError::FundsUnavailable;
 use frame_support::traits::fungible::Mutate; 
 use sp_core::ConstU32;
+use xcm::{latest::{Junction::{AccountId32, Parachain}, Location}, VersionedLocation};
 
 // Helper function to advance timestamp and block number
 fn advance_time_and_block(timestamp: u64) {
@@ -79,6 +80,30 @@ fn setup_basic_rosca_usdc() -> (u64, Vec<u64>) {
     (creator, participants_vec)
 } 
 
+// Setup a ROSCA with four participants, paying out to 1, 2, 3 and then 0 with cutoffs at 11, 21,
+// 31 and 41
+fn setup_four_member_rosca() {
+    System::set_block_number(1);
+    Timestamp::set_timestamp(1);
+
+    assert_ok!(RoscaPallet::create_rosca(
+        RuntimeOrigin::signed(1),
+        false,
+        bounded_vec![2, 3, 0],
+        4,
+        100,
+        crate::PaymentAssets::USDT,
+        10,
+        20,
+        Some(0),
+        bounded_vec![4]
+    ));
+    for participant in [2, 3, 0] {
+        assert_ok!(RoscaPallet::join_rosca(RuntimeOrigin::signed(participant), 0, None));
+    }
+    assert_ok!(RoscaPallet::start_rosca(RuntimeOrigin::signed(1), 0));
+}
+
 #[test]
 fn create_rosca_works() {
     new_test_ext().execute_with(|| {
@@ -612,6 +637,22 @@ fn process_defaulters_sufficient_deposit() {
     });
 }
 
+#[test]
+fn contributing_after_the_cutoff_into_ones_own_round_pays_nothing() {
+    new_test_ext().execute_with(|| {
+        setup_basic_rosca();
+        Timestamp::set_timestamp(15);
+
+        // Catching up makes 2 the recipient, so the overdue round is processed and nothing paid.
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+        assert_eq!(RoscaPallet::current_round(0), 2);
+        assert_eq!(RoscaPallet::eligible_claimant(0), Some(2));
+        assert_eq!(RoscaPallet::default_count(0, &3), 1);
+        assert_eq!(RoscaPallet::current_contributors(0, 2), None);
+        assert_eq!(Assets::balance(1984, 2), 10_000);
+    });
+}
+
 #[test]
 fn process_defaulters_insufficient_deposit() {
     new_test_ext().execute_with(|| {
@@ -895,3 +936,1646 @@ fn test_rosca_started_event_emitted_with_manual_rounds() {
         ));
     });
 }
+
+#[test]
+fn contribute_for_credits_participant_and_charges_payer() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+
+        // Non-participant account 0 pays participant 2's contribution.
+        assert_ok!(RoscaPallet::contribute_for(RuntimeOrigin::signed(0), 0, 2));
+        assert_eq!(Assets::balance(1984, 0), 9900);
+        assert_eq!(Assets::balance(1984, 2), 10000);
+        assert_eq!(Assets::balance(1984, creator), 10100);
+        assert!(RoscaPallet::current_contributors(0, &2).is_some());
+        assert_eq!(RoscaPallet::current_contribution_count(0), 1);
+
+        System::assert_last_event(RuntimeEvent::RoscaPallet(Event::ContributionMade {
+            rosca_id: 0,
+            contributor: 2,
+            recipient: creator,
+            amount: 100,
+            payer: 0,
+            platform_fee: 0,
+            organiser_fee: 0,
+        }));
+
+        // Participant 2 is already credited for this round.
+        assert_noop!(
+            RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::AlreadyContributed
+        );
+    });
+}
+
+#[test]
+fn contribute_for_fails_for_non_participant_or_recipient() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+
+        assert_noop!(
+            RoscaPallet::contribute_for(RuntimeOrigin::signed(2), 0, 4),
+            Error::<Test>::NotAParticipant
+        );
+        assert_noop!(
+            RoscaPallet::contribute_for(RuntimeOrigin::signed(2), 0, creator),
+            Error::<Test>::CantContributeToSelf
+        );
+    });
+}
+
+#[test]
+fn add_to_security_deposit_for_credits_participant() {
+    new_test_ext().execute_with(|| {
+        let (_, _) = setup_basic_rosca();
+
+        assert_ok!(RoscaPallet::add_to_security_deposit_for(RuntimeOrigin::signed(0), 0, 3, 150));
+        assert_eq!(RoscaPallet::security_deposit(0, &3), Some(150));
+        assert_eq!(RoscaPallet::security_deposit(0, &0), None);
+        assert_eq!(Assets::balance(1984, 0), 9850);
+        assert_eq!(Assets::balance(1984, 3), 10000);
+
+        System::assert_last_event(RuntimeEvent::RoscaPallet(Event::SecurityDepositContribution {
+            rosca_id: 0,
+            depositor: 3,
+            amount: 150,
+            payer: 0,
+        }));
+    });
+}
+
+#[test]
+fn swap_positions_updates_order_and_rounds() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+
+        assert_ok!(RoscaPallet::propose_swap(RuntimeOrigin::signed(2), 0, 3));
+        assert_eq!(RoscaPallet::pending_swaps(0, &2), Some(3));
+
+        assert_ok!(RoscaPallet::accept_swap(RuntimeOrigin::signed(3), 0, 2));
+        assert_eq!(RoscaPallet::pending_swaps(0, &2), None);
+        assert_eq!(RoscaPallet::active_rosca_participants_order(0).unwrap().into_inner(), vec![1, 2, 3]);
+
+        let expected_rounds: BoundedVec<_, ConstU32<150>> = bounded_vec![
+            RoundInfo {
+                round_number: 2,
+                payment_cutoff: 21,
+                expected_contributors: bounded_vec![1, 2],
+                recipient: 3,
+            },
+            RoundInfo {
+                round_number: 3,
+                payment_cutoff: 31,
+                expected_contributors: bounded_vec![1, 3],
+                recipient: 2,
+            }
+        ];
+        System::assert_last_event(RuntimeEvent::RoscaPallet(Event::PositionsSwapped {
+            rosca_id: 0,
+            proposer: 2,
+            counterparty: 3,
+            rounds: expected_rounds,
+        }));
+
+        // Participant 3 now receives the next pot.
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(3), 0));
+        assert_eq!(RoscaPallet::eligible_claimant(0), Some(3));
+    });
+}
+
+#[test]
+fn swap_fails_for_paid_out_or_unproposed_positions() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+
+        assert_noop!(
+            RoscaPallet::propose_swap(RuntimeOrigin::signed(2), 0, creator),
+            Error::<Test>::PositionAlreadyPaidOut
+        );
+        assert_noop!(
+            RoscaPallet::propose_swap(RuntimeOrigin::signed(2), 0, 2),
+            Error::<Test>::CantSwapWithSelf
+        );
+        assert_noop!(
+            RoscaPallet::accept_swap(RuntimeOrigin::signed(3), 0, 2),
+            Error::<Test>::SwapNotProposed
+        );
+
+        assert_ok!(RoscaPallet::propose_swap(RuntimeOrigin::signed(2), 0, 3));
+        assert_noop!(
+            RoscaPallet::accept_swap(RuntimeOrigin::signed(1), 0, 2),
+            Error::<Test>::SwapNotProposed
+        );
+
+        // After the first round the swap can no longer be accepted once the cutoff passes.
+        Timestamp::set_timestamp(11);
+        assert_noop!(
+            RoscaPallet::accept_swap(RuntimeOrigin::signed(3), 0, 2),
+            Error::<Test>::RoundCutoffPassed
+        );
+    });
+}
+
+#[test]
+fn substitute_takes_over_position_and_deposit() {
+    new_test_ext().execute_with(|| {
+        let (_, _) = setup_basic_rosca();
+        assert_ok!(RoscaPallet::add_to_security_deposit(RuntimeOrigin::signed(2), 0, 100));
+
+        assert_ok!(RoscaPallet::nominate_substitute(RuntimeOrigin::signed(2), 0, 5));
+        assert_eq!(RoscaPallet::pending_substitutions(0, &2), Some(5));
+        assert_noop!(
+            RoscaPallet::accept_substitution(RuntimeOrigin::signed(4), 0, 2),
+            Error::<Test>::SubstitutionNotNominated
+        );
+        assert_ok!(RoscaPallet::accept_substitution(RuntimeOrigin::signed(5), 0, 2));
+
+        assert_eq!(RoscaPallet::participants(0, &2), None);
+        assert_eq!(RoscaPallet::participants(0, &5), Some(1));
+        assert_eq!(RoscaPallet::security_deposit(0, &2), None);
+        assert_eq!(RoscaPallet::security_deposit(0, &5), Some(100));
+        assert_eq!(RoscaPallet::active_rosca_participants_order(0).unwrap().into_inner(), vec![1, 3, 5]);
+
+        System::assert_last_event(RuntimeEvent::RoscaPallet(Event::ParticipantSubstituted {
+            rosca_id: 0,
+            departing: 2,
+            substitute: 5,
+            already_received: false,
+            outstanding_contributions: 2,
+            inherited_debt: 0,
+            deposit_transferred: 100,
+            debt_deposit: 0,
+        }));
+    });
+}
+
+#[test]
+fn substitute_of_paid_out_participant_secures_inherited_debt() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+        assert_ok!(Assets::transfer(RuntimeOrigin::signed(0), parity_scale_codec::Compact(1984), 5, 300));
+
+        // The creator is receiving the first pot, so can't hand it over yet.
+        assert_ok!(RoscaPallet::nominate_substitute(RuntimeOrigin::signed(creator), 0, 5));
+        assert_noop!(
+            RoscaPallet::accept_substitution(RuntimeOrigin::signed(5), 0, creator),
+            Error::<Test>::CantSubstituteRecipient
+        );
+
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(3), 0));
+        assert_eq!(RoscaPallet::eligible_claimant(0), Some(2));
+
+        assert_ok!(RoscaPallet::accept_substitution(RuntimeOrigin::signed(5), 0, creator));
+        System::assert_last_event(RuntimeEvent::RoscaPallet(Event::ParticipantSubstituted {
+            rosca_id: 0,
+            departing: creator,
+            substitute: 5,
+            already_received: true,
+            outstanding_contributions: 2,
+            inherited_debt: 200,
+            deposit_transferred: 0,
+            debt_deposit: 200,
+        }));
+        assert_eq!(RoscaPallet::security_deposit(0, &5), Some(200));
+        assert_eq!(Assets::balance(1984, 5), 100);
+
+        // A contribution the substitute doesn't pay is taken from the secured debt.
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(3), 0));
+        Timestamp::set_timestamp(21);
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+        System::assert_has_event(RuntimeEvent::RoscaPallet(Event::DepositDeducted {
+            rosca_id: 0,
+            contributor: 5,
+            recipient: 2,
+            amount: 100,
+            sufficient: true,
+            platform_fee: 0,
+            organiser_fee: 0,
+        }));
+        assert_eq!(RoscaPallet::security_deposit(0, &5), Some(100));
+    });
+}
+
+#[test]
+fn nominate_existing_participant_as_substitute_fails() {
+    new_test_ext().execute_with(|| {
+        let (_, _) = setup_basic_rosca();
+
+        assert_noop!(
+            RoscaPallet::nominate_substitute(RuntimeOrigin::signed(2), 0, 3),
+            Error::<Test>::SubstituteAlreadyParticipant
+        );
+        assert_noop!(
+            RoscaPallet::nominate_substitute(RuntimeOrigin::signed(4), 0, 5),
+            Error::<Test>::NotAParticipant
+        );
+    });
+}
+
+#[test]
+fn proposal_executes_once_majority_reached() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+
+        assert_ok!(RoscaPallet::propose(
+            RuntimeOrigin::signed(creator),
+            0,
+            ProposalAction::ExtendDeadline { extension: 5 },
+            VoteThreshold::Majority
+        ));
+        // Proposer's aye alone is not a majority of three.
+        assert!(RoscaPallet::proposals(0, 0).is_some());
+        assert_eq!(RoscaPallet::next_pay_by_timestamp(0), Some(11));
+
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(2), 0, 0, true));
+        assert!(RoscaPallet::proposals(0, 0).is_none());
+        assert_eq!(RoscaPallet::proposal_votes((0, 0, 2)), None);
+        assert_eq!(RoscaPallet::next_pay_by_timestamp(0), Some(16));
+        assert_eq!(RoscaPallet::final_pay_by_timestamp(0), Some(36));
+
+        System::assert_last_event(RuntimeEvent::RoscaPallet(Event::ProposalApproved {
+            rosca_id: 0,
+            proposal_id: 0,
+        }));
+    });
+}
+
+#[test]
+fn forgive_default_by_vote() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+        Timestamp::set_timestamp(15);
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+        assert_eq!(RoscaPallet::default_count(0, &3), 1);
+
+        assert_ok!(RoscaPallet::propose(
+            RuntimeOrigin::signed(creator),
+            0,
+            ProposalAction::ForgiveDefault { participant: 3 },
+            VoteThreshold::Supermajority
+        ));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(2), 0, 0, true));
+        assert_eq!(RoscaPallet::default_count(0, &3), 0);
+    });
+}
+
+#[test]
+fn failed_action_closes_the_proposal_and_keeps_the_vote() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+
+        // 3 has no defaults to forgive.
+        assert_ok!(RoscaPallet::propose(
+            RuntimeOrigin::signed(creator),
+            0,
+            ProposalAction::ForgiveDefault { participant: 3 },
+            VoteThreshold::Majority
+        ));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(2), 0, 0, true));
+
+        System::assert_last_event(RuntimeEvent::RoscaPallet(Event::ProposalExecutionFailed {
+            rosca_id: 0,
+            proposal_id: 0,
+            error: Error::<Test>::NoDefaultsToForgive.into(),
+        }));
+        System::assert_has_event(RuntimeEvent::RoscaPallet(Event::Voted {
+            rosca_id: 0,
+            proposal_id: 0,
+            voter: 2,
+            aye: true,
+        }));
+        assert!(RoscaPallet::proposals(0, 0).is_none());
+        assert_eq!(RoscaPallet::default_count(0, &3), 0);
+    });
+}
+
+#[test]
+fn proposal_rejected_when_threshold_unreachable() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+
+        assert_noop!(
+            RoscaPallet::propose(RuntimeOrigin::signed(creator), 0, ProposalAction::EndEarly, VoteThreshold::Majority),
+            Error::<Test>::ThresholdTooLow
+        );
+
+        assert_ok!(RoscaPallet::propose(RuntimeOrigin::signed(creator), 0, ProposalAction::EndEarly, VoteThreshold::Unanimous));
+        assert_noop!(
+            RoscaPallet::vote(RuntimeOrigin::signed(creator), 0, 0, true),
+            Error::<Test>::AlreadyVoted
+        );
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(2), 0, 0, false));
+        assert!(RoscaPallet::proposals(0, 0).is_none());
+        assert!(RoscaPallet::active_roscas(0).is_some());
+
+        System::assert_last_event(RuntimeEvent::RoscaPallet(Event::ProposalRejected {
+            rosca_id: 0,
+            proposal_id: 0,
+        }));
+    });
+}
+
+#[test]
+fn end_early_by_vote_allows_deposit_claims() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+        assert_ok!(RoscaPallet::add_to_security_deposit(RuntimeOrigin::signed(2), 0, 200));
+
+        assert_ok!(RoscaPallet::propose(RuntimeOrigin::signed(creator), 0, ProposalAction::EndEarly, VoteThreshold::Supermajority));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(2), 0, 0, true));
+        assert!(RoscaPallet::completed_roscas(0).is_some());
+        assert!(RoscaPallet::active_roscas(0).is_none());
+
+        advance_time_and_block(1);
+        assert_ok!(RoscaPallet::claim_security_deposit(RuntimeOrigin::signed(2), 0, crate::PaymentAssets::USDT));
+        assert_eq!(Assets::balance(1984, 2), 10000);
+    });
+}
+
+#[test]
+fn end_early_refunds_members_still_waiting_for_their_pot() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+        for participant in [1, 2, 3] {
+            assert_ok!(RoscaPallet::add_to_security_deposit(RuntimeOrigin::signed(participant), 0, 200));
+        }
+
+        // The creator receives the first pot, participant 2 is receiving the second and has
+        // been paid by 3 so far, and 3 would receive the last.
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(3), 0));
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(3), 0));
+        assert_eq!(RoscaPallet::eligible_claimant(0), Some(2));
+
+        assert_ok!(RoscaPallet::propose(RuntimeOrigin::signed(creator), 0, ProposalAction::EndEarly, VoteThreshold::Supermajority));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(2), 0, 0, true));
+
+        // The creator received 200 and paid nothing in, so their deposit refunds 3's payments.
+        System::assert_has_event(RuntimeEvent::RoscaPallet(Event::EarlyEndRefund {
+            rosca_id: 0,
+            member: 3,
+            amount: 200,
+        }));
+        assert_eq!(RoscaPallet::security_deposit(0, &creator), Some(0));
+        assert_eq!(RoscaPallet::eligible_claimant(0), None);
+        assert_eq!(RoscaPallet::next_pay_by_timestamp(0), None);
+        assert_eq!(RoscaPallet::active_rosca_participants_order(0), None);
+        assert_eq!(RoscaPallet::current_contributors(0, 3), None);
+
+        advance_time_and_block(1);
+        assert_noop!(
+            RoscaPallet::claim_security_deposit(RuntimeOrigin::signed(creator), 0, crate::PaymentAssets::USDT),
+            Error::<Test>::SecurityDepositIsZero
+        );
+        assert_ok!(RoscaPallet::claim_security_deposit(RuntimeOrigin::signed(2), 0, crate::PaymentAssets::USDT));
+        assert_ok!(RoscaPallet::claim_security_deposit(RuntimeOrigin::signed(3), 0, crate::PaymentAssets::USDT));
+
+        // Everyone ends up with what they started with.
+        for participant in [1, 2, 3] {
+            assert_eq!(Assets::balance(1984, participant), 10_000);
+        }
+        assert_eq!(Assets::balance(1984, RoscaPallet::rosca_account_id(0)), 0);
+    });
+}
+
+#[test]
+fn round_paid_by_an_ejection_while_paused_moves_on_at_resume() {
+    new_test_ext().execute_with(|| {
+        setup_four_member_rosca();
+        for contributor in [2, 3] {
+            assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(contributor), 0));
+        }
+
+        Timestamp::set_timestamp(5);
+        assert_ok!(RoscaPallet::propose(RuntimeOrigin::signed(1), 0, ProposalAction::Pause, VoteThreshold::Majority));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(2), 0, 0, true));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(3), 0, 0, true));
+        assert_eq!(RoscaPallet::paused_at(0), Some(5));
+
+        // Ejecting the last member still to pay leaves the paused round where it is.
+        assert_ok!(RoscaPallet::propose(
+            RuntimeOrigin::signed(1),
+            0,
+            ProposalAction::EjectMember { participant: 0 },
+            VoteThreshold::Supermajority
+        ));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(2), 0, 1, true));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(3), 0, 1, true));
+        assert_eq!(RoscaPallet::participants(0, 0), None);
+        assert_eq!(RoscaPallet::current_round(0), 1);
+        assert_eq!(RoscaPallet::eligible_claimant(0), Some(1));
+        assert_eq!(RoscaPallet::next_pay_by_timestamp(0), Some(11));
+
+        Timestamp::set_timestamp(8);
+        assert_ok!(RoscaPallet::propose(RuntimeOrigin::signed(1), 0, ProposalAction::Resume, VoteThreshold::Majority));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(2), 0, 2, true));
+        assert_eq!(RoscaPallet::paused_at(0), None);
+        assert_eq!(RoscaPallet::current_round(0), 2);
+        assert_eq!(RoscaPallet::eligible_claimant(0), Some(2));
+        assert_eq!(RoscaPallet::next_pay_by_timestamp(0), Some(24));
+    });
+}
+
+#[test]
+fn end_early_forfeits_nothing_when_nobody_is_owed() {
+    new_test_ext().execute_with(|| {
+        setup_four_member_rosca();
+        assert_ok!(RoscaPallet::add_to_security_deposit(RuntimeOrigin::signed(1), 0, 300));
+        assert_ok!(RoscaPallet::add_to_security_deposit(RuntimeOrigin::signed(2), 0, 100));
+        for contributor in [2, 3, 0] {
+            assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(contributor), 0));
+        }
+        assert_eq!(RoscaPallet::eligible_claimant(0), Some(2));
+
+        // Ejecting the paid-out creator shares their deposit between the recipients they won't
+        // pay, leaving everyone left even.
+        assert_ok!(RoscaPallet::propose(
+            RuntimeOrigin::signed(2),
+            0,
+            ProposalAction::EjectMember { participant: 1 },
+            VoteThreshold::Supermajority
+        ));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(3), 0, 0, true));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(0), 0, 0, true));
+        for member in [2, 3, 0] {
+            assert_eq!(RoscaPallet::pot_ledger(0, member), PotLedger { paid_in: 100, received: 100 });
+        }
+
+        assert_ok!(RoscaPallet::propose(RuntimeOrigin::signed(2), 0, ProposalAction::EndEarly, VoteThreshold::Supermajority));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(3), 0, 1, true));
+
+        assert!(!System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::RoscaPallet(Event::EarlyEndRefund { .. })
+        )));
+        assert_eq!(RoscaPallet::security_deposit(0, &2), Some(100));
+        assert_eq!(RoscaPallet::active_rosca_participants_order(0), None);
+    });
+}
+
+#[test]
+fn expired_proposal_can_be_closed() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+
+        assert_ok!(RoscaPallet::propose(
+            RuntimeOrigin::signed(creator),
+            0,
+            ProposalAction::ExtendDeadline { extension: 5 },
+            VoteThreshold::Majority
+        ));
+        assert_noop!(
+            RoscaPallet::close_proposal(RuntimeOrigin::signed(4), 0, 0),
+            Error::<Test>::ProposalStillOpen
+        );
+
+        Timestamp::set_timestamp(11);
+        assert_noop!(
+            RoscaPallet::vote(RuntimeOrigin::signed(2), 0, 0, true),
+            Error::<Test>::ProposalExpired
+        );
+        assert_ok!(RoscaPallet::close_proposal(RuntimeOrigin::signed(4), 0, 0));
+        assert!(RoscaPallet::proposals(0, 0).is_none());
+    });
+}
+
+#[test]
+fn pause_and_resume_shifts_remaining_cutoffs() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+
+        assert_ok!(RoscaPallet::propose(RuntimeOrigin::signed(creator), 0, ProposalAction::Pause, VoteThreshold::Majority));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(2), 0, 0, true));
+        assert_eq!(RoscaPallet::paused_at(0), Some(1));
+
+        assert_noop!(
+            RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0),
+            Error::<Test>::RoscaPaused
+        );
+
+        Timestamp::set_timestamp(50);
+        assert_ok!(RoscaPallet::propose(RuntimeOrigin::signed(2), 0, ProposalAction::Resume, VoteThreshold::Majority));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(3), 0, 1, true));
+        assert_eq!(RoscaPallet::paused_at(0), None);
+        assert_eq!(RoscaPallet::next_pay_by_timestamp(0), Some(60));
+        assert_eq!(RoscaPallet::final_pay_by_timestamp(0), Some(80));
+
+        let expected_rounds: BoundedVec<_, ConstU32<150>> = bounded_vec![
+            RoundInfo {
+                round_number: 1,
+                payment_cutoff: 60,
+                expected_contributors: bounded_vec![3, 2],
+                recipient: 1,
+            },
+            RoundInfo {
+                round_number: 2,
+                payment_cutoff: 70,
+                expected_contributors: bounded_vec![1, 3],
+                recipient: 2,
+            },
+            RoundInfo {
+                round_number: 3,
+                payment_cutoff: 80,
+                expected_contributors: bounded_vec![1, 2],
+                recipient: 3,
+            }
+        ];
+        System::assert_has_event(RuntimeEvent::RoscaPallet(Event::RoscaResumed {
+            rosca_id: 0,
+            paused_duration: 49,
+            rounds: expected_rounds,
+        }));
+
+        // Contributions are accepted again without any defaults being processed.
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+        assert_eq!(RoscaPallet::eligible_claimant(0), Some(creator));
+    });
+}
+
+#[test]
+fn pause_requires_creator_approval() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+
+        assert_ok!(RoscaPallet::propose(RuntimeOrigin::signed(2), 0, ProposalAction::Pause, VoteThreshold::Majority));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(3), 0, 0, true));
+        // Majority reached but the creator has not approved yet.
+        assert!(RoscaPallet::proposals(0, 0).is_some());
+        assert_eq!(RoscaPallet::paused_at(0), None);
+
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(creator), 0, 0, true));
+        assert_eq!(RoscaPallet::paused_at(0), Some(1));
+
+        // A creator nay rejects a pause outright.
+        Timestamp::set_timestamp(5);
+        assert_ok!(RoscaPallet::propose(RuntimeOrigin::signed(2), 0, ProposalAction::Resume, VoteThreshold::Majority));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(3), 0, 1, true));
+        assert_ok!(RoscaPallet::propose(RuntimeOrigin::signed(2), 0, ProposalAction::Pause, VoteThreshold::Majority));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(creator), 0, 2, false));
+        assert!(RoscaPallet::proposals(0, 2).is_none());
+        assert_eq!(RoscaPallet::paused_at(0), None);
+    });
+}
+
+#[test]
+fn pause_without_recorded_creator_needs_only_the_threshold() {
+    new_test_ext().execute_with(|| {
+        let (_, _) = setup_basic_rosca();
+        // Roscas created before creators were recorded have none.
+        crate::RoscaCreator::<Test>::remove(0);
+
+        assert_ok!(RoscaPallet::propose(RuntimeOrigin::signed(2), 0, ProposalAction::Pause, VoteThreshold::Majority));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(3), 0, 0, true));
+        assert_eq!(RoscaPallet::paused_at(0), Some(1));
+    });
+}
+
+#[test]
+fn chronic_defaulter_is_ejected_and_slot_dropped() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        Timestamp::set_timestamp(1);
+
+        let participants: BoundedVec<u64, ConstU32<149>> = vec![2, 3].try_into().unwrap();
+        assert_ok!(RoscaPallet::create_rosca(
+            RuntimeOrigin::signed(1),
+            false,
+            participants,
+            3,
+            100,
+            crate::PaymentAssets::USDT,
+            10,
+            20,
+            Some(0),
+            bounded_vec![1]
+        ));
+        assert_noop!(
+            RoscaPallet::set_max_defaults(RuntimeOrigin::signed(2), 0, Some(0)),
+            Error::<Test>::NotRoscaCreator
+        );
+        assert_ok!(RoscaPallet::set_max_defaults(RuntimeOrigin::signed(1), 0, Some(0)));
+        assert_ok!(RoscaPallet::join_rosca(RuntimeOrigin::signed(2), 0, None));
+        assert_ok!(RoscaPallet::join_rosca(RuntimeOrigin::signed(3), 0, None));
+        assert_ok!(RoscaPallet::start_rosca(RuntimeOrigin::signed(1), 0));
+
+        // Participant 3 misses round one.
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+        Timestamp::set_timestamp(15);
+        // The defaulter's own late contribution triggers the round processing that ejects them.
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(3), 0));
+
+        let expected_rounds: BoundedVec<_, ConstU32<150>> = bounded_vec![
+            RoundInfo {
+                round_number: 1,
+                payment_cutoff: 11,
+                expected_contributors: bounded_vec![2],
+                recipient: 1,
+            },
+            RoundInfo {
+                round_number: 2,
+                payment_cutoff: 21,
+                expected_contributors: bounded_vec![1],
+                recipient: 2,
+            }
+        ];
+        System::assert_has_event(RuntimeEvent::RoscaPallet(Event::ParticipantEjected {
+            rosca_id: 0,
+            participant: 3,
+            defaults: 1,
+            forfeited_deposit: 0,
+            forfeited_to: vec![],
+            slot_dropped: true,
+            final_payment_cutoff: 21,
+            rounds: expected_rounds,
+        }));
+
+        assert_eq!(RoscaPallet::participants(0, &3), None);
+        assert_eq!(RoscaPallet::participants_count(0), Some(2));
+        assert_eq!(RoscaPallet::eligible_claimant(0), Some(2));
+        assert!(RoscaPallet::completed_roscas(0).is_none());
+
+        // With the third slot dropped, round two is the last one.
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(1), 0));
+        assert!(RoscaPallet::completed_roscas(0).is_some());
+        assert_eq!(Assets::balance(1984, 2), 10000);
+        assert_eq!(Assets::balance(1984, 3), 10000);
+    });
+}
+
+#[test]
+fn eject_member_by_vote_forfeits_deposit() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+        assert_ok!(RoscaPallet::add_to_security_deposit(RuntimeOrigin::signed(3), 0, 50));
+
+        assert_noop!(
+            RoscaPallet::propose(RuntimeOrigin::signed(2), 0, ProposalAction::EjectMember { participant: 3 }, VoteThreshold::Majority),
+            Error::<Test>::ThresholdTooLow
+        );
+        assert_ok!(RoscaPallet::propose(
+            RuntimeOrigin::signed(2),
+            0,
+            ProposalAction::EjectMember { participant: 3 },
+            VoteThreshold::Supermajority
+        ));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(creator), 0, 0, true));
+
+        assert_eq!(RoscaPallet::active_rosca_participants_order(0).unwrap().into_inner(), vec![1, 2]);
+        assert_eq!(RoscaPallet::final_pay_by_timestamp(0), Some(21));
+        assert_eq!(RoscaPallet::security_deposit(0, &3), None);
+        // The deposit is shared by the recipients of the rounds 3 no longer pays into.
+        assert_eq!(Assets::balance(1984, creator), 10025);
+        assert_eq!(Assets::balance(1984, 2), 10025);
+    });
+}
+
+#[test]
+fn ejecting_the_last_outstanding_contributor_completes_the_round() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+
+        assert_ok!(RoscaPallet::propose(
+            RuntimeOrigin::signed(creator),
+            0,
+            ProposalAction::EjectMember { participant: 3 },
+            VoteThreshold::Supermajority
+        ));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(2), 0, 0, true));
+
+        // Everyone left has paid the creator, so round two starts straight away.
+        System::assert_has_event(RuntimeEvent::RoscaPallet(Event::NewRoundStarted {
+            rosca_id: 0,
+            new_eligible_recipient: 2,
+            payment_cutoff: 21,
+        }));
+        assert_eq!(RoscaPallet::eligible_claimant(0), Some(2));
+        assert_eq!(RoscaPallet::current_contribution_count(0), 0);
+
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(creator), 0));
+        assert!(RoscaPallet::completed_roscas(0).is_some());
+    });
+}
+
+#[test]
+fn ejecting_a_paid_out_member_keeps_the_round_numbers() {
+    new_test_ext().execute_with(|| {
+        setup_four_member_rosca();
+        for contributor in [2, 3, 0] {
+            assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(contributor), 0));
+        }
+        for contributor in [1, 3, 0] {
+            assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(contributor), 0));
+        }
+        assert_eq!(RoscaPallet::current_round(0), 3);
+        assert_eq!(RoscaPallet::eligible_claimant(0), Some(3));
+
+        // 1 was paid out in round one, so ejecting them leaves the remaining rounds as they were.
+        assert_ok!(RoscaPallet::propose(
+            RuntimeOrigin::signed(2),
+            0,
+            ProposalAction::EjectMember { participant: 1 },
+            VoteThreshold::Supermajority
+        ));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(3), 0, 0, true));
+        assert_ok!(RoscaPallet::vote(RuntimeOrigin::signed(0), 0, 0, true));
+
+        let expected_rounds: BoundedVec<_, ConstU32<150>> = bounded_vec![
+            RoundInfo {
+                round_number: 3,
+                payment_cutoff: 31,
+                expected_contributors: bounded_vec![2, 0],
+                recipient: 3,
+            },
+            RoundInfo {
+                round_number: 4,
+                payment_cutoff: 41,
+                expected_contributors: bounded_vec![3, 2],
+                recipient: 0,
+            }
+        ];
+        System::assert_has_event(RuntimeEvent::RoscaPallet(Event::ParticipantEjected {
+            rosca_id: 0,
+            participant: 1,
+            defaults: 0,
+            forfeited_deposit: 0,
+            forfeited_to: vec![],
+            slot_dropped: false,
+            final_payment_cutoff: 41,
+            rounds: expected_rounds,
+        }));
+        let round_numbers: Vec<u32> = RoscaPallet::round_summaries(0).iter().map(|round| round.round_number).collect();
+        assert_eq!(round_numbers, vec![3, 4]);
+
+        for contributor in [2, 0] {
+            assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(contributor), 0));
+        }
+        assert_eq!(RoscaPallet::current_round(0), 4);
+        assert_eq!(RoscaPallet::round_summaries(0)[0].round_number, 4);
+    });
+}
+
+#[test]
+fn manually_ending_stops_at_the_final_cutoff_moved_by_an_ejection() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        Timestamp::set_timestamp(1);
+
+        let participants: BoundedVec<u64, ConstU32<149>> = vec![2, 3].try_into().unwrap();
+        assert_ok!(RoscaPallet::create_rosca(
+            RuntimeOrigin::signed(1),
+            false,
+            participants,
+            3,
+            100,
+            crate::PaymentAssets::USDT,
+            10,
+            20,
+            Some(0),
+            bounded_vec![1]
+        ));
+        assert_ok!(RoscaPallet::set_max_defaults(RuntimeOrigin::signed(1), 0, Some(0)));
+        assert_ok!(RoscaPallet::join_rosca(RuntimeOrigin::signed(2), 0, None));
+        assert_ok!(RoscaPallet::join_rosca(RuntimeOrigin::signed(3), 0, None));
+        assert_ok!(RoscaPallet::add_to_security_deposit(RuntimeOrigin::signed(1), 0, 300));
+        assert_ok!(RoscaPallet::add_to_security_deposit(RuntimeOrigin::signed(2), 0, 300));
+        assert_ok!(RoscaPallet::start_rosca(RuntimeOrigin::signed(1), 0));
+
+        // Nobody pays. Participant 3 has no deposit and is ejected in round one, dropping the
+        // third round, so the deposits only cover rounds one and two.
+        Timestamp::set_timestamp(32);
+        assert_ok!(RoscaPallet::manually_end_rosca(RuntimeOrigin::signed(1), 0));
+
+        assert!(RoscaPallet::completed_roscas(0).is_some());
+        assert_eq!(RoscaPallet::final_pay_by_timestamp(0), Some(21));
+        assert_eq!(RoscaPallet::security_deposit(0, &1), Some(200));
+        assert_eq!(RoscaPallet::security_deposit(0, &2), Some(200));
+        assert_eq!(Assets::balance(1984, 1), 9800);
+        assert_eq!(Assets::balance(1984, 2), 9800);
+    });
+}
+
+#[test]
+fn insurance_premiums_fund_claims_for_short_deposits() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+        let config = InsuranceConfig { premium: Permill::from_percent(10), max_claim: 60 };
+
+        assert_noop!(
+            RoscaPallet::set_insurance_config(RuntimeOrigin::signed(creator), PaymentAssets::USDT, Some(config.clone())),
+            BadOrigin
+        );
+        assert_ok!(RoscaPallet::set_insurance_config(RuntimeOrigin::root(), PaymentAssets::USDT, Some(config)));
+
+        // Participant 2 pays the contribution plus a 10% premium.
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+        assert_eq!(Assets::balance(1984, 2), 9890);
+        assert_eq!(RoscaPallet::insurance_pool(PaymentAssets::USDT), 10);
+
+        // Participant 3 only has half a contribution as deposit and misses round one.
+        assert_ok!(RoscaPallet::add_to_security_deposit(RuntimeOrigin::signed(3), 0, 50));
+        Timestamp::set_timestamp(15);
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(3), 0));
+
+        System::assert_has_event(RuntimeEvent::RoscaPallet(Event::InsuranceClaimPaid {
+            rosca_id: 0,
+            defaulter: 3,
+            recipient: creator,
+            asset: PaymentAssets::USDT,
+            shortfall: 50,
+            amount: 9,
+        }));
+        // 100 from participant 2, 50 from the deposit and 9 from the pool, which keeps the
+        // asset's minimum balance.
+        assert_eq!(Assets::balance(1984, creator), 10159);
+        // The pool was drained by the claim and refilled by participant 3's premium.
+        assert_eq!(RoscaPallet::insurance_pool(PaymentAssets::USDT), 11);
+        assert_eq!(RoscaPallet::default_count(0, &3), 1);
+    });
+}
+
+#[test]
+fn premiums_below_the_minimum_balance_are_waived() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+        assert_ok!(Assets::force_asset_status(
+            RuntimeOrigin::root(),
+            parity_scale_codec::Compact(1984),
+            0,
+            0,
+            0,
+            0,
+            50,
+            true,
+            false
+        ));
+        let config = InsuranceConfig { premium: Permill::from_percent(10), max_claim: 60 };
+        assert_ok!(RoscaPallet::set_insurance_config(RuntimeOrigin::root(), PaymentAssets::USDT, Some(config)));
+
+        // A 10 premium can't open an insurance account that needs 50.
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+        assert_eq!(Assets::balance(1984, 2), 9900);
+        assert_eq!(RoscaPallet::insurance_pool(PaymentAssets::USDT), 0);
+    });
+}
+
+#[test]
+fn guarantor_covers_shortfall_and_is_repaid() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        Timestamp::set_timestamp(1);
+        let guarantor = 0u64;
+
+        assert_ok!(RoscaPallet::create_rosca(
+            RuntimeOrigin::signed(1),
+            false,
+            bounded_vec![2, 3],
+            3,
+            100,
+            PaymentAssets::USDT,
+            10,
+            20,
+            Some(0),
+            bounded_vec![1]
+        ));
+        assert_noop!(
+            RoscaPallet::set_guarantor(RuntimeOrigin::signed(2), 0, Some(guarantor)),
+            Error::<Test>::NotRoscaCreator
+        );
+        assert_ok!(RoscaPallet::set_guarantor(RuntimeOrigin::signed(1), 0, Some(guarantor)));
+        assert_noop!(
+            RoscaPallet::fund_guarantee(RuntimeOrigin::signed(2), 0, 150),
+            Error::<Test>::NotGuarantor
+        );
+        assert_ok!(RoscaPallet::fund_guarantee(RuntimeOrigin::signed(guarantor), 0, 150));
+        assert_ok!(RoscaPallet::join_rosca(RuntimeOrigin::signed(2), 0, None));
+        assert_ok!(RoscaPallet::join_rosca(RuntimeOrigin::signed(3), 0, None));
+        assert_ok!(RoscaPallet::start_rosca(RuntimeOrigin::signed(1), 0));
+
+        // Participant 3 misses round one with a deposit of only 30.
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+        assert_ok!(RoscaPallet::add_to_security_deposit(RuntimeOrigin::signed(3), 0, 30));
+        Timestamp::set_timestamp(15);
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(3), 0));
+
+        System::assert_has_event(RuntimeEvent::RoscaPallet(Event::GuaranteeDrawn {
+            rosca_id: 0,
+            guarantor,
+            defaulter: 3,
+            recipient: 1,
+            amount: 70,
+        }));
+        assert_eq!(Assets::balance(1984, 1), 10200);
+        assert_eq!(RoscaPallet::guarantee_balance(0), 80);
+        assert_eq!(RoscaPallet::guarantor_debt(0, &3), 70);
+
+        assert_noop!(
+            RoscaPallet::repay_guarantor(RuntimeOrigin::signed(2), 0, 20),
+            Error::<Test>::NoDebtToGuarantor
+        );
+        assert_ok!(RoscaPallet::repay_guarantor(RuntimeOrigin::signed(3), 0, 20));
+        assert_eq!(RoscaPallet::guarantor_debt(0, &3), 50);
+        assert_noop!(
+            RoscaPallet::claim_guarantee(RuntimeOrigin::signed(guarantor), 0),
+            Error::<Test>::RoscaNotCompleted
+        );
+
+        // Finish rounds two and three.
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(1), 0));
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(1), 0));
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+        assert!(RoscaPallet::completed_roscas(0).is_some());
+
+        // The undrawn guarantee goes back to the guarantor and the debt can still be repaid.
+        assert_ok!(RoscaPallet::claim_guarantee(RuntimeOrigin::signed(guarantor), 0));
+        assert_eq!(RoscaPallet::guarantee_balance(0), 0);
+        assert_ok!(RoscaPallet::repay_guarantor(RuntimeOrigin::signed(3), 0, 100));
+        assert_eq!(RoscaPallet::guarantor_debt(0, &3), 0);
+        assert_eq!(Assets::balance(1984, guarantor), 10000);
+    });
+}
+
+#[test]
+fn guarantee_can_be_claimed_before_the_rosca_starts_or_on_replacement() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        Timestamp::set_timestamp(1);
+        let guarantor = 0u64;
+
+        assert_ok!(RoscaPallet::create_rosca(
+            RuntimeOrigin::signed(1),
+            false,
+            bounded_vec![2, 3],
+            3,
+            100,
+            PaymentAssets::USDT,
+            10,
+            20,
+            Some(0),
+            bounded_vec![1]
+        ));
+        assert_ok!(RoscaPallet::set_guarantor(RuntimeOrigin::signed(1), 0, Some(guarantor)));
+        assert_ok!(RoscaPallet::fund_guarantee(RuntimeOrigin::signed(guarantor), 0, 150));
+
+        // A replaced guarantor gets their guarantee back.
+        assert_ok!(RoscaPallet::set_guarantor(RuntimeOrigin::signed(1), 0, Some(2)));
+        System::assert_has_event(RuntimeEvent::RoscaPallet(Event::GuaranteeClaimed {
+            rosca_id: 0,
+            guarantor,
+            amount: 150,
+        }));
+        assert_eq!(Assets::balance(1984, guarantor), 10000);
+        assert_eq!(RoscaPallet::guarantee_balance(0), 0);
+
+        // The new guarantor can withdraw while the Rosca is pending, also once it has expired.
+        assert_ok!(RoscaPallet::fund_guarantee(RuntimeOrigin::signed(2), 0, 150));
+        assert_noop!(RoscaPallet::claim_guarantee(RuntimeOrigin::signed(guarantor), 0), Error::<Test>::NotGuarantor);
+        Timestamp::set_timestamp(25);
+        assert_ok!(RoscaPallet::claim_guarantee(RuntimeOrigin::signed(2), 0));
+        assert_eq!(Assets::balance(1984, 2), 10000);
+        assert_eq!(RoscaPallet::guarantee_balance(0), 0);
+        assert_noop!(RoscaPallet::claim_guarantee(RuntimeOrigin::signed(2), 0), Error::<Test>::GuaranteeIsZero);
+    });
+}
+
+#[test]
+fn platform_and_organiser_fees_are_taken_from_contributions() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        Timestamp::set_timestamp(1);
+
+        assert_ok!(RoscaPallet::create_rosca(
+            RuntimeOrigin::signed(1),
+            false,
+            bounded_vec![2, 3],
+            3,
+            100,
+            PaymentAssets::USDT,
+            10,
+            20,
+            Some(0),
+            bounded_vec![1]
+        ));
+        assert_noop!(
+            RoscaPallet::set_organiser_fee(RuntimeOrigin::signed(1), 0, Some(Permill::from_percent(20))),
+            Error::<Test>::OrganiserFeeTooHigh
+        );
+        assert_noop!(
+            RoscaPallet::set_organiser_fee(RuntimeOrigin::signed(2), 0, Some(Permill::from_percent(5))),
+            Error::<Test>::NotRoscaCreator
+        );
+        assert_ok!(RoscaPallet::set_organiser_fee(RuntimeOrigin::signed(1), 0, Some(Permill::from_percent(5))));
+        assert_noop!(
+            RoscaPallet::set_platform_fee(RuntimeOrigin::signed(1), None, Some(Permill::from_percent(1))),
+            BadOrigin
+        );
+        assert_ok!(RoscaPallet::set_platform_fee(RuntimeOrigin::root(), None, Some(Permill::from_percent(1))));
+        assert_ok!(RoscaPallet::set_platform_fee(RuntimeOrigin::root(), Some(PaymentAssets::USDT), Some(Permill::from_percent(2))));
+        assert_eq!(RoscaPallet::platform_fee_for(&PaymentAssets::USDT), Permill::from_percent(2));
+        assert_eq!(RoscaPallet::platform_fee_for(&PaymentAssets::USDC), Permill::from_percent(1));
+
+        assert_ok!(RoscaPallet::join_rosca(RuntimeOrigin::signed(2), 0, None));
+        assert_ok!(RoscaPallet::join_rosca(RuntimeOrigin::signed(3), 0, None));
+        assert_ok!(RoscaPallet::start_rosca(RuntimeOrigin::signed(1), 0));
+
+        // Round one pays the creator, so participant 2 funds both fees.
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+        System::assert_last_event(RuntimeEvent::RoscaPallet(Event::ContributionMade {
+            rosca_id: 0,
+            contributor: 2,
+            recipient: 1,
+            amount: 100,
+            payer: 2,
+            platform_fee: 2,
+            organiser_fee: 5,
+        }));
+        assert_eq!(Assets::balance(1984, 2), 9900);
+        assert_eq!(Assets::balance(1984, 1), 10098);
+        assert_eq!(Assets::balance(1984, FeeDestinationAccount::get()), 2);
+
+        // Clearing the override falls back to the default fee.
+        assert_ok!(RoscaPallet::set_platform_fee(RuntimeOrigin::root(), Some(PaymentAssets::USDT), None));
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(3), 0));
+        assert_eq!(Assets::balance(1984, FeeDestinationAccount::get()), 3);
+        assert_eq!(Assets::balance(1984, 1), 10197);
+    });
+}
+
+#[test]
+fn combined_fees_are_capped_and_fees_below_the_minimum_balance_are_skipped() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        Timestamp::set_timestamp(1);
+
+        assert_ok!(RoscaPallet::create_rosca(
+            RuntimeOrigin::signed(1),
+            false,
+            bounded_vec![2, 3],
+            3,
+            100,
+            PaymentAssets::USDT,
+            10,
+            20,
+            Some(0),
+            bounded_vec![1]
+        ));
+        assert_noop!(
+            RoscaPallet::set_platform_fee(RuntimeOrigin::root(), None, Some(Permill::from_percent(13))),
+            Error::<Test>::FeeTooHigh
+        );
+        assert_ok!(RoscaPallet::set_platform_fee(RuntimeOrigin::root(), Some(PaymentAssets::USDT), Some(Permill::from_percent(2))));
+        assert_noop!(
+            RoscaPallet::set_organiser_fee(RuntimeOrigin::signed(1), 0, Some(Permill::from_percent(10))),
+            Error::<Test>::FeeTooHigh
+        );
+        assert_ok!(RoscaPallet::set_organiser_fee(RuntimeOrigin::signed(1), 0, Some(Permill::from_percent(7))));
+
+        assert_ok!(RoscaPallet::join_rosca(RuntimeOrigin::signed(2), 0, None));
+        assert_ok!(RoscaPallet::join_rosca(RuntimeOrigin::signed(3), 0, None));
+        assert_ok!(RoscaPallet::start_rosca(RuntimeOrigin::signed(1), 0));
+
+        // A platform fee of 2 can't open the fee destination's account, so the recipient keeps it.
+        assert_ok!(Assets::force_asset_status(
+            RuntimeOrigin::root(),
+            parity_scale_codec::Compact(1984),
+            0,
+            0,
+            0,
+            0,
+            5,
+            true,
+            false
+        ));
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+        System::assert_last_event(RuntimeEvent::RoscaPallet(Event::ContributionMade {
+            rosca_id: 0,
+            contributor: 2,
+            recipient: 1,
+            amount: 100,
+            payer: 2,
+            platform_fee: 0,
+            organiser_fee: 7,
+        }));
+        assert_eq!(Assets::balance(1984, FeeDestinationAccount::get()), 0);
+        assert_eq!(Assets::balance(1984, 1), 10100);
+
+        // Raising the platform fee afterwards trims the organiser fee to stay within the cap.
+        assert_ok!(RoscaPallet::set_platform_fee(RuntimeOrigin::root(), Some(PaymentAssets::USDT), Some(Permill::from_percent(12))));
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(3), 0));
+        System::assert_has_event(RuntimeEvent::RoscaPallet(Event::ContributionMade {
+            rosca_id: 0,
+            contributor: 3,
+            recipient: 1,
+            amount: 100,
+            payer: 3,
+            platform_fee: 12,
+            organiser_fee: 0,
+        }));
+        assert_eq!(Assets::balance(1984, FeeDestinationAccount::get()), 12);
+        assert_eq!(Assets::balance(1984, 1), 10188);
+    });
+}
+
+#[test]
+fn sponsorship_pays_member_fees_up_to_round_limit() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+        Balances::set_balance(&creator, 1000);
+
+        assert_noop!(
+            RoscaPallet::fund_sponsorship(RuntimeOrigin::signed(2), 0, 500),
+            Error::<Test>::NotRoscaCreator
+        );
+        // The existential deposit of 10 stays in the account, outside the budget.
+        assert_ok!(RoscaPallet::fund_sponsorship(RuntimeOrigin::signed(creator), 0, 500));
+        assert_eq!(RoscaPallet::sponsorship_balance(0), 490);
+
+        assert_eq!(RoscaPallet::sponsored_rosca(&crate::Call::contribute_to_rosca { rosca_id: 0 }), Some(0));
+        assert_eq!(RoscaPallet::sponsored_rosca(&crate::Call::manually_end_rosca { rosca_id: 0 }), None);
+        assert!(!RoscaPallet::can_sponsor(0, &4, 100));
+        assert!(!RoscaPallet::can_sponsor(0, &2, 491));
+
+        // The sponsorship keeps the unused part of the estimated fee.
+        assert_ok!(RoscaPallet::charge_sponsorship(0, &2, 100));
+        assert_eq!(RoscaPallet::sponsorship_balance(0), 390);
+        assert_ok!(RoscaPallet::settle_sponsorship(0, &2, 100, 60));
+        System::assert_last_event(RuntimeEvent::RoscaPallet(Event::FeeSponsored {
+            rosca_id: 0,
+            participant: 2,
+            fee: 60,
+        }));
+        assert_eq!(RoscaPallet::sponsorship_balance(0), 430);
+        assert_eq!(Balances::free_balance(FeeDestinationAccount::get()), 60);
+
+        // Participant 2 reaches the limit of two sponsored calls this round.
+        assert_ok!(RoscaPallet::charge_sponsorship(0, &2, 100));
+        assert_noop!(
+            RoscaPallet::charge_sponsorship(0, &2, 100),
+            Error::<Test>::SponsorshipUnavailable
+        );
+        assert!(RoscaPallet::can_sponsor(0, &3, 100));
+
+        // The limit resets once the round moves on.
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(3), 0));
+        assert!(RoscaPallet::can_sponsor(0, &2, 100));
+
+        assert_noop!(
+            RoscaPallet::withdraw_sponsorship(RuntimeOrigin::signed(creator), 0, 1000),
+            Error::<Test>::InsufficientSponsorship
+        );
+        assert_ok!(RoscaPallet::withdraw_sponsorship(RuntimeOrigin::signed(creator), 0, 300));
+        assert_eq!(RoscaPallet::sponsorship_balance(0), 30);
+        assert_eq!(Balances::free_balance(creator), 800);
+
+        // Withdrawing the whole budget still leaves the account alive.
+        assert_ok!(RoscaPallet::withdraw_sponsorship(RuntimeOrigin::signed(creator), 0, 30));
+        assert_eq!(Balances::free_balance(RoscaPallet::sponsorship_account_id(0)), 110);
+    });
+}
+
+#[test]
+fn failed_sponsorship_settlement_changes_nothing() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+        Balances::set_balance(&creator, 1000);
+        assert_ok!(RoscaPallet::fund_sponsorship(RuntimeOrigin::signed(creator), 0, 500));
+        assert_ok!(RoscaPallet::charge_sponsorship(0, &2, 100));
+
+        // A fee of 5 can't open the fee destination's account, which needs 10.
+        assert_noop!(
+            RoscaPallet::settle_sponsorship(0, &2, 100, 5),
+            Token(sp_runtime::TokenError::BelowMinimum)
+        );
+        assert_eq!(RoscaPallet::sponsorship_balance(0), 390);
+    });
+}
+
+#[test]
+fn payouts_go_to_remote_destination_or_fall_back_locally() {
+    new_test_ext().execute_with(|| {
+        let (creator, _) = setup_basic_rosca();
+        let asset_hub_account: VersionedLocation =
+            Location::new(1, [Parachain(1000), AccountId32 { network: None, id: [1u8; 32] }]).into();
+        let unroutable: VersionedLocation =
+            Location::new(1, [Parachain(2000), AccountId32 { network: None, id: [2u8; 32] }]).into();
+
+        assert_noop!(
+            RoscaPallet::set_payout_destination(RuntimeOrigin::signed(4), 0, Some(Box::new(asset_hub_account.clone()))),
+            Error::<Test>::NotAParticipant
+        );
+        assert_ok!(RoscaPallet::set_payout_destination(RuntimeOrigin::signed(creator), 0, Some(Box::new(asset_hub_account.clone()))));
+        assert_ok!(RoscaPallet::set_payout_destination(RuntimeOrigin::signed(2), 0, Some(Box::new(unroutable))));
+
+        // Round one pays the creator on Asset Hub.
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+        System::assert_has_event(RuntimeEvent::RoscaPallet(Event::RemotePayoutSent {
+            rosca_id: 0,
+            recipient: creator,
+            destination: asset_hub_account,
+            amount: 100,
+        }));
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(3), 0));
+        assert_eq!(Assets::balance(1984, AssetHubSovereign::get()), 200);
+        assert_eq!(Assets::balance(1984, creator), 10000);
+
+        // Participant 2's destination can't be reached, so round two pays them here.
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(creator), 0));
+        // The error message isn't encoded, so only the variant can be matched.
+        assert!(System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::RoscaPallet(Event::RemotePayoutFailed { rosca_id: 0, recipient: 2, amount: 100, .. })
+        )));
+        assert_eq!(Assets::balance(1984, 2), 10000);
+    });
+}
+
+// Runs the scheduler for every block up to `block`
+fn run_scheduler_to(block: u64) {
+    while System::block_number() < block {
+        System::set_block_number(System::block_number() + 1);
+        Scheduler::on_initialize(System::block_number());
+    }
+}
+
+#[test]
+fn autopay_contributes_through_an_approval() {
+    new_test_ext().execute_with(|| {
+        let (_creator, participants) = setup_basic_rosca();
+        let participant = participants[0];
+
+        assert_noop!(RoscaPallet::enable_autopay(RuntimeOrigin::signed(4), 0), Error::<Test>::NotAParticipant);
+        assert_ok!(RoscaPallet::enable_autopay(RuntimeOrigin::signed(participant), 0));
+        System::assert_last_event(Event::AutopayEnabled { rosca_id: 0, participant }.into());
+        assert_noop!(
+            RoscaPallet::enable_autopay(RuntimeOrigin::signed(participant), 0),
+            Error::<Test>::AutopayAlreadyEnabled
+        );
+        // Only the scheduler runs autopay.
+        assert_noop!(RoscaPallet::autopay_contribution(RuntimeOrigin::signed(participant), 0, participant), BadOrigin);
+
+        // Without an approval the attempt fails and says so.
+        run_scheduler_to(7);
+        assert!(System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::RoscaPallet(Event::AutopayFailed { rosca_id: 0, participant: 2, .. })
+        )));
+        assert_eq!(RoscaPallet::current_contributors(0, participant), None);
+
+        // The next attempt pulls the contribution through the approval.
+        assert_ok!(Assets::approve_transfer(
+            RuntimeOrigin::signed(participant),
+            parity_scale_codec::Compact(1984),
+            RoscaPallet::autopay_account_id(),
+            1_000
+        ));
+        let balance = Assets::balance(1984, participant);
+        run_scheduler_to(13);
+        assert!(RoscaPallet::current_contributors(0, participant).is_some());
+        assert_eq!(Assets::balance(1984, participant), balance - 100);
+
+        // Nothing more is taken once the round is paid.
+        run_scheduler_to(19);
+        assert_eq!(Assets::balance(1984, participant), balance - 100);
+
+        assert_ok!(RoscaPallet::disable_autopay(RuntimeOrigin::signed(participant), 0));
+        System::assert_last_event(Event::AutopayDisabled { rosca_id: 0, participant }.into());
+        assert_eq!(RoscaPallet::autopay(0, participant), None);
+        assert_noop!(RoscaPallet::disable_autopay(RuntimeOrigin::signed(participant), 0), Error::<Test>::AutopayNotEnabled);
+    });
+}
+
+#[test]
+fn autopay_returns_a_waived_premium() {
+    new_test_ext().execute_with(|| {
+        let (_creator, participants) = setup_basic_rosca();
+        let participant = participants[0];
+        assert_ok!(Assets::force_asset_status(
+            RuntimeOrigin::root(),
+            parity_scale_codec::Compact(1984),
+            0,
+            0,
+            0,
+            0,
+            50,
+            true,
+            false
+        ));
+        let config = InsuranceConfig { premium: Permill::from_percent(10), max_claim: 60 };
+        assert_ok!(RoscaPallet::set_insurance_config(RuntimeOrigin::root(), PaymentAssets::USDT, Some(config)));
+        assert_ok!(RoscaPallet::enable_autopay(RuntimeOrigin::signed(participant), 0));
+        assert_ok!(Assets::approve_transfer(
+            RuntimeOrigin::signed(participant),
+            parity_scale_codec::Compact(1984),
+            RoscaPallet::autopay_account_id(),
+            1_000
+        ));
+
+        // The 10 premium is pulled with the contribution, then waived and handed back.
+        run_scheduler_to(7);
+        assert!(RoscaPallet::current_contributors(0, participant).is_some());
+        assert_eq!(Assets::balance(1984, participant), 9900);
+        assert_eq!(RoscaPallet::insurance_pool(PaymentAssets::USDT), 0);
+        assert_eq!(Assets::balance(1984, RoscaPallet::autopay_account_id()), 0);
+    });
+}
+
+#[test]
+fn autopay_pulls_nothing_when_catching_up_completes_the_rosca() {
+    new_test_ext().execute_with(|| {
+        let (_creator, participants) = setup_basic_rosca();
+        let participant = participants[0];
+        assert_ok!(RoscaPallet::enable_autopay(RuntimeOrigin::signed(participant), 0));
+        assert_ok!(Assets::approve_transfer(
+            RuntimeOrigin::signed(participant),
+            parity_scale_codec::Compact(1984),
+            RoscaPallet::autopay_account_id(),
+            1_000
+        ));
+
+        // Every round's cutoff has passed by the first attempt.
+        Timestamp::set_timestamp(32);
+        run_scheduler_to(7);
+        assert!(RoscaPallet::completed_roscas(0).is_some());
+        assert_eq!(Assets::balance(1984, participant), 10000);
+        assert_eq!(Assets::balance(1984, RoscaPallet::autopay_account_id()), 0);
+    });
+}
+
+#[test]
+fn autopay_is_turned_off_when_the_next_attempt_cant_be_scheduled() {
+    new_test_ext().execute_with(|| {
+        let (_creator, participants) = setup_basic_rosca();
+        let participant = participants[0];
+        assert_ok!(RoscaPallet::enable_autopay(RuntimeOrigin::signed(participant), 0));
+
+        // The pending attempt holds the task name, so this one can't schedule the next.
+        assert_ok!(RoscaPallet::autopay_contribution(RuntimeOrigin::root(), 0, participant));
+        assert!(System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::RoscaPallet(Event::AutopayFailed { rosca_id: 0, participant: 2, .. })
+        )));
+        System::assert_last_event(Event::AutopayDisabled { rosca_id: 0, participant }.into());
+        assert_eq!(RoscaPallet::autopay(0, participant), None);
+    });
+}
+
+#[test]
+fn try_state_names_the_rosca_and_broken_invariant() {
+    new_test_ext().execute_with(|| {
+        setup_basic_rosca();
+        assert_eq!(RoscaPallet::do_try_state(), Ok(()));
+
+        CurrentContributionCount::<Test>::insert(0, 1);
+        assert_eq!(
+            RoscaPallet::do_try_state(),
+            Err(InvariantViolation {
+                rosca_id: Some(0),
+                invariant: "CurrentContributionCount matches CurrentContributors"
+            })
+        );
+        CurrentContributionCount::<Test>::insert(0, 0);
+
+        // A deposit the Rosca account doesn't hold.
+        RoscaSecurityDeposits::<Test>::insert(0, 2, 500);
+        assert_eq!(
+            RoscaPallet::do_try_state(),
+            Err(InvariantViolation {
+                rosca_id: Some(0),
+                invariant: "the Rosca account holds the security deposits and guarantee"
+            })
+        );
+        RoscaSecurityDeposits::<Test>::remove(0, 2);
+    });
+}
+
+#[test]
+fn invariant_violations_reports_every_broken_rosca() {
+    new_test_ext().execute_with(|| {
+        setup_basic_rosca();
+        assert_ok!(RoscaPallet::create_rosca(RuntimeOrigin::signed(2), false, bounded_vec![3], 2, 50, PaymentAssets::USDC, 10, 20, None, bounded_vec![2]));
+        assert_eq!(RoscaPallet::invariant_violations(), vec![]);
+
+        CurrentContributionCount::<Test>::insert(0, 1);
+        // Account 3 is invited to Rosca 1 but hasn't joined.
+        RoscaSecurityDeposits::<Test>::insert(1, 3, 50);
+        assert_eq!(
+            RoscaPallet::invariant_violations(),
+            vec![
+                InvariantViolation {
+                    rosca_id: Some(0),
+                    invariant: "CurrentContributionCount matches CurrentContributors"
+                },
+                InvariantViolation { rosca_id: Some(1), invariant: "security deposits belong to participants" },
+            ]
+        );
+        assert_eq!(RoscaPallet::do_try_state(), Err(RoscaPallet::invariant_violations().remove(0)));
+
+        CurrentContributionCount::<Test>::insert(0, 0);
+        RoscaSecurityDeposits::<Test>::remove(1, 3);
+    });
+}
+
+#[test]
+fn rosca_storage_reads_every_item_of_a_rosca() {
+    new_test_ext().execute_with(|| {
+        setup_basic_rosca();
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+        assert_ok!(RoscaPallet::enable_autopay(RuntimeOrigin::signed(3), 0));
+
+        let storage = RoscaPallet::rosca_storage(0);
+        assert_eq!(storage.status, Some(RoscaStatus::Active));
+        assert_eq!(storage.details.unwrap().contribution_amount, 100);
+        assert_eq!(storage.creator, Some(1));
+        assert_eq!(storage.participants.len(), 3);
+        assert_eq!(storage.participants_count, Some(3));
+        assert_eq!(storage.active_order.unwrap()[0], 1);
+        assert_eq!(storage.pending_order, None);
+        assert_eq!(storage.next_pay_by_timestamp, Some(11));
+        assert_eq!(storage.eligible_claimant, Some(1));
+        assert_eq!(storage.current_contributors, vec![2]);
+        assert_eq!(storage.current_contribution_count, 1);
+        assert_eq!(storage.autopay, vec![3]);
+
+        let missing = RoscaPallet::rosca_storage(1);
+        assert_eq!(missing.status, None);
+        assert!(missing.details.is_none() && missing.participants.is_empty());
+
+        assert_eq!(RoscaPallet::pallet_storage().next_rosca_id, 1);
+    });
+}
+
+#[test]
+fn migrates_v0_rosca_details() {
+    use crate::migrations::{v0, v1};
+    use crate::{ActiveRoscas, PendingRoscaDetails, RoscaDetails};
+    use frame_support::{storage::unhashed, traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion}};
+
+    new_test_ext().execute_with(|| {
+        // Rosca 0 is active and Rosca 1 pending, as a chain at version 0 would store them.
+        setup_basic_rosca();
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+        assert_ok!(RoscaPallet::create_rosca(
+            RuntimeOrigin::signed(1),
+            true,
+            bounded_vec![2],
+            2,
+            50,
+            PaymentAssets::USDC,
+            10,
+            30,
+            None,
+            bounded_vec![2]
+        ));
+        let active = RoscaPallet::active_roscas(0).unwrap();
+        let pending = RoscaPallet::rosca_details(1).unwrap();
+
+        let downgrade = |rosca: &RoscaDetails<Test>| v0::RoscaDetails::<Test> {
+            random_order: rosca.random_order,
+            number_of_participants: rosca.number_of_participants,
+            minimum_participant_threshold: rosca.minimum_participant_threshold,
+            contribution_amount: rosca.contribution_amount,
+            payment_asset: rosca.payment_asset.clone(),
+            contribution_frequency: rosca.contribution_frequency,
+            start_by_timestamp: rosca.start_by_timestamp,
+            name: rosca.name.clone(),
+        };
+        unhashed::put(&ActiveRoscas::<Test>::hashed_key_for(0), &downgrade(&active));
+        unhashed::put(&PendingRoscaDetails::<Test>::hashed_key_for(1), &downgrade(&pending));
+        crate::CurrentRound::<Test>::remove(0);
+        let _ = crate::PotLedgers::<Test>::clear_prefix(0, u32::MAX, None);
+        StorageVersion::new(0).put::<RoscaPallet>();
+
+        // The old layout doesn't decode as the new one.
+        assert_eq!(RoscaPallet::active_roscas(0), None);
+        assert_eq!(RoscaPallet::rosca_details(1), None);
+
+        #[cfg(feature = "try-runtime")]
+        let state = v1::UncheckedMigrateToV1::<Test>::pre_upgrade().unwrap();
+        v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+        #[cfg(feature = "try-runtime")]
+        v1::UncheckedMigrateToV1::<Test>::post_upgrade(state).unwrap();
+
+        assert_eq!(RoscaPallet::active_roscas(0), Some(active.clone()));
+        assert_eq!(RoscaPallet::rosca_details(1), Some(pending.clone()));
+        assert_eq!(RoscaPallet::current_round(0), 1);
+        assert_eq!(RoscaPallet::pot_ledger(0, 1), PotLedger { paid_in: 0, received: 100 });
+        assert_eq!(RoscaPallet::pot_ledger(0, 2), PotLedger { paid_in: 100, received: 0 });
+        assert_eq!(RoscaPallet::pot_ledger(0, 3), PotLedger::default());
+        assert_eq!(RoscaPallet::on_chain_storage_version(), 1);
+
+        // Running it again leaves the migrated details alone.
+        v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+        assert_eq!(RoscaPallet::active_roscas(0), Some(active));
+        assert_eq!(RoscaPallet::rosca_details(1), Some(pending));
+    });
+}
+
+#[test]
+fn genesis_config_sets_up_roscas() {
+    use crate::GenesisRosca;
+
+    let rosca = |creator, name: &[u8], invited: Vec<u64>, start| GenesisRosca {
+        creator,
+        name: name.to_vec(),
+        random_order: false,
+        joined: invited.clone(),
+        invited,
+        minimum_participant_threshold: 2,
+        contribution_amount: 100,
+        payment_asset: PaymentAssets::USDT,
+        contribution_frequency: 10,
+        starts_within: 1_000,
+        start,
+    };
+    let genesis = crate::GenesisConfig::<Test> {
+        allowed_assets: vec![PaymentAssets::USDT],
+        genesis_timestamp: 1_000,
+        roscas: vec![rosca(1, b"pending", vec![2], false), rosca(2, b"active", vec![1, 3], true)],
+    };
+
+    new_test_ext_with(genesis).execute_with(|| {
+        assert_eq!(RoscaPallet::next_rosca_id(), 2);
+
+        assert!(RoscaPallet::rosca_details(0).is_some());
+        assert_eq!(RoscaPallet::participants_count(0), Some(2));
+
+        assert!(RoscaPallet::active_roscas(1).is_some());
+        assert_eq!(RoscaPallet::eligible_claimant(1), Some(2));
+        // Cutoffs count from the genesis time, and the clock is left as it was.
+        assert_eq!(RoscaPallet::next_pay_by_timestamp(1), Some(1_010));
+        assert_eq!(Timestamp::get(), 0);
+
+        assert_noop!(
+            RoscaPallet::create_rosca(RuntimeOrigin::signed(1), false, bounded_vec![2], 2, 100, PaymentAssets::USDC, 10, 20, None, bounded_vec![1]),
+            Error::<Test>::AssetNotAllowed
+        );
+        assert_ok!(RoscaPallet::set_asset_allowed(RuntimeOrigin::root(), PaymentAssets::USDC, true));
+        assert_ok!(RoscaPallet::create_rosca(RuntimeOrigin::signed(1), false, bounded_vec![2], 2, 100, PaymentAssets::USDC, 10, 20, None, bounded_vec![1]));
+    });
+}
+
+#[test]
+#[should_panic(expected = "genesis_timestamp must be set to set up genesis Roscas")]
+fn genesis_roscas_need_a_genesis_timestamp() {
+    let genesis = crate::GenesisConfig::<Test> {
+        allowed_assets: vec![],
+        genesis_timestamp: 0,
+        roscas: vec![crate::GenesisRosca {
+            creator: 1,
+            name: b"untimed".to_vec(),
+            random_order: false,
+            invited: vec![2],
+            joined: vec![2],
+            minimum_participant_threshold: 2,
+            contribution_amount: 100,
+            payment_asset: PaymentAssets::USDT,
+            contribution_frequency: 10,
+            starts_within: 1_000,
+            start: false,
+        }],
+    };
+
+    new_test_ext_with(genesis);
+}
+
+#[test]
+fn summaries_describe_rosca_state() {
+    new_test_ext().execute_with(|| {
+        setup_basic_rosca();
+        assert_ok!(RoscaPallet::create_rosca(RuntimeOrigin::signed(2), false, bounded_vec![3], 2, 50, PaymentAssets::USDC, 10, 20, None, bounded_vec![2]));
+        assert_ok!(RoscaPallet::contribute_to_rosca(RuntimeOrigin::signed(2), 0));
+
+        let active = RoscaPallet::rosca_summary(0).unwrap();
+        assert_eq!(active.status, RoscaStatus::Active);
+        assert_eq!(active.creator, Some(1));
+        assert_eq!(active.participants_count, 3);
+        assert_eq!(active.next_payment_cutoff, Some(11));
+        assert_eq!(active.final_payment_cutoff, Some(31));
+        assert_eq!(active.eligible_claimant, Some(1));
+        assert_eq!(active.current_contribution_count, 1);
+
+        let pending = RoscaPallet::rosca_summary(1).unwrap();
+        assert_eq!(pending.status, RoscaStatus::Pending);
+        assert_eq!(pending.name, b"\x02".to_vec());
+        assert_eq!(pending.next_payment_cutoff, None);
+        assert_eq!(RoscaPallet::rosca_summary(2), None);
+
+        assert_eq!(RoscaPallet::rosca_summaries(None).len(), 2);
+        assert_eq!(RoscaPallet::rosca_summaries(Some(RoscaStatus::Pending)), vec![pending]);
+
+        let schedule = RoscaPallet::round_summaries(0);
+        assert_eq!(schedule.iter().map(|round| round.round_number).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(schedule.iter().map(|round| round.payment_cutoff).collect::<Vec<_>>(), vec![11, 21, 31]);
+        assert_eq!(schedule[0].recipient, 1);
+        assert!(RoscaPallet::round_summaries(1).is_empty());
+
+        let participations = RoscaPallet::participations_of(&2);
+        assert_eq!(participations.iter().map(|p| (p.rosca_id, p.status)).collect::<Vec<_>>(), vec![(0, RoscaStatus::Active), (1, RoscaStatus::Pending)]);
+        assert!(participations[0].contributed_this_round);
+        assert!(!participations[0].is_eligible_claimant);
+        assert!(RoscaPallet::participations_of(&1)[0].is_eligible_claimant);
+    });
+}
@@ -3,6 +3,11 @@ use crate::*;
 use frame_support::BoundedVec;
 use frame_support::pallet_prelude::*;
 use frame_system::pallet_prelude::*;
+use sp_runtime::Permill;
+use xcm::VersionedLocation;
+use serde::{Deserialize, Serialize};
+
+pub use rosca_primitives::{ParticipationSummary, PaymentAssets, RoscaId, RoscaStatus, RoscaSummary, RoundSummary};
 
 #[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, Debug)]
 #[scale_info(skip_type_params(T))]
@@ -14,11 +19,10 @@ pub struct RoscaDetails<T: Config> {
     pub payment_asset: PaymentAssets,
     pub contribution_frequency: <T as pallet_timestamp::Config>::Moment,
     pub start_by_timestamp: <T as pallet_timestamp::Config>::Moment,
-    pub name: BoundedVec<u8, <T as Config>::StringLimit>
+    pub name: BoundedVec<u8, <T as Config>::StringLimit>,
+    pub guarantor: Option<AccountIdOf<T>>
 }
 
-pub type RoscaId = u32;
-
 #[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, RuntimeDebugNoBound)]
 #[scale_info(skip_type_params(T))]
 pub struct RoundInfo<T: Config> {
@@ -28,22 +32,210 @@ pub struct RoundInfo<T: Config> {
     pub recipient: AccountIdOf<T>,
 }
 
+impl<T: Config> RoundInfo<T> {
+    /// The round as seen from outside the pallet.
+    pub fn into_summary(self) -> RoundSummary<AccountIdOf<T>, <T as pallet_timestamp::Config>::Moment> {
+        RoundSummary {
+            round_number: self.round_number,
+            payment_cutoff: self.payment_cutoff,
+            recipient: self.recipient,
+            expected_contributors: self.expected_contributors.into_inner(),
+        }
+    }
+}
+
 pub type RoscaRounds<T: Config> = BoundedVec<RoundInfo<T>, T::MaxParticipants>;
     
 
-#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, Debug)]
-pub enum PaymentAssets {
+/// A Rosca set up in the genesis config. It's created by `creator`, the `joined` accounts join it
+/// in turn and, if `start` is set, the creator starts it. It must start within `starts_within` of
+/// the genesis timestamp.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct GenesisRosca<AccountId> {
+    pub creator: AccountId,
+    pub name: Vec<u8>,
+    pub random_order: bool,
+    pub invited: Vec<AccountId>,
+    pub joined: Vec<AccountId>,
+    pub minimum_participant_threshold: u32,
+    pub contribution_amount: u32,
+    pub payment_asset: PaymentAssets,
+    pub contribution_frequency: u64,
+    pub starts_within: u64,
+    pub start: bool,
+}
+
+pub type ProposalId = u32;
+
+/// Share of the Rosca participants that must vote aye for a proposal to pass.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, TypeInfo, Debug)]
+pub enum VoteThreshold {
+    /// More than half of the participants
     #[codec(index = 0)]
-    USDT,
+    Majority,
+    /// At least two thirds of the participants
     #[codec(index = 1)]
-    USDC,
+    Supermajority,
+    /// Every participant
+    #[codec(index = 2)]
+    Unanimous,
 }
 
-impl PaymentAssets {
-    pub const fn id(&self) -> u32 {
+impl VoteThreshold {
+    pub fn is_met(&self, ayes: u32, electorate: u32) -> bool {
+        let ayes = ayes as u64;
+        let electorate = electorate as u64;
         match self {
-            PaymentAssets::USDT => 1984,
-            PaymentAssets::USDC => 1337,
+            VoteThreshold::Majority => ayes * 2 > electorate,
+            VoteThreshold::Supermajority => ayes * 3 >= electorate * 2,
+            VoteThreshold::Unanimous => ayes >= electorate,
         }
     }
 }
+
+/// The whitelisted admin actions a Rosca can take by member vote.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, RuntimeDebugNoBound)]
+#[scale_info(skip_type_params(T))]
+pub enum ProposalAction<T: Config> {
+    /// Push back the current and all remaining payment cutoffs
+    #[codec(index = 0)]
+    ExtendDeadline { extension: <T as pallet_timestamp::Config>::Moment },
+    /// Remove one recorded default from a participant
+    #[codec(index = 1)]
+    ForgiveDefault { participant: AccountIdOf<T> },
+    /// End the Rosca before its final round
+    #[codec(index = 2)]
+    EndEarly,
+    /// Nominate a substitute for a participant who can't do so themselves
+    #[codec(index = 3)]
+    SubstituteMember { departing: AccountIdOf<T>, substitute: AccountIdOf<T> },
+    /// Freeze contributions and default processing
+    #[codec(index = 4)]
+    Pause,
+    /// Unfreeze the Rosca, shifting all remaining cutoffs by the paused duration
+    #[codec(index = 5)]
+    Resume,
+    /// Remove a participant, forfeiting their deposit and dropping any future payout slot
+    #[codec(index = 6)]
+    EjectMember { participant: AccountIdOf<T> },
+}
+
+impl<T: Config> ProposalAction<T> {
+    /// The lowest threshold a proposal for this action may be created with.
+    pub fn minimum_threshold(&self) -> VoteThreshold {
+        match self {
+            ProposalAction::ExtendDeadline { .. } => VoteThreshold::Majority,
+            ProposalAction::ForgiveDefault { .. } => VoteThreshold::Majority,
+            ProposalAction::EndEarly => VoteThreshold::Supermajority,
+            ProposalAction::SubstituteMember { .. } => VoteThreshold::Majority,
+            ProposalAction::Pause => VoteThreshold::Majority,
+            ProposalAction::Resume => VoteThreshold::Majority,
+            ProposalAction::EjectMember { .. } => VoteThreshold::Supermajority,
+        }
+    }
+
+    /// Whether the Rosca creator must be among the ayes for the action to pass. Roscas with no
+    /// recorded creator need only the threshold.
+    pub fn requires_creator_approval(&self) -> bool {
+        matches!(self, ProposalAction::Pause)
+    }
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, RuntimeDebugNoBound)]
+#[scale_info(skip_type_params(T))]
+pub struct Proposal<T: Config> {
+    pub proposer: AccountIdOf<T>,
+    pub action: ProposalAction<T>,
+    pub threshold: VoteThreshold,
+    pub ayes: u32,
+    pub nays: u32,
+    pub voting_ends: <T as pallet_timestamp::Config>::Moment,
+}
+
+/// Governance-set parameters of the insurance pool of a payment asset.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, Debug)]
+pub struct InsuranceConfig {
+    /// Share of each contribution charged to the payer on top of it and paid into the pool
+    pub premium: Permill,
+    /// Maximum paid out of the pool for a single defaulted contribution
+    pub max_claim: Balance,
+}
+
+/// What a participant has paid into the pots of a Rosca and received from them. Money from the
+/// insurance pool isn't the members', so it isn't counted.
+#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug)]
+pub struct PotLedger {
+    /// Contributions, security deposit deducted for missed ones and guarantee drawn on the
+    /// participant's behalf, plus any deposit forfeited to other members
+    pub paid_in: Balance,
+    /// Everything the above paid to the participant
+    pub received: Balance,
+}
+
+/// Every storage item the pallet keeps for one Rosca, as read by `Pallet::rosca_storage`.
+#[derive(Debug)]
+pub struct RoscaStorage<T: Config> {
+    pub rosca_id: RoscaId,
+    pub status: Option<RoscaStatus>,
+    /// Details from whichever of the pending, active or completed maps holds the Rosca
+    pub details: Option<RoscaDetails<T>>,
+    pub creator: Option<AccountIdOf<T>>,
+    pub account: Option<AccountIdOf<T>>,
+    pub invited: Vec<AccountIdOf<T>>,
+    pub participants: Vec<(AccountIdOf<T>, u32)>,
+    pub participants_count: Option<u32>,
+    pub security_deposits: Vec<(AccountIdOf<T>, u32)>,
+    pub pending_order: Option<Vec<Option<AccountIdOf<T>>>>,
+    pub active_order: Option<Vec<AccountIdOf<T>>>,
+    pub next_pay_by_timestamp: Option<<T as pallet_timestamp::Config>::Moment>,
+    pub final_pay_by_timestamp: Option<<T as pallet_timestamp::Config>::Moment>,
+    pub eligible_claimant: Option<AccountIdOf<T>>,
+    pub current_contributors: Vec<AccountIdOf<T>>,
+    pub current_contribution_count: u32,
+    pub current_round: u32,
+    pub pot_ledgers: Vec<(AccountIdOf<T>, PotLedger)>,
+    pub default_counts: Vec<(AccountIdOf<T>, u32)>,
+    /// Proposer and counterparty of each swap proposal
+    pub pending_swaps: Vec<(AccountIdOf<T>, AccountIdOf<T>)>,
+    /// Departing participant and nominated substitute
+    pub pending_substitutions: Vec<(AccountIdOf<T>, AccountIdOf<T>)>,
+    pub max_defaults: Option<u32>,
+    pub paused_at: Option<<T as pallet_timestamp::Config>::Moment>,
+    pub guarantee_balance: u32,
+    pub guarantor_debts: Vec<(AccountIdOf<T>, u32)>,
+    pub organiser_fee: Option<Permill>,
+    pub sponsorship_balance: Balance,
+    /// Sponsored call window start and calls made, per account
+    pub sponsored_calls: Vec<(AccountIdOf<T>, (Option<<T as pallet_timestamp::Config>::Moment>, u32))>,
+    pub payout_destinations: Vec<(AccountIdOf<T>, VersionedLocation)>,
+    pub autopay: Vec<AccountIdOf<T>>,
+    pub next_proposal_id: ProposalId,
+    pub proposals: Vec<(ProposalId, Proposal<T>)>,
+    pub proposal_votes: Vec<(ProposalId, AccountIdOf<T>, bool)>,
+}
+
+/// The storage items the pallet keeps across Roscas, as read by `Pallet::pallet_storage`.
+#[derive(Debug)]
+pub struct PalletStorage {
+    pub next_rosca_id: RoscaId,
+    pub platform_fee: Permill,
+    pub asset_platform_fees: Vec<(PaymentAssets, Permill)>,
+    pub disallowed_assets: Vec<PaymentAssets>,
+    pub insurance_configs: Vec<(PaymentAssets, InsuranceConfig)>,
+    pub insurance_pools: Vec<(PaymentAssets, Balance)>,
+}
+
+/// Sends a payout to a participant's account on another chain.
+pub trait PayoutSender<AccountId> {
+    /// Sends `amount` of `asset`, held by `from`, to `destination`. An error means nothing was
+    /// sent and the payout is made on this chain instead.
+    fn send_payout(from: &AccountId, asset: u32, amount: Balance, destination: VersionedLocation) -> DispatchResult;
+}
+
+/// Cross-chain payouts are unsupported, so every payout is made on this chain.
+impl<AccountId> PayoutSender<AccountId> for () {
+    fn send_payout(_: &AccountId, _: u32, _: Balance, _: VersionedLocation) -> DispatchResult {
+        Err(DispatchError::Other("Cross-chain payouts are unsupported"))
+    }
+}
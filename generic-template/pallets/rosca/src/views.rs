@@ -0,0 +1,146 @@
+//! Read-only views of the pallet's state, built from the `rosca-primitives` summary types. They
+//! back the runtime's `RoscaApi`, so clients can read a Rosca without knowing how its state is
+//! spread over the storage maps. `rosca_storage` and `pallet_storage` instead read every storage
+//! item as stored, for inspecting a chain's state from outside the runtime.
+
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+	/// The lifecycle stage of `rosca_id`, `None` if there's no such Rosca.
+	pub fn rosca_status(rosca_id: RoscaId) -> Option<RoscaStatus> {
+		if PendingRoscaDetails::<T>::contains_key(rosca_id) {
+			Some(RoscaStatus::Pending)
+		} else if ActiveRoscas::<T>::contains_key(rosca_id) {
+			Some(RoscaStatus::Active)
+		} else if CompletedRoscas::<T>::contains_key(rosca_id) {
+			Some(RoscaStatus::Completed)
+		} else {
+			None
+		}
+	}
+
+	/// A summary of `rosca_id`. Roscas completed before their details were kept have none.
+	pub fn rosca_summary(rosca_id: RoscaId) -> Option<RoscaSummary<AccountIdOf<T>, T::Moment>> {
+		let status = Self::rosca_status(rosca_id)?;
+		let rosca = match status {
+			RoscaStatus::Pending => Self::rosca_details(rosca_id),
+			RoscaStatus::Active => Self::active_roscas(rosca_id),
+			RoscaStatus::Completed => Self::completed_rosca_details(rosca_id),
+		}?;
+		let active = status == RoscaStatus::Active;
+
+		Some(RoscaSummary {
+			rosca_id,
+			status,
+			name: rosca.name.into_inner(),
+			creator: Self::rosca_creator(rosca_id),
+			random_order: rosca.random_order,
+			number_of_participants: rosca.number_of_participants,
+			minimum_participant_threshold: rosca.minimum_participant_threshold,
+			participants_count: Self::participants_count(rosca_id).unwrap_or(0),
+			contribution_amount: rosca.contribution_amount,
+			payment_asset: rosca.payment_asset,
+			contribution_frequency: rosca.contribution_frequency,
+			start_by_timestamp: rosca.start_by_timestamp,
+			guarantor: rosca.guarantor,
+			next_payment_cutoff: Self::next_pay_by_timestamp(rosca_id).filter(|_| active),
+			final_payment_cutoff: Self::final_pay_by_timestamp(rosca_id).filter(|_| active),
+			eligible_claimant: Self::eligible_claimant(rosca_id).filter(|_| active),
+			current_contribution_count: if active { Self::current_contribution_count(rosca_id) } else { 0 },
+			paused_at: Self::paused_at(rosca_id),
+		})
+	}
+
+	/// Summaries of every Rosca, or only those with `status` if given.
+	pub fn rosca_summaries(status: Option<RoscaStatus>) -> Vec<RoscaSummary<AccountIdOf<T>, T::Moment>> {
+		(0..Self::next_rosca_id())
+			.filter_map(Self::rosca_summary)
+			.filter(|summary| status.map_or(true, |status| summary.status == status))
+			.collect()
+	}
+
+	/// The current round of an active Rosca followed by every round still to be paid out.
+	pub fn round_summaries(rosca_id: RoscaId) -> Vec<RoundSummary<AccountIdOf<T>, T::Moment>> {
+		Self::remaining_schedule(rosca_id)
+			.map(|rounds| rounds.into_iter().map(RoundInfo::into_summary).collect())
+			.unwrap_or_default()
+	}
+
+	/// Where `account` stands in each Rosca it has joined.
+	pub fn participations_of(account: &AccountIdOf<T>) -> Vec<ParticipationSummary> {
+		(0..Self::next_rosca_id())
+			.filter_map(|rosca_id| {
+				let position = Self::participants(rosca_id, account)?;
+				let status = Self::rosca_status(rosca_id)?;
+				let active = status == RoscaStatus::Active;
+				Some(ParticipationSummary {
+					rosca_id,
+					status,
+					position,
+					security_deposit: Self::security_deposit(rosca_id, account).unwrap_or(0),
+					defaults: Self::default_count(rosca_id, account),
+					contributed_this_round: active && Self::current_contributors(rosca_id, account).is_some(),
+					is_eligible_claimant: active && Self::eligible_claimant(rosca_id).as_ref() == Some(account),
+					autopay: Self::autopay(rosca_id, account).is_some(),
+				})
+			})
+			.collect()
+	}
+
+	/// Every storage item kept for `rosca_id`, whether or not its state is consistent.
+	pub fn rosca_storage(rosca_id: RoscaId) -> RoscaStorage<T> {
+		let details = Self::rosca_details(rosca_id)
+			.or_else(|| Self::active_roscas(rosca_id))
+			.or_else(|| Self::completed_rosca_details(rosca_id));
+
+		RoscaStorage {
+			rosca_id,
+			status: Self::rosca_status(rosca_id),
+			details,
+			creator: Self::rosca_creator(rosca_id),
+			account: Self::rosca_account(rosca_id),
+			invited: RoscaInvitedPreverifiedParticipants::<T>::iter_key_prefix(rosca_id).collect(),
+			participants: RoscaParticipants::<T>::iter_prefix(rosca_id).collect(),
+			participants_count: Self::participants_count(rosca_id),
+			security_deposits: RoscaSecurityDeposits::<T>::iter_prefix(rosca_id).collect(),
+			pending_order: Self::pending_rosca_participants_order(rosca_id).map(BoundedVec::into_inner),
+			active_order: Self::active_rosca_participants_order(rosca_id).map(BoundedVec::into_inner),
+			next_pay_by_timestamp: Self::next_pay_by_timestamp(rosca_id),
+			final_pay_by_timestamp: Self::final_pay_by_timestamp(rosca_id),
+			eligible_claimant: Self::eligible_claimant(rosca_id),
+			current_contributors: CurrentContributors::<T>::iter_key_prefix(rosca_id).collect(),
+			current_contribution_count: Self::current_contribution_count(rosca_id),
+			current_round: Self::current_round(rosca_id),
+			pot_ledgers: PotLedgers::<T>::iter_prefix(rosca_id).collect(),
+			default_counts: DefaultCount::<T>::iter_prefix(rosca_id).collect(),
+			pending_swaps: PendingSwaps::<T>::iter_prefix(rosca_id).collect(),
+			pending_substitutions: PendingSubstitutions::<T>::iter_prefix(rosca_id).collect(),
+			max_defaults: Self::max_defaults(rosca_id),
+			paused_at: Self::paused_at(rosca_id),
+			guarantee_balance: Self::guarantee_balance(rosca_id),
+			guarantor_debts: GuarantorDebts::<T>::iter_prefix(rosca_id).collect(),
+			organiser_fee: Self::organiser_fee(rosca_id),
+			sponsorship_balance: Self::sponsorship_balance(rosca_id),
+			sponsored_calls: SponsoredCalls::<T>::iter_prefix(rosca_id).collect(),
+			payout_destinations: PayoutDestinations::<T>::iter_prefix(rosca_id).collect(),
+			autopay: Autopay::<T>::iter_key_prefix(rosca_id).collect(),
+			next_proposal_id: Self::next_proposal_id(rosca_id),
+			proposals: RoscaProposals::<T>::iter_prefix(rosca_id).collect(),
+			proposal_votes: ProposalVotes::<T>::iter_prefix((rosca_id,))
+				.map(|((proposal_id, voter), aye)| (proposal_id, voter, aye))
+				.collect(),
+		}
+	}
+
+	/// Every storage item kept across Roscas.
+	pub fn pallet_storage() -> PalletStorage {
+		PalletStorage {
+			next_rosca_id: Self::next_rosca_id(),
+			platform_fee: Self::platform_fee(),
+			asset_platform_fees: AssetPlatformFees::<T>::iter().collect(),
+			disallowed_assets: DisallowedAssets::<T>::iter_keys().collect(),
+			insurance_configs: InsuranceConfigs::<T>::iter().collect(),
+			insurance_pools: InsurancePools::<T>::iter().collect(),
+		}
+	}
+}
@@ -0,0 +1,130 @@
+//! Types and runtime API shared by `pallet-rosca`, the runtime and ROSCA clients.
+//!
+//! The pallet's own storage types are generic over its `Config`, which clients don't have. The
+//! views here are generic only over the account and moment types, so they can be decoded from a
+//! runtime API call or serialized to JSON without pulling in the runtime.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::{Codec, Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_std::vec::Vec;
+
+pub type RoscaId = u32;
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug, Serialize, Deserialize)]
+pub enum PaymentAssets {
+    #[codec(index = 0)]
+    USDT,
+    #[codec(index = 1)]
+    USDC,
+}
+
+impl PaymentAssets {
+    /// Every payment asset.
+    pub const ALL: [PaymentAssets; 2] = [PaymentAssets::USDT, PaymentAssets::USDC];
+
+    pub const fn id(&self) -> u32 {
+        match self {
+            PaymentAssets::USDT => 1984,
+            PaymentAssets::USDC => 1337,
+        }
+    }
+}
+
+/// Where a Rosca is in its lifecycle.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, TypeInfo, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RoscaStatus {
+    /// Created and waiting for participants to join and the creator to start it
+    #[codec(index = 0)]
+    Pending,
+    /// Started and paying out rounds
+    #[codec(index = 1)]
+    Active,
+    /// Every round has been paid out, or the participants ended it early
+    #[codec(index = 2)]
+    Completed,
+}
+
+/// A Rosca as seen from outside the pallet.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoscaSummary<AccountId, Moment> {
+    pub rosca_id: RoscaId,
+    pub status: RoscaStatus,
+    pub name: Vec<u8>,
+    pub creator: Option<AccountId>,
+    pub random_order: bool,
+    /// Participants the Rosca was created for, including the creator
+    pub number_of_participants: u32,
+    pub minimum_participant_threshold: u32,
+    /// Participants that have joined and not left
+    pub participants_count: u32,
+    pub contribution_amount: u32,
+    pub payment_asset: PaymentAssets,
+    pub contribution_frequency: Moment,
+    pub start_by_timestamp: Moment,
+    pub guarantor: Option<AccountId>,
+    /// Cutoff of the current round, while the Rosca is active
+    pub next_payment_cutoff: Option<Moment>,
+    /// Cutoff of the last round, while the Rosca is active
+    pub final_payment_cutoff: Option<Moment>,
+    /// Recipient of the current round, while the Rosca is active
+    pub eligible_claimant: Option<AccountId>,
+    /// Participants that have paid into the current round
+    pub current_contribution_count: u32,
+    /// When the Rosca was paused, if it is
+    pub paused_at: Option<Moment>,
+}
+
+/// One payout round of an active Rosca.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoundSummary<AccountId, Moment> {
+    pub round_number: u32,
+    pub payment_cutoff: Moment,
+    pub recipient: AccountId,
+    pub expected_contributors: Vec<AccountId>,
+}
+
+/// An account's place in a Rosca it has joined.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParticipationSummary {
+    pub rosca_id: RoscaId,
+    pub status: RoscaStatus,
+    /// Position the account asked for when joining
+    pub position: u32,
+    /// Security deposit held for the account
+    pub security_deposit: u32,
+    pub defaults: u32,
+    /// Whether the account has paid into the current round
+    pub contributed_this_round: bool,
+    /// Whether the account receives the current round's pot
+    pub is_eligible_claimant: bool,
+    pub autopay: bool,
+}
+
+sp_api::decl_runtime_apis! {
+    /// Read access to the Roscas of `pallet-rosca`.
+    pub trait RoscaApi<AccountId, Moment>
+    where
+        AccountId: Codec,
+        Moment: Codec,
+    {
+        /// The Rosca `rosca_id`, if it exists.
+        fn rosca(rosca_id: RoscaId) -> Option<RoscaSummary<AccountId, Moment>>;
+
+        /// Every Rosca, optionally only those with `status`.
+        fn roscas(status: Option<RoscaStatus>) -> Vec<RoscaSummary<AccountId, Moment>>;
+
+        /// The current round of an active Rosca followed by every round still to be paid out.
+        /// Empty if the Rosca isn't active.
+        fn schedule(rosca_id: RoscaId) -> Vec<RoundSummary<AccountId, Moment>>;
+
+        /// The Roscas `account` has joined.
+        fn participations(account: AccountId) -> Vec<ParticipationSummary>;
+    }
+}
@@ -0,0 +1,258 @@
+//! Text and JSON rendering of ROSCA state, and parsing of durations.
+
+use rosca_client::{AccountId, Moment, ParticipationSummary, RoscaSummary, RoundSummary};
+use serde_json::Value;
+
+use crate::ParticipantState;
+
+type Summary = RoscaSummary<AccountId, Moment>;
+
+const SECOND: u64 = 1000;
+const MINUTE: u64 = 60 * SECOND;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+
+/// Parses a duration in milliseconds from a number followed by `ms`, `s`, `m`, `h` or `d`. A
+/// bare number is taken as milliseconds.
+pub fn parse_duration(input: &str) -> Result<u64, String> {
+    let split = input.find(|c: char| !c.is_ascii_digit()).unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+    let number: u64 = number.parse().map_err(|_| format!("`{input}` doesn't start with a number"))?;
+    let unit = match unit {
+        "" | "ms" => 1,
+        "s" => SECOND,
+        "m" => MINUTE,
+        "h" => HOUR,
+        "d" => DAY,
+        _ => return Err(format!("unknown unit `{unit}`, expected ms, s, m, h or d")),
+    };
+    number.checked_mul(unit).ok_or_else(|| format!("`{input}` is too long"))
+}
+
+/// Renders a duration in milliseconds in its largest whole units, e.g. `2d 3h`.
+fn duration(millis: u64) -> String {
+    let units = [(DAY, "d"), (HOUR, "h"), (MINUTE, "m"), (SECOND, "s")];
+    let parts: Vec<String> = units
+        .iter()
+        .scan(millis, |left, &(size, unit)| {
+            let count = *left / size;
+            *left %= size;
+            Some((count, unit))
+        })
+        .filter(|&(count, _)| count > 0)
+        .take(2)
+        .map(|(count, unit)| format!("{count}{unit}"))
+        .collect();
+    if parts.is_empty() {
+        format!("{millis}ms")
+    } else {
+        parts.join(" ")
+    }
+}
+
+/// When `moment` is relative to `now`.
+fn relative(moment: Moment, now: Moment) -> String {
+    if moment >= now {
+        format!("in {}", duration(moment - now))
+    } else {
+        format!("{} ago", duration(now - moment))
+    }
+}
+
+fn name(rosca: &Summary) -> String {
+    String::from_utf8_lossy(&rosca.name).into_owned()
+}
+
+/// A summary as JSON, with its name as a string rather than bytes.
+pub fn rosca_json(rosca: &Summary) -> Value {
+    let mut value = serde_json::to_value(rosca).expect("summaries serialize");
+    value["name"] = Value::String(name(rosca));
+    value
+}
+
+pub fn print_rosca_list(roscas: &[Summary]) {
+    if roscas.is_empty() {
+        println!("No ROSCAs.");
+        return;
+    }
+    println!(
+        "{:>6}  {:<10} {:>12}  {:<5} {:>7}  NAME",
+        "ID", "STATUS", "CONTRIBUTION", "ASSET", "JOINED"
+    );
+    for rosca in roscas {
+        println!(
+            "{:>6}  {:<10} {:>12}  {:<5} {:>7}  {}",
+            rosca.rosca_id,
+            format!("{:?}", rosca.status),
+            rosca.contribution_amount,
+            format!("{:?}", rosca.payment_asset),
+            format!("{}/{}", rosca.participants_count, rosca.number_of_participants),
+            name(rosca),
+        );
+    }
+}
+
+pub fn print_rosca(
+    rosca: &Summary,
+    schedule: &[RoundSummary<AccountId, Moment>],
+    participants: &[ParticipantState],
+    now: Moment,
+) {
+    println!("ROSCA {} \"{}\" ({:?})", rosca.rosca_id, name(rosca), rosca.status);
+    if let Some(creator) = &rosca.creator {
+        println!("  Creator:        {creator}");
+    }
+    println!("  Contribution:   {} {:?}", rosca.contribution_amount, rosca.payment_asset);
+    println!("  Frequency:      {}", duration(rosca.contribution_frequency));
+    println!(
+        "  Participants:   {} joined of {}, {} needed to start",
+        rosca.participants_count, rosca.number_of_participants, rosca.minimum_participant_threshold
+    );
+    println!("  Payout order:   {}", if rosca.random_order { "random" } else { "by position" });
+    if let Some(guarantor) = &rosca.guarantor {
+        println!("  Guarantor:      {guarantor}");
+    }
+    if let Some(paused_at) = rosca.paused_at {
+        println!("  Paused:         {}", relative(paused_at, now));
+    }
+    if rosca.next_payment_cutoff.is_none() {
+        println!("  Start by:       {}", relative(rosca.start_by_timestamp, now));
+    }
+    if let Some(final_cutoff) = rosca.final_payment_cutoff {
+        println!("  Final cutoff:   {}", relative(final_cutoff, now));
+    }
+
+    if !schedule.is_empty() {
+        println!();
+        println!("Upcoming rounds:");
+        for round in schedule {
+            let paid = if round.round_number == schedule[0].round_number {
+                format!(
+                    ", {}/{} paid",
+                    rosca.current_contribution_count,
+                    round.expected_contributors.len()
+                )
+            } else {
+                String::new()
+            };
+            println!(
+                "  Round {:>3}  cutoff {:<16} to {}{paid}",
+                round.round_number,
+                relative(round.payment_cutoff, now),
+                round.recipient,
+            );
+        }
+    }
+
+    if !participants.is_empty() {
+        println!();
+        println!("Participants:");
+        for participant in participants {
+            let mut flags = Vec::new();
+            if rosca.eligible_claimant.as_ref() == Some(&participant.account) {
+                flags.push("receiving this round");
+            }
+            if participant.contributed_this_round {
+                flags.push("paid");
+            }
+            if participant.autopay {
+                flags.push("autopay");
+            }
+            println!(
+                "  {:>3}  {}  deposit {}, {} defaults{}",
+                participant.position,
+                participant.account,
+                participant.security_deposit,
+                participant.defaults,
+                if flags.is_empty() { String::new() } else { format!(", {}", flags.join(", ")) },
+            );
+        }
+    }
+}
+
+pub fn print_participations(participations: &[ParticipationSummary]) {
+    if participations.is_empty() {
+        println!("No participations.");
+        return;
+    }
+    println!(
+        "{:>6}  {:<10} {:>8} {:>8} {:>8}  FLAGS",
+        "ROSCA", "STATUS", "POSITION", "DEPOSIT", "DEFAULTS"
+    );
+    for participation in participations {
+        let mut flags = Vec::new();
+        if participation.is_eligible_claimant {
+            flags.push("receiving this round");
+        }
+        if participation.contributed_this_round {
+            flags.push("paid");
+        }
+        if participation.autopay {
+            flags.push("autopay");
+        }
+        println!(
+            "{:>6}  {:<10} {:>8} {:>8} {:>8}  {}",
+            participation.rosca_id,
+            format!("{:?}", participation.status),
+            participation.position,
+            participation.security_deposit,
+            participation.defaults,
+            flags.join(", "),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rosca_client::{Amount, RoscaEvent};
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("1500"), Ok(1500));
+        assert_eq!(parse_duration("250ms"), Ok(250));
+        assert_eq!(parse_duration("90s"), Ok(90 * SECOND));
+        assert_eq!(parse_duration("12h"), Ok(12 * HOUR));
+        assert_eq!(parse_duration("30d"), Ok(30 * DAY));
+        assert!(parse_duration("d").is_err());
+        assert!(parse_duration("3w").is_err());
+    }
+
+    #[test]
+    fn renders_durations() {
+        assert_eq!(duration(500), "500ms");
+        assert_eq!(duration(DAY + 3 * HOUR + 5 * MINUTE), "1d 3h");
+        assert_eq!(relative(10 * MINUTE, 4 * MINUTE), "in 6m");
+        assert_eq!(relative(4 * MINUTE, 10 * MINUTE), "6m ago");
+    }
+
+    #[test]
+    fn events_are_json_objects_with_named_fields() {
+        let alice = AccountId::new([1u8; 32]);
+        let bob = AccountId::new([2u8; 32]);
+        let event = RoscaEvent::ContributionMade {
+            rosca_id: 3,
+            contributor: alice.clone(),
+            recipient: bob.clone(),
+            amount: Amount(100),
+            payer: alice.clone(),
+            platform_fee: Amount(1),
+            organiser_fee: Amount(0),
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            json!({
+                "event": "ContributionMade",
+                "roscaId": 3,
+                "contributor": alice.to_string(),
+                "recipient": bob.to_string(),
+                "amount": "100",
+                "payer": alice.to_string(),
+                "platformFee": "1",
+                "organiserFee": "0",
+            })
+        );
+    }
+}
@@ -0,0 +1,349 @@
+//! `rosca`, a command-line tool to inspect and act on the ROSCAs of a chain running the generic
+//! runtime template.
+//!
+//! Read commands go through the `RoscaApi` runtime API and the pallet's storage. Write commands
+//! sign with an account given by secret URI or found in a keystore directory. Every command
+//! prints text by default and JSON with `--json`.
+
+mod format;
+
+use std::{fs, path::PathBuf, str::FromStr};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use rosca_client::{
+    account_of, AccountId, Keypair, PaymentAssets, RoscaClient, RoscaEvent, RoscaId, RoscaStatus,
+};
+use serde::Serialize;
+use serde_json::json;
+use subxt_signer::SecretUri;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Debug, Parser)]
+#[command(name = "rosca", version, about = "Inspect and act on ROSCAs from the command line.")]
+struct Cli {
+    /// WebSocket URL of the node.
+    #[arg(long, global = true, default_value = "ws://127.0.0.1:9944")]
+    url: String,
+
+    /// Print JSON instead of text.
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// List ROSCAs.
+    List {
+        /// Only list ROSCAs with this status.
+        #[arg(long, value_enum)]
+        status: Option<Status>,
+    },
+
+    /// Show the full state of a ROSCA and its upcoming payment cutoffs.
+    Show { rosca_id: RoscaId },
+
+    /// Show the ROSCAs an account has joined.
+    Participations {
+        /// SS58 address or hex public key.
+        account: AccountId,
+    },
+
+    /// Create a ROSCA. The signer is its creator and takes part in it.
+    Create {
+        #[command(flatten)]
+        signer: Signer,
+
+        /// Name of the ROSCA.
+        #[arg(long)]
+        name: String,
+
+        /// Invite an account. Repeat for each one.
+        #[arg(long = "invite", required = true)]
+        invited: Vec<AccountId>,
+
+        /// Contribution per round, in the asset's smallest unit (USDT and USDC have 6 decimals).
+        #[arg(long)]
+        contribution: u32,
+
+        /// Asset contributions are paid in.
+        #[arg(long, value_enum, default_value_t = Asset::Usdt)]
+        asset: Asset,
+
+        /// Time between payment cutoffs, e.g. `30d`, `12h` or `90000ms`.
+        #[arg(long, value_parser = format::parse_duration)]
+        frequency: u64,
+
+        /// How long the ROSCA has to start, from now.
+        #[arg(long, value_parser = format::parse_duration)]
+        start_within: u64,
+
+        /// Participants needed to start. Defaults to everyone invited.
+        #[arg(long)]
+        min_participants: Option<u32>,
+
+        /// Draw the payout order at random when the ROSCA starts.
+        #[arg(long)]
+        random_order: bool,
+
+        /// The creator's payout position, counting from 0.
+        #[arg(long)]
+        position: Option<u32>,
+    },
+
+    /// Join a ROSCA the signer was invited to.
+    Join {
+        #[command(flatten)]
+        signer: Signer,
+
+        rosca_id: RoscaId,
+
+        /// Payout position to ask for, counting from 0.
+        #[arg(long)]
+        position: Option<u32>,
+    },
+
+    /// Pay the signer's contribution to the current round.
+    Contribute {
+        #[command(flatten)]
+        signer: Signer,
+
+        rosca_id: RoscaId,
+    },
+
+    /// Claim back the signer's security deposit after the final payment cutoff.
+    Claim {
+        #[command(flatten)]
+        signer: Signer,
+
+        rosca_id: RoscaId,
+
+        /// Claim the undrawn guarantee as the ROSCA's guarantor instead.
+        #[arg(long)]
+        guarantee: bool,
+    },
+}
+
+/// The account that signs a write command.
+#[derive(Debug, Args)]
+struct Signer {
+    /// Secret URI of the signer: a seed phrase, a hex seed or a dev URI like `//Alice`.
+    #[arg(long, required_unless_present = "keystore")]
+    suri: Option<String>,
+
+    /// Keystore directory holding the signer's key, such as a node's `keystore` directory.
+    #[arg(long, conflicts_with = "suri", requires = "account")]
+    keystore: Option<PathBuf>,
+
+    /// Address of the signer's key in the keystore.
+    #[arg(long)]
+    account: Option<AccountId>,
+}
+
+impl Signer {
+    fn keypair(&self) -> Result<Keypair> {
+        let suri = match (&self.suri, &self.keystore, &self.account) {
+            (Some(suri), _, _) => suri.clone(),
+            (None, Some(keystore), Some(account)) => read_keystore(keystore, account)?,
+            _ => return Err("give either --suri or --keystore and --account".into()),
+        };
+        let keypair = Keypair::from_uri(&SecretUri::from_str(&suri)?)?;
+        if let Some(account) = &self.account {
+            if &account_of(&keypair) != account {
+                return Err(format!("the key found is not {account}'s").into());
+            }
+        }
+        Ok(keypair)
+    }
+}
+
+/// The secret URI of `account` in a keystore directory. Keystore files are named after the
+/// hex key type and public key, and hold the secret URI as a JSON string.
+fn read_keystore(keystore: &PathBuf, account: &AccountId) -> Result<String> {
+    let public: &[u8] = account.as_ref();
+    let suffix: String = public.iter().map(|byte| format!("{byte:02x}")).collect();
+    for entry in fs::read_dir(keystore)? {
+        let path = entry?.path();
+        let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        if name.len() == 8 + suffix.len() && name.ends_with(&suffix) {
+            return Ok(serde_json::from_str(&fs::read_to_string(&path)?)?);
+        }
+    }
+    Err(format!("no key for {account} in {}", keystore.display()).into())
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Status {
+    Pending,
+    Active,
+    Completed,
+}
+
+impl From<Status> for RoscaStatus {
+    fn from(status: Status) -> Self {
+        match status {
+            Status::Pending => RoscaStatus::Pending,
+            Status::Active => RoscaStatus::Active,
+            Status::Completed => RoscaStatus::Completed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum Asset {
+    Usdt,
+    Usdc,
+}
+
+impl From<Asset> for PaymentAssets {
+    fn from(asset: Asset) -> Self {
+        match asset {
+            Asset::Usdt => PaymentAssets::USDT,
+            Asset::Usdc => PaymentAssets::USDC,
+        }
+    }
+}
+
+/// A participant's standing in the ROSCA shown by `show`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ParticipantState {
+    account: AccountId,
+    position: u32,
+    security_deposit: u32,
+    defaults: u32,
+    contributed_this_round: bool,
+    autopay: bool,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    if let Err(err) = run(cli).await {
+        eprintln!("Error: {err}");
+        std::process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    let client = RoscaClient::connect(&cli.url).await?;
+    let json = cli.json;
+
+    match cli.command {
+        Command::List { status } => {
+            let roscas = client.roscas(status.map(Into::into)).await?;
+            if json {
+                let roscas: Vec<_> = roscas.iter().map(format::rosca_json).collect();
+                print_json(&roscas)?;
+            } else {
+                format::print_rosca_list(&roscas);
+            }
+        }
+        Command::Show { rosca_id } => {
+            let rosca = client.rosca(rosca_id).await?.ok_or(format!("no ROSCA {rosca_id}"))?;
+            let schedule = client.schedule(rosca_id).await?;
+            let now = client.timestamp().await?;
+            let storage = client.storage();
+            let mut participants = Vec::new();
+            for (account, position) in storage.participants(rosca_id).await? {
+                let security_deposit = storage.security_deposit(rosca_id, &account).await?;
+                let contributed = storage.is_current_contributor(rosca_id, &account).await?;
+                participants.push(ParticipantState {
+                    position,
+                    security_deposit: security_deposit.unwrap_or(0),
+                    defaults: storage.default_count(rosca_id, &account).await?,
+                    contributed_this_round: contributed,
+                    autopay: storage.is_autopay(rosca_id, &account).await?,
+                    account,
+                });
+            }
+            participants.sort_by_key(|participant| participant.position);
+            if json {
+                print_json(&json!({
+                    "now": now,
+                    "rosca": format::rosca_json(&rosca),
+                    "schedule": schedule,
+                    "participants": participants,
+                }))?;
+            } else {
+                format::print_rosca(&rosca, &schedule, &participants, now);
+            }
+        }
+        Command::Participations { account } => {
+            let participations = client.participations(account).await?;
+            if json {
+                print_json(&participations)?;
+            } else {
+                format::print_participations(&participations);
+            }
+        }
+        Command::Create {
+            signer,
+            name,
+            invited,
+            contribution,
+            asset,
+            frequency,
+            start_within,
+            min_participants,
+            random_order,
+            position,
+        } => {
+            let minimum_participant_threshold =
+                min_participants.unwrap_or(invited.len() as u32 + 1);
+            let start_by = client.timestamp().await? + start_within;
+            let events = client
+                .create_rosca(
+                    &signer.keypair()?,
+                    random_order,
+                    invited,
+                    minimum_participant_threshold,
+                    contribution,
+                    asset.into(),
+                    frequency,
+                    start_by,
+                    position,
+                    name.into_bytes(),
+                )
+                .await?;
+            print_events(&events, json)?;
+        }
+        Command::Join { signer, rosca_id, position } => {
+            let events = client.join_rosca(&signer.keypair()?, rosca_id, position).await?;
+            print_events(&events, json)?;
+        }
+        Command::Contribute { signer, rosca_id } => {
+            let events = client.contribute_to_rosca(&signer.keypair()?, rosca_id).await?;
+            print_events(&events, json)?;
+        }
+        Command::Claim { signer, rosca_id, guarantee } => {
+            let keypair = signer.keypair()?;
+            let events = if guarantee {
+                client.claim_guarantee(&keypair, rosca_id).await?
+            } else {
+                let rosca = client.rosca(rosca_id).await?.ok_or(format!("no ROSCA {rosca_id}"))?;
+                client.claim_security_deposit(&keypair, rosca_id, rosca.payment_asset).await?
+            };
+            print_events(&events, json)?;
+        }
+    }
+    Ok(())
+}
+
+fn print_json(value: &impl Serialize) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Prints the events a submitted call emitted, which is how its outcome is reported.
+fn print_events(events: &[RoscaEvent], json: bool) -> Result<()> {
+    if json {
+        print_json(&json!({ "events": events }))
+    } else {
+        events.iter().for_each(|event| println!("{event:?}"));
+        Ok(())
+    }
+}
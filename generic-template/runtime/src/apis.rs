@@ -2,6 +2,7 @@ use frame_support::{
     genesis_builder_helper::{build_config, create_default_config},
     weights::Weight,
 };
+use rosca_primitives::{ParticipationSummary, RoscaId, RoscaStatus, RoscaSummary, RoundSummary};
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
@@ -13,14 +14,27 @@ use sp_runtime::{
 use sp_std::prelude::Vec;
 use sp_version::RuntimeVersion;
 
-use crate::{constants::SLOT_DURATION, types::ConsensusHook};
+use crate::{constants::SLOT_DURATION, genesis_config_presets, types::ConsensusHook};
 use crate::{
     constants::VERSION,
-    types::{AccountId, Balance, Block, Executive, Nonce},
-    InherentDataExt, ParachainSystem, Runtime, RuntimeCall, RuntimeGenesisConfig, SessionKeys,
-    System, TransactionPayment,
+    types::{AccountId, Balance, Block, Executive, Moment, Nonce},
+    InherentDataExt, ParachainSystem, Rosca, Runtime, RuntimeCall, RuntimeGenesisConfig,
+    SessionKeys, System, TransactionPayment,
 };
 
+sp_api::decl_runtime_apis! {
+    /// Named genesis config patches. `sp_genesis_builder::GenesisBuilder` has no calls for
+    /// presets in this release, so the runtime serves them here and the node builds its chain
+    /// specs from what it returns.
+    pub trait GenesisPresets {
+        /// Names of the presets.
+        fn preset_names() -> Vec<Vec<u8>>;
+
+        /// The JSON genesis config patch of the preset `id`, if there is one.
+        fn get_preset(id: Vec<u8>) -> Option<Vec<u8>>;
+    }
+}
+
 impl_runtime_apis! {
     impl sp_consensus_aura::AuraApi<Block, AuraId> for Runtime {
         fn slot_duration() -> sp_consensus_aura::SlotDuration {
@@ -159,6 +173,24 @@ impl_runtime_apis! {
         }
     }
 
+    impl rosca_primitives::RoscaApi<Block, AccountId, Moment> for Runtime {
+        fn rosca(rosca_id: RoscaId) -> Option<RoscaSummary<AccountId, Moment>> {
+            Rosca::rosca_summary(rosca_id)
+        }
+
+        fn roscas(status: Option<RoscaStatus>) -> Vec<RoscaSummary<AccountId, Moment>> {
+            Rosca::rosca_summaries(status)
+        }
+
+        fn schedule(rosca_id: RoscaId) -> Vec<RoundSummary<AccountId, Moment>> {
+            Rosca::round_summaries(rosca_id)
+        }
+
+        fn participations(account: AccountId) -> Vec<ParticipationSummary> {
+            Rosca::participations_of(&account)
+        }
+    }
+
     impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
         fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
             ParachainSystem::collect_collation_info(header)
@@ -317,4 +349,22 @@ impl_runtime_apis! {
             build_config::<RuntimeGenesisConfig>(config)
         }
     }
+
+    impl self::GenesisPresets<Block> for Runtime {
+        fn preset_names() -> Vec<Vec<u8>> {
+            genesis_config_presets::preset_names()
+                .into_iter()
+                .map(|name| name.as_bytes().to_vec())
+                .collect()
+        }
+
+        fn get_preset(id: Vec<u8>) -> Option<Vec<u8>> {
+            let id = core::str::from_utf8(&id).ok()?;
+            genesis_config_presets::get_preset(id).map(|patch| {
+                serde_json::to_string(&patch)
+                    .expect("serialization to json is expected to work; qed")
+                    .into_bytes()
+            })
+        }
+    }
 }
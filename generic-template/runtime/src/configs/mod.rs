@@ -20,13 +20,16 @@ use frame_system::{
 };
 pub use governance::origins::pallet_custom_origins;
 use governance::{origins::Treasurer, TreasurySpender};
-use parachains_common::message_queue::{NarrowOriginToSibling, ParaIdToSibling};
+use parachains_common::{
+    impls::AssetsToBlockAuthor,
+    message_queue::{NarrowOriginToSibling, ParaIdToSibling},
+};
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use polkadot_runtime_common::{BlockHashCount, SlowAdjustingFeeUpdate};
 use scale_info::TypeInfo;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_runtime::{
-    traits::{AccountIdLookup, BlakeTwo256, IdentityLookup},
+    traits::{AccountIdLookup, BlakeTwo256, ConvertInto, IdentityLookup},
     Perbill, Permill, RuntimeDebug,
 };
 use sp_version::RuntimeVersion;
@@ -49,7 +52,7 @@ use crate::{
     },
     types::{
         AccountId, AssetKind, Balance, Beneficiary, Block, BlockNumber,
-        CollatorSelectionUpdateOrigin, ConsensusHook, Hash, Nonce,
+        CollatorSelectionUpdateOrigin, ConsensusHook, Hash, Moment, Nonce,
         PriceForSiblingParachainDelivery, TreasuryPaymaster,
     },
     weights::{self, BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight},
@@ -201,8 +204,7 @@ impl pallet_timestamp::Config for Runtime {
     type MinimumPeriod = ConstU64<0>;
     #[cfg(not(feature = "experimental"))]
     type MinimumPeriod = ConstU64<{ SLOT_DURATION / 2 }>;
-    /// A timestamp: milliseconds since the unix epoch.
-    type Moment = u64;
+    type Moment = Moment;
     type OnTimestampSet = Aura;
     /// Rerun benchmarks if you are making changes to runtime configuration.
     type WeightInfo = weights::pallet_timestamp::WeightInfo<Runtime>;
@@ -248,6 +250,10 @@ pub enum ProxyType {
     CancelProxy,
     /// Allows to operate with collators list (invulnerables, candidates, etc.)
     Collator,
+    /// Allows all ROSCA calls
+    Rosca,
+    /// Allows paying contributions and security deposits to ROSCAs
+    RoscaContributeOnly,
 }
 
 impl InstanceFilter<RuntimeCall> for ProxyType {
@@ -263,6 +269,12 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
             ProxyType::Collator => {
                 matches!(c, RuntimeCall::CollatorSelection { .. } | RuntimeCall::Multisig { .. })
             }
+            ProxyType::Rosca => matches!(c, RuntimeCall::Rosca { .. }),
+            ProxyType::RoscaContributeOnly => matches!(
+                c,
+                RuntimeCall::Rosca(pallet_rosca::Call::contribute_to_rosca { .. })
+                    | RuntimeCall::Rosca(pallet_rosca::Call::add_to_security_deposit { .. })
+            ),
         }
     }
 }
@@ -362,6 +374,18 @@ impl pallet_transaction_payment::Config for Runtime {
     type WeightToFee = WeightToFee;
 }
 
+/// Lets signers pay transaction fees in a sufficient asset such as the ROSCA stablecoins,
+/// so members don't need to hold the native token. The native fee is converted at the
+/// ratio of the asset's minimum balance to the existential deposit and goes to the block author.
+impl pallet_asset_tx_payment::Config for Runtime {
+    type Fungibles = Assets;
+    type OnChargeAssetTransaction = pallet_asset_tx_payment::FungiblesAdapter<
+        pallet_assets::BalanceToAssetBalance<Balances, Runtime, ConvertInto>,
+        AssetsToBlockAuthor<Runtime, ()>,
+    >;
+    type RuntimeEvent = RuntimeEvent;
+}
+
 impl pallet_sudo::Config for Runtime {
     type RuntimeCall = RuntimeCall;
     type RuntimeEvent = RuntimeEvent;
@@ -595,6 +619,9 @@ impl pallet_treasury::Config for Runtime {
 
 parameter_types! {
 	pub const RoscaPalletId: PalletId = PalletId(*b"py/rosca");
+	pub const MaxOrganiserFee: Permill = Permill::from_percent(5);
+	pub const MaxFee: Permill = Permill::from_percent(8);
+	pub const AutopayInterval: BlockNumber = HOURS;
 }
 
 impl pallet_rosca::Config for Runtime {
@@ -604,4 +631,15 @@ impl pallet_rosca::Config for Runtime {
 	type MaxInvitedParticipants = ConstU32<149>;
 	type PalletId = RoscaPalletId;
 	type StringLimit = ConstU32<50>;
+	type AdminOrigin = EitherOfDiverse<EnsureRoot<AccountId>, Treasurer>;
+	type FeeDestination = TreasuryAccount;
+	type MaxOrganiserFee = MaxOrganiserFee;
+	type MaxFee = MaxFee;
+	type NativeCurrency = Balances;
+	type MaxSponsoredCallsPerRound = ConstU32<3>;
+	type PayoutSender = xcm_config::XcmPayoutSender;
+	type RuntimeCall = RuntimeCall;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	type AutopayInterval = AutopayInterval;
 }
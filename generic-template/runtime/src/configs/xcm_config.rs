@@ -17,14 +17,15 @@ use xcm_builder::{
     SignedToAccountId32, SovereignSignedViaLocation, TakeWeightCredit, TrailingSetTopicAsId,
     UsingComponents, WithComputedOrigin, WithUniqueTopic, FixedRateOfFungible,
     MatchedConvertedConcreteId, AllowKnownQueryResponses, AllowSubscriptionsFrom,
-    MatchXcm,
+    MatchXcm, HashedDescription, DescribeFamily, DescribeAllTerminal,
 };
 use xcm_executor::{
     traits::{JustTry, ShouldExecute, Properties},
     XcmExecutor,
 };
-use sp_runtime::traits::MaybeEquivalence;
-use sp_std::vec::Vec;
+use sp_runtime::{traits::MaybeEquivalence, DispatchError, DispatchResult};
+use sp_std::{boxed::Box, vec::Vec};
+use xcm::{VersionedAssets, VersionedLocation};
 
 use crate::{
     configs::{
@@ -73,6 +74,9 @@ pub type LocationToAccountId = (
     SiblingParachainConvertsVia<Sibling, AccountId>,
     // Straight up local `AccountId32` origins just alias directly to `AccountId`.
     AccountId32Aliases<RelayNetwork, AccountId>,
+    // Accounts on other chains, e.g. Asset Hub users, get a local account derived from their
+    // location. This is the account that joins and contributes to ROSCAs on their behalf.
+    HashedDescription<AccountId, DescribeFamily<DescribeAllTerminal>>,
 );
 
 /// Means for transacting assets on this chain.
@@ -233,6 +237,15 @@ pub type Barrier = TrailingSetTopicAsId<
 
 pub type Reserves = (NativeAsset, AssetHubAssets);
 
+/// Calls that may be dispatched with `Transact`. Remote participants use it to join and
+/// contribute to ROSCAs from another chain with a single message.
+pub struct RoscaCalls;
+impl Contains<RuntimeCall> for RoscaCalls {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(call, RuntimeCall::Rosca(_))
+	}
+}
+
 pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
     type Aliasers = Nothing;
@@ -258,7 +271,7 @@ impl xcm_executor::Config for XcmConfig {
     type PalletInstancesInfo = AllPalletsWithSystem;
     type ResponseHandler = PolkadotXcm;
     type RuntimeCall = RuntimeCall;
-    type SafeCallFilter = Nothing;
+    type SafeCallFilter = RoscaCalls;
     type SubscriptionService = PolkadotXcm;
     type Trader = (
         UsingComponents<WeightToFee, RelayLocation, AccountId, Balances, ToAuthor<Runtime>>,
@@ -273,6 +286,37 @@ impl xcm_executor::Config for XcmConfig {
     type XcmSender = XcmRouter;
 }
 
+/// Delivers ROSCA payouts to a participant's account on another chain. The destination's last
+/// junction is the beneficiary on the chain identified by the rest of it, e.g.
+/// `(1, [Parachain(1000), AccountId32 { .. }])` for an Asset Hub account.
+pub struct XcmPayoutSender;
+impl pallet_rosca::PayoutSender<AccountId> for XcmPayoutSender {
+    fn send_payout(
+        from: &AccountId,
+        asset: u32,
+        amount: Balance,
+        destination: VersionedLocation,
+    ) -> DispatchResult {
+        let destination = Location::try_from(destination)
+            .map_err(|_| DispatchError::Other("Unsupported XCM version"))?;
+        let (chain, beneficiary) = destination.split_last_interior();
+        let beneficiary =
+            beneficiary.ok_or(DispatchError::Other("Payout destination has no beneficiary"))?;
+        let asset_location = SupportedAssets::convert_back(&asset)
+            .ok_or(DispatchError::Other("Asset can't be sent cross-chain"))?;
+        let assets: xcm::latest::Assets = Asset::from((asset_location, amount)).into();
+
+        PolkadotXcm::transfer_assets(
+            RuntimeOrigin::signed(from.clone()),
+            Box::new(chain.into()),
+            Box::new(Location::new(0, [beneficiary]).into()),
+            Box::new(VersionedAssets::from(assets)),
+            0,
+            WeightLimit::Unlimited,
+        )
+    }
+}
+
 /// No local origins on this chain are allowed to dispatch XCM sends/executions.
 pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, RelayNetwork>;
 
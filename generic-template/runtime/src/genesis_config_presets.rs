@@ -0,0 +1,198 @@
+//! Named genesis presets, as patches over the default genesis config.
+//!
+//! - `dev` endows the well-known development accounts, makes Alice and Bob the collators and Alice
+//!   sudo, and creates the USDT and USDC payment assets with a balance for every account.
+//! - `demo-rosca` adds a pending and an active ROSCA between the development accounts.
+//!
+//! The runtime serves these through the `GenesisPresets` API in [`crate::apis`], so they are
+//! built in Wasm too and name the development accounts by their public keys.
+//!
+//! ROSCAs count their start-by time and payment cutoffs from `rosca.genesisTimestamp`, which
+//! `demo-rosca` can't know. Whoever builds a chain spec from it must set it to around the time the
+//! chain starts; genesis is rejected while it is still zero.
+
+use hex_literal::hex;
+use pallet_rosca::{GenesisRosca, PaymentAssets};
+use sp_core::sr25519;
+use sp_runtime::{traits::IdentifyAccount, MultiSigner};
+use sp_std::{vec, vec::Vec};
+
+use crate::{constants::currency::EXISTENTIAL_DEPOSIT, AccountId, AuraId, SessionKeys};
+
+/// The `dev` preset.
+pub const DEV_PRESET: &str = "dev";
+/// The `demo-rosca` preset.
+pub const DEMO_ROSCA_PRESET: &str = "demo-rosca";
+
+/// The default XCM version to set in genesis config.
+const SAFE_XCM_VERSION: u32 = xcm::prelude::XCM_VERSION;
+/// Native balance of every endowed account.
+const ENDOWMENT: u128 = 1 << 60;
+/// Balance of every endowed account in each payment asset, 1,000,000 at 6 decimals.
+const ASSET_ENDOWMENT: u128 = 1_000_000_000_000;
+/// Para id of the development chains.
+const PARA_ID: u32 = 1000;
+/// One day in milliseconds.
+const DAY: u64 = 24 * 60 * 60 * 1000;
+/// Public keys of the development accounts `//<name>` and `//<name>//stash`.
+const DEVELOPMENT_KEYS: [(&str, [u8; 32], [u8; 32]); 6] = [
+    (
+        "Alice",
+        hex!("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"),
+        hex!("be5ddb1579b72e84524fc29e78609e3caf42e85aa118ebfe0b0ad404b5bdd25f"),
+    ),
+    (
+        "Bob",
+        hex!("8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"),
+        hex!("fe65717dad0447d715f660a0a58411de509b42e6efb8375f562f58a554d5860e"),
+    ),
+    (
+        "Charlie",
+        hex!("90b5ab205c6974c9ea841be688864633dc9ca8a357843eeacf2314649965fe22"),
+        hex!("1e07379407fecc4b89eb7dbd287c2c781cfb1907a96947a3eb18e4f8e7198625"),
+    ),
+    (
+        "Dave",
+        hex!("306721211d5404bd9da88e0204360a1a9ab8b87c66c1bc2fcdd37f3c2222cc20"),
+        hex!("e860f1b1c7227f7c22602f53f15af80747814dffd839719731ee3bba6edc126c"),
+    ),
+    (
+        "Eve",
+        hex!("e659a7a1628cdd93febc04a4e0646ea20e9f5f0ce097d9a05290d4a9e054df4e"),
+        hex!("8ac59e11963af19174d0b94d5d78041c233f55d2e19324665bafdfb62925af2d"),
+    ),
+    (
+        "Ferdie",
+        hex!("1cbd2d43530a44705ad088af313e18f80b53ef16b36177cd4b77b846f2a5f07c"),
+        hex!("101191192fc877c24d725b337120fa3edc63d227bbc92705db1e2cb65f56981a"),
+    ),
+];
+
+/// Names of the presets.
+pub fn preset_names() -> Vec<&'static str> {
+    vec![DEV_PRESET, DEMO_ROSCA_PRESET]
+}
+
+/// The genesis config patch of a preset, if there's one named `id`.
+pub fn get_preset(id: &str) -> Option<serde_json::Value> {
+    match id {
+        DEV_PRESET => Some(dev()),
+        DEMO_ROSCA_PRESET => Some(demo_rosca()),
+        _ => None,
+    }
+}
+
+/// Account of a development key, e.g. `Alice`.
+pub fn account(name: &str) -> AccountId {
+    MultiSigner::from(public(name)).into_account()
+}
+
+fn public(name: &str) -> sr25519::Public {
+    let (_, key, _) = DEVELOPMENT_KEYS
+        .iter()
+        .find(|(known, ..)| *known == name)
+        .expect("only development keys are named; qed");
+    sr25519::Public::from_raw(*key)
+}
+
+/// Accounts endowed by the development presets, each development key followed by its stash.
+pub fn development_accounts() -> Vec<AccountId> {
+    DEVELOPMENT_KEYS
+        .iter()
+        .flat_map(|(_, key, stash)| [*key, *stash])
+        .map(|key| MultiSigner::from(sr25519::Public::from_raw(key)).into_account())
+        .collect()
+}
+
+/// Genesis config patch of a test network. `endowed_accounts` get native tokens and both
+/// payment assets, and `root` is sudo and owns the assets.
+pub fn testnet_genesis(
+    invulnerables: Vec<(AccountId, AuraId)>,
+    endowed_accounts: Vec<AccountId>,
+    root: AccountId,
+    id: u32,
+) -> serde_json::Value {
+    let payment_assets = PaymentAssets::ALL.map(|asset| asset.id());
+
+    serde_json::json!({
+        "balances": {
+            "balances": endowed_accounts.iter().cloned().map(|k| (k, ENDOWMENT)).collect::<Vec<_>>(),
+        },
+        "parachainInfo": {
+            "parachainId": id,
+        },
+        "collatorSelection": {
+            "invulnerables": invulnerables.iter().cloned().map(|(acc, _)| acc).collect::<Vec<_>>(),
+            "candidacyBond": EXISTENTIAL_DEPOSIT * 16,
+        },
+        "session": {
+            "keys": invulnerables
+                .into_iter()
+                .map(|(acc, aura)| {
+                    (
+                        acc.clone(),           // account id
+                        acc,                   // validator id
+                        SessionKeys { aura },  // session keys
+                    )
+                })
+                .collect::<Vec<_>>(),
+        },
+        "treasury": {},
+        "polkadotXcm": {
+            "safeXcmVersion": Some(SAFE_XCM_VERSION),
+        },
+        "sudo": { "key": Some(root.clone()) },
+        "assets": {
+            // (id, owner, is_sufficient, min_balance)
+            "assets": payment_assets.map(|id| (id, root.clone(), true, 70000)),
+            // (id, name, symbol, decimals)
+            "metadata": vec![
+                (PaymentAssets::USDT.id(), b"Tether USD".to_vec(), b"USDT".to_vec(), 6),
+                (PaymentAssets::USDC.id(), b"USD Coin".to_vec(), b"USDC".to_vec(), 6),
+            ],
+            // (id, account_id, amount)
+            "accounts": payment_assets
+                .iter()
+                .flat_map(|id| endowed_accounts.iter().map(|account| (*id, account.clone(), ASSET_ENDOWMENT)))
+                .collect::<Vec<_>>(),
+        },
+    })
+}
+
+fn dev() -> serde_json::Value {
+    testnet_genesis(
+        ["Alice", "Bob"].into_iter().map(|name| (account(name), public(name).into())).collect(),
+        development_accounts(),
+        account("Alice"),
+        PARA_ID,
+    )
+}
+
+fn demo_rosca() -> serde_json::Value {
+    let mut genesis = dev();
+    let rosca = |creator: &str, name: &[u8], invited: &[&str], joined: &[&str], start: bool| {
+        GenesisRosca {
+            creator: account(creator),
+            name: name.to_vec(),
+            random_order: false,
+            invited: invited.iter().map(|name| account(name)).collect(),
+            joined: joined.iter().map(|name| account(name)).collect(),
+            minimum_participant_threshold: 3,
+            // 100 USDT
+            contribution_amount: 100_000_000,
+            payment_asset: PaymentAssets::USDT,
+            contribution_frequency: DAY,
+            starts_within: 7 * DAY,
+            start,
+        }
+    };
+    genesis["rosca"] = serde_json::json!({
+        "roscas": [
+            // Alice's circle is waiting for Dave to join before it can start.
+            rosca("Alice", b"Alice's circle", &["Bob", "Charlie", "Dave"], &["Bob", "Charlie"], false),
+            // Eve's circle is under way, with Eve collecting the first payout.
+            rosca("Eve", b"Eve's circle", &["Ferdie", "Bob"], &["Ferdie", "Bob"], true),
+        ],
+    });
+    genesis
+}
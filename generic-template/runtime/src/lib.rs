@@ -9,6 +9,8 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 pub mod apis;
 pub mod configs;
 pub mod constants;
+pub mod genesis_config_presets;
+pub mod sponsorship;
 mod types;
 mod weights;
 
@@ -34,7 +36,8 @@ use crate::{
 pub use crate::{
     configs::RuntimeBlockWeights,
     types::{
-        AccountId, Balance, Block, BlockNumber, Executive, Nonce, Signature, UncheckedExtrinsic,
+        AccountId, Balance, Block, BlockNumber, Executive, Moment, Nonce, Signature,
+        UncheckedExtrinsic,
     },
 };
 
@@ -128,6 +131,7 @@ construct_runtime!(
         TransactionPayment: pallet_transaction_payment = 11,
         Assets: pallet_assets = 12,
         Treasury: pallet_treasury::{Pallet, Call, Storage, Config<T>, Event<T>} = 13,
+        AssetTxPayment: pallet_asset_tx_payment = 14,
 
         // Governance
         Sudo: pallet_sudo = 15,
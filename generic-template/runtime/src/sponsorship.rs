@@ -0,0 +1,133 @@
+//! Transaction payment with ROSCA fee sponsorship.
+//!
+//! [`ChargeSponsoredTxPayment`] wraps [`ChargeAssetTxPayment`]. When a participant calls one of
+//! the sponsorable ROSCA extrinsics and the ROSCA's sponsorship can cover the fee, the fee is
+//! taken from the sponsorship instead of the signer. Every other transaction pays as usual, in
+//! the native token or in a sufficient asset.
+
+use pallet_asset_tx_payment::ChargeAssetTxPayment;
+use pallet_rosca::RoscaId;
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{
+    traits::{DispatchInfoOf, PostDispatchInfoOf, SignedExtension},
+    transaction_validity::{
+        InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+    },
+    DispatchResult, RuntimeDebug,
+};
+
+use crate::{AccountId, Balance, Rosca, Runtime, RuntimeCall, TransactionPayment};
+
+/// Pays transaction fees from a ROSCA's fee sponsorship where possible, falling back to
+/// [`ChargeAssetTxPayment`]. It encodes exactly like the wrapped extension, so wallets sign
+/// it the same way.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo, RuntimeDebug)]
+pub struct ChargeSponsoredTxPayment(pub ChargeAssetTxPayment<Runtime>);
+
+impl From<ChargeAssetTxPayment<Runtime>> for ChargeSponsoredTxPayment {
+    fn from(inner: ChargeAssetTxPayment<Runtime>) -> Self {
+        Self(inner)
+    }
+}
+
+/// What [`ChargeSponsoredTxPayment`] did before dispatch.
+pub enum Pre {
+    /// The estimated `fee` was reserved from the sponsorship of `rosca_id`.
+    Sponsored { rosca_id: RoscaId, who: AccountId, fee: Balance },
+    /// The signer was charged by the wrapped extension.
+    Charged(<ChargeAssetTxPayment<Runtime> as SignedExtension>::Pre),
+}
+
+impl ChargeSponsoredTxPayment {
+    /// The ROSCA sponsoring this call and the fee it would pay, if the call is sponsored.
+    /// Sponsored calls pay no tip.
+    fn sponsorship(
+        who: &AccountId,
+        call: &RuntimeCall,
+        info: &DispatchInfoOf<RuntimeCall>,
+        len: usize,
+    ) -> Option<(RoscaId, Balance)> {
+        let RuntimeCall::Rosca(call) = call else {
+            return None;
+        };
+        let rosca_id = Rosca::sponsored_rosca(call)?;
+        let fee = TransactionPayment::compute_fee(len as u32, info, 0);
+        Rosca::can_sponsor(rosca_id, who, fee).then_some((rosca_id, fee))
+    }
+}
+
+impl SignedExtension for ChargeSponsoredTxPayment {
+    type AccountId = AccountId;
+    type AdditionalSigned = ();
+    type Call = RuntimeCall;
+    type Pre = Pre;
+
+    // Kept the same as the wrapped extension so clients recognise it.
+    const IDENTIFIER: &'static str = "ChargeAssetTxPayment";
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+        self.0.additional_signed()
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> TransactionValidity {
+        if Self::sponsorship(who, call, info, len).is_some() {
+            return Ok(ValidTransaction::default());
+        }
+        self.0.validate(who, call, info, len)
+    }
+
+    fn pre_dispatch(
+        self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> Result<Self::Pre, TransactionValidityError> {
+        if let Some((rosca_id, fee)) = Self::sponsorship(who, call, info, len) {
+            Rosca::charge_sponsorship(rosca_id, who, fee)
+                .map_err(|_| InvalidTransaction::Payment)?;
+            return Ok(Pre::Sponsored { rosca_id, who: who.clone(), fee });
+        }
+        Ok(Pre::Charged(self.0.pre_dispatch(who, call, info, len)?))
+    }
+
+    fn post_dispatch(
+        pre: Option<Self::Pre>,
+        info: &DispatchInfoOf<Self::Call>,
+        post_info: &PostDispatchInfoOf<Self::Call>,
+        len: usize,
+        result: &DispatchResult,
+    ) -> Result<(), TransactionValidityError> {
+        match pre {
+            Some(Pre::Sponsored { rosca_id, who, fee }) => {
+                let actual_fee =
+                    TransactionPayment::compute_actual_fee(len as u32, info, post_info, 0);
+                // The call has run by now, so failing here would make the whole block invalid.
+                if let Err(error) = Rosca::settle_sponsorship(rosca_id, &who, fee, actual_fee) {
+                    log::error!(
+                        target: "runtime::sponsorship",
+                        "Failed to settle the sponsored fee of Rosca {rosca_id}: {error:?}",
+                    );
+                }
+                Ok(())
+            }
+            Some(Pre::Charged(pre)) => ChargeAssetTxPayment::<Runtime>::post_dispatch(
+                Some(pre),
+                info,
+                post_info,
+                len,
+                result,
+            ),
+            None => ChargeAssetTxPayment::<Runtime>::post_dispatch(
+                None, info, post_info, len, result,
+            ),
+        }
+    }
+}
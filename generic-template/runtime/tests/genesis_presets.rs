@@ -0,0 +1,34 @@
+// Genesis presets checks
+use generic_runtime_template::{
+    genesis_config_presets::{account, development_accounts, get_preset, preset_names},
+    AccountId,
+};
+use sp_core::{sr25519, Pair};
+use sp_runtime::{traits::IdentifyAccount, MultiSigner};
+
+fn derived(seed: &str) -> AccountId {
+    let public = sr25519::Pair::from_string(&format!("//{seed}"), None).unwrap().public();
+    MultiSigner::from(public).into_account()
+}
+
+#[test]
+fn development_keys_match_their_seeds() {
+    let names = ["Alice", "Bob", "Charlie", "Dave", "Eve", "Ferdie"];
+    for name in names {
+        assert_eq!(account(name), derived(name));
+    }
+
+    let expected: Vec<_> = names
+        .into_iter()
+        .flat_map(|name| [derived(name), derived(&format!("{name}//stash"))])
+        .collect();
+    assert_eq!(development_accounts(), expected);
+}
+
+#[test]
+fn every_preset_has_a_patch() {
+    for name in preset_names() {
+        assert!(get_preset(name).is_some());
+    }
+    assert_eq!(get_preset("unknown"), None);
+}
@@ -0,0 +1,39 @@
+// Proxies that may only act within ROSCAs.
+use frame_support::traits::InstanceFilter;
+use generic_runtime_template::{configs::ProxyType, AccountId, RuntimeCall};
+
+fn contribute() -> RuntimeCall {
+    RuntimeCall::Rosca(pallet_rosca::Call::contribute_to_rosca { rosca_id: 0 })
+}
+
+fn top_up_deposit() -> RuntimeCall {
+    RuntimeCall::Rosca(pallet_rosca::Call::add_to_security_deposit { rosca_id: 0, amount: 10 })
+}
+
+fn leave() -> RuntimeCall {
+    RuntimeCall::Rosca(pallet_rosca::Call::leave_rosca { rosca_id: 0 })
+}
+
+fn transfer() -> RuntimeCall {
+    RuntimeCall::Assets(pallet_assets::Call::transfer {
+        id: 1984.into(),
+        target: AccountId::new([1u8; 32]).into(),
+        amount: 1,
+    })
+}
+
+#[test]
+fn rosca_proxy_allows_only_rosca_calls() {
+    assert!(ProxyType::Rosca.filter(&contribute()));
+    assert!(ProxyType::Rosca.filter(&top_up_deposit()));
+    assert!(ProxyType::Rosca.filter(&leave()));
+    assert!(!ProxyType::Rosca.filter(&transfer()));
+}
+
+#[test]
+fn rosca_contribute_only_proxy_allows_only_payments() {
+    assert!(ProxyType::RoscaContributeOnly.filter(&contribute()));
+    assert!(ProxyType::RoscaContributeOnly.filter(&top_up_deposit()));
+    assert!(!ProxyType::RoscaContributeOnly.filter(&leave()));
+    assert!(!ProxyType::RoscaContributeOnly.filter(&transfer()));
+}
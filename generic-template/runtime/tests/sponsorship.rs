@@ -0,0 +1,172 @@
+// Transaction fees paid from a ROSCA's fee sponsorship.
+use frame_support::dispatch::{GetDispatchInfo, PostDispatchInfo};
+use generic_runtime_template::{
+    configs::TreasuryAccount,
+    constants::currency::{DOLLARS, EXISTENTIAL_DEPOSIT},
+    sponsorship::{ChargeSponsoredTxPayment, Pre},
+    AccountId, Balance, Balances, BuildStorage, Rosca, Runtime, RuntimeCall, RuntimeEvent,
+    RuntimeOrigin, System, TransactionPayment,
+};
+use pallet_asset_tx_payment::ChargeAssetTxPayment;
+use pallet_rosca::PaymentAssets;
+use sp_runtime::traits::SignedExtension;
+
+const USDT: u32 = 1984;
+const LEN: usize = 100;
+const SPONSORSHIP: Balance = 10 * DOLLARS;
+
+const ALICE: AccountId = AccountId::new([1u8; 32]);
+const BOB: AccountId = AccountId::new([2u8; 32]);
+const CHARLIE: AccountId = AccountId::new([3u8; 32]);
+const DAVE: AccountId = AccountId::new([4u8; 32]);
+
+/// Alice has created a ROSCA inviting Bob and Charlie, and funded its sponsorship.
+/// Bob holds no native tokens, so any fee they pay themselves fails.
+fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+    pallet_balances::GenesisConfig::<Runtime> {
+        balances: vec![
+            (ALICE, 100 * DOLLARS),
+            (CHARLIE, 100 * DOLLARS),
+            (DAVE, 100 * DOLLARS),
+            (TreasuryAccount::get(), DOLLARS),
+        ],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    pallet_assets::GenesisConfig::<Runtime> {
+        assets: vec![(USDT, ALICE, true, 1)],
+        metadata: vec![],
+        accounts: vec![
+            (USDT, ALICE, 1_000_000),
+            (USDT, BOB, 1_000_000),
+            (USDT, CHARLIE, 1_000_000),
+        ],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        pallet_timestamp::Pallet::<Runtime>::set_timestamp(1);
+
+        assert!(Rosca::create_rosca(
+            RuntimeOrigin::signed(ALICE),
+            false,
+            vec![BOB, CHARLIE].try_into().unwrap(),
+            3,
+            100,
+            PaymentAssets::USDT,
+            10,
+            20,
+            Some(0),
+            b"sponsored".to_vec().try_into().unwrap(),
+        )
+        .is_ok());
+        assert!(Rosca::fund_sponsorship(RuntimeOrigin::signed(ALICE), 0, SPONSORSHIP).is_ok());
+    });
+    ext
+}
+
+fn extension() -> ChargeSponsoredTxPayment {
+    ChargeAssetTxPayment::<Runtime>::from(0, None).into()
+}
+
+fn join() -> RuntimeCall {
+    RuntimeCall::Rosca(pallet_rosca::Call::join_rosca { rosca_id: 0, position: None })
+}
+
+#[test]
+fn sponsored_call_is_paid_from_the_sponsorship() {
+    new_test_ext().execute_with(|| {
+        let call = join();
+        let info = call.get_dispatch_info();
+        let budget = Rosca::sponsorship_balance(0);
+        let treasury = Balances::free_balance(TreasuryAccount::get());
+
+        assert!(extension().validate(&BOB, &call, &info, LEN).is_ok());
+        let pre = extension().pre_dispatch(&BOB, &call, &info, LEN).unwrap();
+        let Pre::Sponsored { rosca_id: 0, fee, .. } = pre else {
+            panic!("Bob's join should be sponsored");
+        };
+        assert!(fee > 0);
+        assert_eq!(Rosca::sponsorship_balance(0), budget - fee);
+
+        assert!(ChargeSponsoredTxPayment::post_dispatch(
+            Some(pre),
+            &info,
+            &PostDispatchInfo::default(),
+            LEN,
+            &Ok(())
+        )
+        .is_ok());
+        System::assert_last_event(RuntimeEvent::Rosca(pallet_rosca::Event::FeeSponsored {
+            rosca_id: 0,
+            participant: BOB,
+            fee,
+        }));
+        assert_eq!(Rosca::sponsorship_balance(0), budget - fee);
+        assert_eq!(Balances::free_balance(TreasuryAccount::get()), treasury + fee);
+        assert_eq!(Balances::free_balance(BOB), 0);
+    });
+}
+
+#[test]
+fn unused_part_of_the_estimate_returns_to_the_sponsorship() {
+    new_test_ext().execute_with(|| {
+        let call = join();
+        let info = call.get_dispatch_info();
+        let budget = Rosca::sponsorship_balance(0);
+
+        let pre = extension().pre_dispatch(&BOB, &call, &info, LEN).unwrap();
+        let post_info =
+            PostDispatchInfo { actual_weight: Some(info.weight / 2), ..Default::default() };
+        assert!(
+            ChargeSponsoredTxPayment::post_dispatch(Some(pre), &info, &post_info, LEN, &Ok(()))
+                .is_ok()
+        );
+
+        let charged = budget - Rosca::sponsorship_balance(0);
+        let estimated = TransactionPayment::compute_fee(LEN as u32, &info, 0);
+        assert!(charged < estimated);
+    });
+}
+
+#[test]
+fn other_callers_and_calls_pay_as_usual() {
+    new_test_ext().execute_with(|| {
+        // Dave was never invited.
+        let call = join();
+        let info = call.get_dispatch_info();
+        let pre = extension().pre_dispatch(&DAVE, &call, &info, LEN).unwrap();
+        assert!(matches!(pre, Pre::Charged(_)));
+        assert!(Balances::free_balance(DAVE) < 100 * DOLLARS);
+
+        // Starting the ROSCA isn't a sponsorable call.
+        let call = RuntimeCall::Rosca(pallet_rosca::Call::start_rosca { rosca_id: 0 });
+        let info = call.get_dispatch_info();
+        let pre = extension().pre_dispatch(&ALICE, &call, &info, LEN).unwrap();
+        assert!(matches!(pre, Pre::Charged(_)));
+        assert_eq!(Rosca::sponsorship_balance(0), SPONSORSHIP - EXISTENTIAL_DEPOSIT);
+    });
+}
+
+#[test]
+fn calls_over_the_round_limit_are_not_sponsored() {
+    new_test_ext().execute_with(|| {
+        let call = join();
+        let info = call.get_dispatch_info();
+
+        for _ in 0..3 {
+            let pre = extension().pre_dispatch(&BOB, &call, &info, LEN).unwrap();
+            assert!(matches!(pre, Pre::Sponsored { .. }));
+        }
+
+        // Bob has no native tokens to pay for the fourth call.
+        assert!(extension().validate(&BOB, &call, &info, LEN).is_err());
+        assert!(extension().pre_dispatch(&BOB, &call, &info, LEN).is_err());
+    });
+}
@@ -0,0 +1,285 @@
+// Cross-chain ROSCA participation on an emulated network of Rococo, Asset Hub and this chain.
+// Asset Hub users get their USDT here with a reserve transfer, then join and contribute with a
+// single message that pays for itself in that USDT and dispatches the ROSCA call.
+use emulated_integration_tests_common::{
+    accounts::{init_balances, CHARLIE},
+    build_genesis_storage, collators,
+    xcm_emulator::{decl_test_networks, decl_test_parachains, Chain, Parachain, TestExt},
+};
+use frame_support::{traits::OnInitialize, weights::Weight};
+use generic_runtime_template::{
+    configs::xcm_config::{LocationToAccountId, UsdtLocation},
+    AccountId, SessionKeys,
+};
+use pallet_rosca::PaymentAssets;
+use parity_scale_codec::Encode;
+use sp_runtime::Storage;
+use xcm::prelude::*;
+use xcm_executor::traits::ConvertLocation;
+
+use asset_hub_rococo_emulated_chain::{AssetHubRococo, AssetHubRococoParaPallet};
+use rococo_emulated_chain::Rococo;
+
+const ASSET_HUB_ID: u32 = 1000;
+const PARA_ID: u32 = 2000;
+const USDT: u32 = 1984;
+const INITIAL_USDT: u128 = 10_000_000;
+const CONTRIBUTION: u32 = 1_000_000;
+// What Charlie moves over from Asset Hub: three contributions and the cost of the messages.
+const TRANSFERRED_USDT: u128 = 4 * CONTRIBUTION as u128;
+// Enough to buy execution for any of the messages below.
+const XCM_FEE: u128 = 100_000;
+
+const LOCAL_ALICE: AccountId = AccountId::new([1u8; 32]);
+const LOCAL_BOB: AccountId = AccountId::new([2u8; 32]);
+
+fn genesis() -> Storage {
+    let genesis_config = generic_runtime_template::RuntimeGenesisConfig {
+        balances: generic_runtime_template::BalancesConfig {
+            balances: init_balances().iter().cloned().map(|k| (k, 1 << 60)).collect(),
+        },
+        parachain_info: generic_runtime_template::ParachainInfoConfig {
+            parachain_id: PARA_ID.into(),
+            ..Default::default()
+        },
+        collator_selection: generic_runtime_template::CollatorSelectionConfig {
+            invulnerables: collators::invulnerables().iter().cloned().map(|(acc, _)| acc).collect(),
+            ..Default::default()
+        },
+        session: generic_runtime_template::SessionConfig {
+            keys: collators::invulnerables()
+                .into_iter()
+                .map(|(acc, aura)| (acc.clone(), acc, SessionKeys { aura }))
+                .collect(),
+        },
+        polkadot_xcm: generic_runtime_template::PolkadotXcmConfig {
+            safe_xcm_version: Some(XCM_VERSION),
+            ..Default::default()
+        },
+        // Only this chain's own accounts start with USDT.
+        assets: generic_runtime_template::AssetsConfig {
+            assets: vec![(USDT, LOCAL_ALICE, true, 1)],
+            metadata: vec![],
+            accounts: vec![(USDT, LOCAL_ALICE, INITIAL_USDT), (USDT, LOCAL_BOB, INITIAL_USDT)],
+        },
+        ..Default::default()
+    };
+
+    build_genesis_storage(
+        &genesis_config,
+        generic_runtime_template::WASM_BINARY.expect("WASM binary was not built, please build it!"),
+    )
+}
+
+decl_test_parachains! {
+    pub struct RoscaChain {
+        genesis = genesis(),
+        on_init = {
+            generic_runtime_template::AuraExt::on_initialize(1);
+        },
+        runtime = generic_runtime_template,
+        core = {
+            XcmpMessageHandler: generic_runtime_template::XcmpQueue,
+            LocationToAccountId: generic_runtime_template::configs::xcm_config::LocationToAccountId,
+            ParachainInfo: generic_runtime_template::ParachainInfo,
+            MessageOrigin: cumulus_primitives_core::AggregateMessageOrigin,
+        },
+        pallets = {
+            PolkadotXcm: generic_runtime_template::PolkadotXcm,
+            Assets: generic_runtime_template::Assets,
+            Rosca: generic_runtime_template::Rosca,
+        }
+    },
+}
+
+decl_test_networks! {
+    pub struct RococoMockNet {
+        relay_chain = Rococo,
+        parachains = vec![
+            AssetHubRococo,
+            RoscaChain,
+        ],
+        bridge = ()
+    },
+}
+
+type AssetHubRococoPara = AssetHubRococo<RococoMockNet>;
+type RoscaChainPara = RoscaChain<RococoMockNet>;
+
+type AssetHubOrigin = <AssetHubRococoPara as Chain>::RuntimeOrigin;
+type AssetHubAssets = <AssetHubRococoPara as AssetHubRococoParaPallet>::Assets;
+type AssetHubXcm = <AssetHubRococoPara as AssetHubRococoParaPallet>::PolkadotXcm;
+type RoscaOrigin = <RoscaChainPara as Chain>::RuntimeOrigin;
+type RoscaAssets = <RoscaChainPara as RoscaChainParaPallet>::Assets;
+type Rosca = <RoscaChainPara as RoscaChainParaPallet>::Rosca;
+
+/// This chain as seen from Asset Hub.
+fn rosca_chain() -> Location {
+    Location::new(1, [Parachain(PARA_ID)])
+}
+
+/// The account on this chain of a user on Asset Hub.
+fn local_account_of(who: &AccountId) -> AccountId {
+    let location = Location::new(
+        1,
+        [Parachain(ASSET_HUB_ID), AccountId32 { network: None, id: who.clone().into() }],
+    );
+    LocationToAccountId::convert_location(&location).unwrap()
+}
+
+/// Creates USDT on Asset Hub and gives `who` their starting balance.
+fn create_usdt_on_asset_hub(who: &AccountId) {
+    AssetHubRococoPara::execute_with(|| {
+        assert!(AssetHubAssets::force_create(
+            AssetHubOrigin::root(),
+            USDT.into(),
+            who.clone().into(),
+            true,
+            1
+        )
+        .is_ok());
+        assert!(AssetHubAssets::mint(
+            AssetHubOrigin::signed(who.clone()),
+            USDT.into(),
+            who.clone().into(),
+            INITIAL_USDT
+        )
+        .is_ok());
+    });
+}
+
+/// `who` moves `amount` USDT from Asset Hub to their account on this chain.
+fn reserve_transfer_usdt(who: &AccountId, amount: u128) {
+    AssetHubRococoPara::execute_with(|| {
+        let usdt: Asset =
+            (Location::new(0, [PalletInstance(50), GeneralIndex(USDT.into())]), amount).into();
+        let beneficiary =
+            Location::new(0, [AccountId32 { network: None, id: local_account_of(who).into() }]);
+        assert!(AssetHubXcm::limited_reserve_transfer_assets(
+            AssetHubOrigin::signed(who.clone()),
+            Box::new(rosca_chain().into()),
+            Box::new(beneficiary.into()),
+            Box::new(usdt.into()),
+            0,
+            Unlimited,
+        )
+        .is_ok());
+    });
+}
+
+/// `who` sends one message from Asset Hub that pays for its execution with their USDT on this
+/// chain, dispatches `call` as their local account and returns the surplus to it.
+fn send_rosca_call(who: &AccountId, call: generic_runtime_template::RuntimeCall) {
+    let fees: Asset = (UsdtLocation::get(), XCM_FEE).into();
+    let message = Xcm::<()>(vec![
+        WithdrawAsset(fees.clone().into()),
+        BuyExecution { fees, weight_limit: Unlimited },
+        Transact {
+            origin_kind: OriginKind::SovereignAccount,
+            require_weight_at_most: Weight::from_parts(1_000_000_000, 64 * 1024),
+            call: call.encode().into(),
+        },
+        RefundSurplus,
+        DepositAsset {
+            assets: AllCounted(1).into(),
+            beneficiary: Location::new(
+                0,
+                [AccountId32 { network: None, id: local_account_of(who).into() }],
+            ),
+        },
+    ]);
+    AssetHubRococoPara::execute_with(|| {
+        assert!(AssetHubXcm::send(
+            AssetHubOrigin::signed(who.clone()),
+            Box::new(rosca_chain().into()),
+            Box::new(VersionedXcm::from(message)),
+        )
+        .is_ok());
+    });
+}
+
+#[test]
+fn asset_hub_user_completes_rosca_with_usdt_sent_from_asset_hub() {
+    let charlie_on_asset_hub = AssetHubRococoPara::account_id_of(CHARLIE);
+    let charlie = local_account_of(&charlie_on_asset_hub);
+    create_usdt_on_asset_hub(&charlie_on_asset_hub);
+
+    // Charlie's USDT arrives as a reserve asset, backed by this chain's account on Asset Hub.
+    reserve_transfer_usdt(&charlie_on_asset_hub, TRANSFERRED_USDT);
+    AssetHubRococoPara::execute_with(|| {
+        let sovereign = AssetHubRococoPara::sovereign_account_id_of(rosca_chain());
+        assert_eq!(
+            AssetHubAssets::balance(USDT, &charlie_on_asset_hub),
+            INITIAL_USDT - TRANSFERRED_USDT
+        );
+        assert_eq!(AssetHubAssets::balance(USDT, sovereign), TRANSFERRED_USDT);
+    });
+    RoscaChainPara::execute_with(|| {
+        let received = RoscaAssets::balance(USDT, &charlie);
+        assert!(received > TRANSFERRED_USDT - XCM_FEE);
+
+        pallet_timestamp::Pallet::<generic_runtime_template::Runtime>::set_timestamp(1);
+        assert!(Rosca::create_rosca(
+            RoscaOrigin::signed(LOCAL_ALICE),
+            false,
+            vec![LOCAL_BOB, charlie.clone()].try_into().unwrap(),
+            3,
+            CONTRIBUTION,
+            PaymentAssets::USDT,
+            10,
+            20,
+            Some(0),
+            b"cross-chain".to_vec().try_into().unwrap(),
+        )
+        .is_ok());
+        assert!(Rosca::join_rosca(RoscaOrigin::signed(LOCAL_BOB), 0, None).is_ok());
+    });
+
+    // Charlie joins from Asset Hub.
+    send_rosca_call(
+        &charlie_on_asset_hub,
+        generic_runtime_template::RuntimeCall::Rosca(pallet_rosca::Call::join_rosca {
+            rosca_id: 0,
+            position: None,
+        }),
+    );
+    let contribute = || {
+        send_rosca_call(
+            &charlie_on_asset_hub,
+            generic_runtime_template::RuntimeCall::Rosca(pallet_rosca::Call::contribute_to_rosca {
+                rosca_id: 0,
+            }),
+        )
+    };
+
+    RoscaChainPara::execute_with(|| {
+        assert!(Rosca::participants(0, &charlie).is_some());
+        assert!(Rosca::start_rosca(RoscaOrigin::signed(LOCAL_ALICE), 0).is_ok());
+
+        // Round one pays Alice.
+        assert!(Rosca::contribute_to_rosca(RoscaOrigin::signed(LOCAL_BOB), 0).is_ok());
+    });
+    contribute();
+    RoscaChainPara::execute_with(|| {
+        assert_eq!(Rosca::eligible_claimant(0), Some(LOCAL_BOB));
+
+        // Round two pays Bob.
+        assert!(Rosca::contribute_to_rosca(RoscaOrigin::signed(LOCAL_ALICE), 0).is_ok());
+    });
+    contribute();
+    RoscaChainPara::execute_with(|| {
+        assert_eq!(Rosca::eligible_claimant(0), Some(charlie.clone()));
+
+        // Round three pays Charlie and completes the ROSCA.
+        assert!(Rosca::contribute_to_rosca(RoscaOrigin::signed(LOCAL_ALICE), 0).is_ok());
+        assert!(Rosca::contribute_to_rosca(RoscaOrigin::signed(LOCAL_BOB), 0).is_ok());
+        assert!(Rosca::completed_roscas(0).is_some());
+
+        assert_eq!(RoscaAssets::balance(USDT, LOCAL_ALICE), INITIAL_USDT);
+        assert_eq!(RoscaAssets::balance(USDT, LOCAL_BOB), INITIAL_USDT);
+        // Charlie only spent what the messages cost to execute.
+        let charlie_balance = RoscaAssets::balance(USDT, &charlie);
+        assert!(charlie_balance < TRANSFERRED_USDT);
+        assert!(charlie_balance > TRANSFERRED_USDT - 4 * XCM_FEE);
+    });
+}
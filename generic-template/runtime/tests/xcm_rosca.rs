@@ -0,0 +1,163 @@
+// Cross-chain ROSCA participation. Messages are executed as they would arrive from Asset Hub,
+// through the runtime's own XCM configuration.
+use frame_support::weights::Weight;
+use generic_runtime_template::{
+    configs::xcm_config::{LocationToAccountId, UsdtLocation, XcmConfig},
+    AccountId, Assets, BuildStorage, Rosca, Runtime, RuntimeCall, RuntimeOrigin, System,
+};
+use pallet_rosca::PaymentAssets;
+use parity_scale_codec::Encode;
+use xcm::latest::{prelude::*, ExecuteXcm};
+use xcm_executor::{traits::ConvertLocation, XcmExecutor};
+
+const USDT: u32 = 1984;
+const INITIAL_USDT: u128 = 10_000_000;
+const CONTRIBUTION: u32 = 1_000_000;
+// Enough to buy execution for any of the messages below.
+const XCM_FEE: u128 = 100_000;
+
+const ALICE: AccountId = AccountId::new([1u8; 32]);
+const BOB: AccountId = AccountId::new([2u8; 32]);
+const CHARLIE_ON_ASSET_HUB: [u8; 32] = [3u8; 32];
+
+fn asset_hub() -> Location {
+    Location::new(1, [Parachain(1000)])
+}
+
+/// The local account of a user on Asset Hub.
+fn remote_account(id: [u8; 32]) -> AccountId {
+    let location = Location::new(1, [Parachain(1000), AccountId32 { network: None, id }]);
+    LocationToAccountId::convert_location(&location).unwrap()
+}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+
+    pallet_assets::GenesisConfig::<Runtime> {
+        assets: vec![(USDT, ALICE, true, 1)],
+        metadata: vec![],
+        accounts: vec![
+            (USDT, ALICE, INITIAL_USDT),
+            (USDT, BOB, INITIAL_USDT),
+            (USDT, remote_account(CHARLIE_ON_ASSET_HUB), INITIAL_USDT),
+        ],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        pallet_timestamp::Pallet::<Runtime>::set_timestamp(1);
+    });
+    ext
+}
+
+/// A message an Asset Hub user sends with `pallet_xcm::send` to dispatch `call` on this chain.
+/// Execution is paid in USDT from their local account and the surplus is returned to it.
+fn remote_call(id: [u8; 32], call: RuntimeCall) -> Xcm<RuntimeCall> {
+    let fees: Asset = (UsdtLocation::get(), XCM_FEE).into();
+    Xcm(vec![
+        DescendOrigin(AccountId32 { network: None, id }.into()),
+        WithdrawAsset(fees.clone().into()),
+        BuyExecution { fees, weight_limit: Unlimited },
+        Transact {
+            origin_kind: OriginKind::SovereignAccount,
+            require_weight_at_most: Weight::from_parts(1_000_000_000, 64 * 1024),
+            call: call.encode().into(),
+        },
+        RefundSurplus,
+        DepositAsset {
+            assets: AllCounted(1).into(),
+            beneficiary: Location::new(
+                0,
+                [AccountId32 { network: None, id: remote_account(id).into() }],
+            ),
+        },
+    ])
+}
+
+fn execute_from_asset_hub(message: Xcm<RuntimeCall>) -> Outcome {
+    let mut hash = message.using_encoded(sp_io::hashing::blake2_256);
+    XcmExecutor::<XcmConfig>::prepare_and_execute(
+        asset_hub(),
+        message,
+        &mut hash,
+        Weight::from_parts(100_000_000_000, 1024 * 1024),
+        Weight::zero(),
+    )
+}
+
+fn remote_contribution(rosca_id: u32) -> Xcm<RuntimeCall> {
+    remote_call(
+        CHARLIE_ON_ASSET_HUB,
+        RuntimeCall::Rosca(pallet_rosca::Call::contribute_to_rosca { rosca_id }),
+    )
+}
+
+#[test]
+fn remote_participant_completes_rosca_over_xcm() {
+    new_test_ext().execute_with(|| {
+        let charlie = remote_account(CHARLIE_ON_ASSET_HUB);
+
+        assert!(Rosca::create_rosca(
+            RuntimeOrigin::signed(ALICE),
+            false,
+            vec![BOB, charlie.clone()].try_into().unwrap(),
+            3,
+            CONTRIBUTION,
+            PaymentAssets::USDT,
+            10,
+            20,
+            Some(0),
+            b"cross-chain".to_vec().try_into().unwrap(),
+        )
+        .is_ok());
+        assert!(Rosca::join_rosca(RuntimeOrigin::signed(BOB), 0, None).is_ok());
+
+        // Charlie joins from Asset Hub.
+        let join = remote_call(
+            CHARLIE_ON_ASSET_HUB,
+            RuntimeCall::Rosca(pallet_rosca::Call::join_rosca { rosca_id: 0, position: None }),
+        );
+        assert!(execute_from_asset_hub(join).ensure_complete().is_ok());
+        assert!(Rosca::participants(0, &charlie).is_some());
+
+        assert!(Rosca::start_rosca(RuntimeOrigin::signed(ALICE), 0).is_ok());
+
+        // Round one pays Alice.
+        assert!(Rosca::contribute_to_rosca(RuntimeOrigin::signed(BOB), 0).is_ok());
+        assert!(execute_from_asset_hub(remote_contribution(0)).ensure_complete().is_ok());
+        assert_eq!(Rosca::eligible_claimant(0), Some(BOB));
+
+        // Round two pays Bob.
+        assert!(Rosca::contribute_to_rosca(RuntimeOrigin::signed(ALICE), 0).is_ok());
+        assert!(execute_from_asset_hub(remote_contribution(0)).ensure_complete().is_ok());
+        assert_eq!(Rosca::eligible_claimant(0), Some(charlie.clone()));
+
+        // Round three pays Charlie and completes the ROSCA.
+        assert!(Rosca::contribute_to_rosca(RuntimeOrigin::signed(ALICE), 0).is_ok());
+        assert!(Rosca::contribute_to_rosca(RuntimeOrigin::signed(BOB), 0).is_ok());
+        assert!(Rosca::completed_roscas(0).is_some());
+
+        assert_eq!(Assets::balance(USDT, ALICE), INITIAL_USDT);
+        assert_eq!(Assets::balance(USDT, BOB), INITIAL_USDT);
+        // Charlie only spent what the three messages cost to execute.
+        let charlie_balance = Assets::balance(USDT, &charlie);
+        assert!(charlie_balance < INITIAL_USDT);
+        assert!(charlie_balance > INITIAL_USDT - 3 * XCM_FEE);
+    });
+}
+
+#[test]
+fn transact_is_limited_to_rosca_calls() {
+    new_test_ext().execute_with(|| {
+        let transfer = RuntimeCall::Assets(pallet_assets::Call::transfer {
+            id: USDT.into(),
+            target: ALICE.into(),
+            amount: 1,
+        });
+        let outcome = execute_from_asset_hub(remote_call(CHARLIE_ON_ASSET_HUB, transfer));
+        assert!(outcome.ensure_complete().is_err());
+    });
+}
@@ -0,0 +1,13 @@
+#!/bin/bash
+
+# From the workspace directory, run :
+# ./scripts/client-tests.sh
+# to build the node and run the rosca-client integration tests against `--dev-seal` nodes.
+# Extra arguments go to the test binary, e.g. `./scripts/client-tests.sh runs_a_rosca_round`.
+# These tests are ignored by a plain `cargo test`; the `client-tests` workflow runs this script.
+
+set -euo pipefail
+
+cargo build --release -p generic-template-node
+ROSCA_NODE="${ROSCA_NODE:-$PWD/target/release/generic-template-node}" \
+  cargo test --release -p rosca-client --test dev_node -- --ignored --test-threads=1 "$@"
@@ -2,4 +2,4 @@
 rustup target add wasm32-unknown-unknown
 rustup component add rust-src
 cargo install ziggy cargo-afl honggfuzz grcov
-AFL_SKIP_CPUFREQ=true AFL_I_DONT_CARE_ABOUT_MISSING_CRASHES=true cargo ziggy fuzz -t 20 -j 5
\ No newline at end of file
+AFL_SKIP_CPUFREQ=true AFL_I_DONT_CARE_ABOUT_MISSING_CRASHES=true cargo ziggy fuzz -b "${FUZZ_TARGET:-template-fuzzer}" -t 20 -j 5
\ No newline at end of file
@@ -0,0 +1,216 @@
+//! Fuzzes ROSCA lifecycles. Inputs decode into a sequence of `pallet_rosca` calls, weighted
+//! towards create/join/deposit/start/contribute, and waits that move the chain and its clock
+//! forward. After every block the pallet's invariants are checked, and so is conservation of the
+//! stablecoins the ROSCAs are paid in.
+use std::time::{Duration, Instant};
+
+use frame_support::{
+    pallet_prelude::Decode,
+    traits::{IntegrityTest, TryState, TryStateSelect},
+};
+use generic_runtime_template::{
+    constants::SLOT_DURATION, AllPalletsWithSystem, Assets, AssetsConfig, Balance, BlockNumber,
+    Executive, Rosca, Runtime, RuntimeCall, RuntimeOrigin, Timestamp,
+};
+use pallet_rosca::PaymentAssets;
+use parachains_common::AccountId;
+use sp_runtime::{traits::Dispatchable, Storage};
+use substrate_runtime_fuzzer::MAX_TIME_FOR_BLOCK;
+use template_fuzzer::{genesis, start_block};
+
+pub type Externalities = sp_state_machine::BasicExternalities;
+
+const ACCOUNTS: u8 = 5;
+// Calls target the first few ROSCA ids so they meet the ones created earlier in the input.
+const ROSCA_IDS: u8 = 4;
+// Each account starts with this much of every payment asset.
+const INITIAL_ASSET_BALANCE: Balance = 1 << 40;
+// Contributions and deposits stay small enough for several rounds to be paid.
+const MAX_AMOUNT: u32 = 1_000_000_000;
+
+const PAYMENT_ASSETS: [PaymentAssets; 2] = [PaymentAssets::USDT, PaymentAssets::USDC];
+
+/// One step of a fuzzed session.
+enum Step {
+    /// Dispatch a ROSCA call signed by one of the endowed accounts.
+    Call { origin: AccountId, call: pallet_rosca::Call<Runtime> },
+    /// Move `lapse` blocks ahead, and the clock with them.
+    Wait { lapse: u32 },
+}
+
+/// Reads steps from the raw fuzzer input.
+struct Input<'a> {
+    data: &'a [u8],
+    accounts: &'a [AccountId],
+}
+
+impl Input<'_> {
+    fn byte(&mut self) -> Option<u8> {
+        let (first, rest) = self.data.split_first()?;
+        self.data = rest;
+        Some(*first)
+    }
+
+    fn amount(&mut self) -> Option<u32> {
+        let bytes = [self.byte()?, self.byte()?, self.byte()?, self.byte()?];
+        Some(u32::from_le_bytes(bytes) % MAX_AMOUNT + 1)
+    }
+
+    fn account(&mut self) -> Option<AccountId> {
+        Some(self.accounts[usize::from(self.byte()? % ACCOUNTS)].clone())
+    }
+
+    /// The next step, given the current time. `None` once the input runs out.
+    fn step(&mut self, now: u64) -> Option<Step> {
+        use pallet_rosca::Call;
+
+        let kind = self.byte()?;
+        let origin = self.account()?;
+        let rosca_id = u32::from(self.byte()? % ROSCA_IDS);
+
+        let call = match kind % 16 {
+            0 | 1 => {
+                let invited = self.byte()?;
+                let invited_pre_verified_participants: Vec<AccountId> = self
+                    .accounts
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, account)| invited & (1 << index) != 0 && **account != origin)
+                    .map(|(_, account)| account.clone())
+                    .collect();
+                let participants = invited_pre_verified_participants.len() as u32 + 1;
+                let options = self.byte()?;
+                Call::create_rosca {
+                    random_order: options & 1 != 0,
+                    invited_pre_verified_participants: invited_pre_verified_participants
+                        .try_into()
+                        .ok()?,
+                    minimum_participant_threshold: u32::from(self.byte()?) % participants + 1,
+                    contribution_amount: self.amount()?,
+                    payment_asset: PAYMENT_ASSETS[usize::from((options >> 1) & 1)].clone(),
+                    contribution_frequency: SLOT_DURATION * u64::from(self.byte()? % 8 + 1),
+                    start_by_timestamp: now + SLOT_DURATION * u64::from(self.byte()? % 32 + 1),
+                    position: Some(u32::from(self.byte()?) % participants),
+                    name: b"fuzz".to_vec().try_into().ok()?,
+                }
+            }
+            2 | 3 => Call::join_rosca { rosca_id, position: None },
+            4 => Call::leave_rosca { rosca_id },
+            5 => Call::add_to_security_deposit { rosca_id, amount: self.amount()? },
+            6 | 7 => Call::start_rosca { rosca_id },
+            8..=10 => Call::contribute_to_rosca { rosca_id },
+            11 => Call::contribute_for { rosca_id, participant: self.account()? },
+            12 => Call::manually_end_rosca { rosca_id },
+            13 => {
+                let asset = PAYMENT_ASSETS[usize::from(self.byte()? & 1)].clone();
+                Call::claim_security_deposit { rosca_id, asset }
+            }
+            14 => return Some(Step::Wait { lapse: u32::from(self.byte()? % 16) + 1 }),
+            // Any other ROSCA call. Payouts to other chains would take funds off this one, so
+            // they're left out of the conservation check's reach.
+            _ => match Call::<Runtime>::decode(&mut self.data).ok()? {
+                Call::set_payout_destination { .. } => return self.step(now),
+                call => call,
+            },
+        };
+
+        Some(Step::Call { origin, call })
+    }
+}
+
+/// Checks the ROSCA invariants and that no payment asset was created or destroyed.
+fn check_block(block: u32) {
+    println!("\ntesting invariants for block {block}");
+    Rosca::do_try_state().unwrap();
+
+    for asset in PAYMENT_ASSETS {
+        let id = asset.id();
+        let supply = Assets::total_supply(id);
+        assert_eq!(
+            supply,
+            INITIAL_ASSET_BALANCE * Balance::from(ACCOUNTS),
+            "{asset:?} was created or destroyed"
+        );
+        // Payment assets are sufficient, so every holder has a system account.
+        let held: Balance = frame_system::Account::<Runtime>::iter_keys()
+            .map(|who| Assets::balance(id, who))
+            .sum();
+        assert_eq!(held, supply, "{asset:?} balances don't add up to its supply");
+    }
+}
+
+fn main() {
+    let endowed_accounts: Vec<AccountId> = (0..ACCOUNTS).map(|i| [i; 32].into()).collect();
+
+    let genesis_storage: Storage = {
+        let owner = endowed_accounts[0].clone();
+        let assets = AssetsConfig {
+            assets: PAYMENT_ASSETS.iter().map(|asset| (asset.id(), owner.clone(), true, 1)).collect(),
+            metadata: vec![
+                (PaymentAssets::USDT.id(), b"Tether USD".to_vec(), b"USDT".to_vec(), 6),
+                (PaymentAssets::USDC.id(), b"USD Coin".to_vec(), b"USDC".to_vec(), 6),
+            ],
+            accounts: PAYMENT_ASSETS
+                .iter()
+                .flat_map(|asset| {
+                    endowed_accounts
+                        .iter()
+                        .map(|account| (asset.id(), account.clone(), INITIAL_ASSET_BALANCE))
+                })
+                .collect(),
+        };
+        genesis(&endowed_accounts, assets)
+    };
+
+    ziggy::fuzz!(|data: &[u8]| {
+        let mut input = Input { data, accounts: &endowed_accounts };
+
+        // `externalities` represents the state of our mock chain.
+        let mut externalities = Externalities::new(genesis_storage.clone());
+
+        let mut current_block: u32 = 1;
+        let mut elapsed: Duration = Duration::ZERO;
+
+        externalities.execute_with(|| start_block(current_block, 0));
+
+        while let Some(step) = input.step(externalities.execute_with(Timestamp::get)) {
+            // We get the current time for timing purposes.
+            let now = Instant::now();
+
+            match step {
+                Step::Wait { lapse } => {
+                    externalities.execute_with(|| {
+                        check_block(current_block);
+                        start_block(current_block, lapse);
+                    });
+                    current_block += lapse;
+                    elapsed = Duration::ZERO;
+                }
+                Step::Call { origin, call } => externalities.execute_with(|| {
+                    println!("\n    origin:     {origin:?}");
+                    println!("    call:       {call:?}");
+                    let _res = RuntimeCall::Rosca(call).dispatch(RuntimeOrigin::signed(origin));
+                    println!("    result:     {_res:?}");
+                }),
+            }
+
+            // Catching up on missed rounds must not make a block overrun.
+            elapsed += now.elapsed();
+            assert!(elapsed.as_secs() <= MAX_TIME_FOR_BLOCK, "block execution took too much time");
+        }
+
+        // We end the final block
+        externalities.execute_with(|| {
+            check_block(current_block);
+            Executive::finalize_block();
+            <AllPalletsWithSystem as TryState<BlockNumber>>::try_state(
+                current_block,
+                TryStateSelect::All,
+            )
+            .unwrap();
+
+            println!("running integrity tests");
+            <AllPalletsWithSystem as IntegrityTest>::integrity_test();
+        });
+    });
+}
@@ -0,0 +1,133 @@
+//! Chain setup shared by the fuzz targets.
+
+use cumulus_primitives_core::relay_chain::Slot;
+use frame_support::pallet_prelude::Encode;
+use generic_runtime_template::{
+    constants::SLOT_DURATION, AssetsConfig, Executive, RuntimeCall, SudoConfig, UncheckedExtrinsic,
+};
+use parachains_common::AccountId;
+use sp_consensus_aura::AURA_ENGINE_ID;
+use sp_runtime::{traits::Header, Digest, DigestItem, Storage};
+use substrate_runtime_fuzzer::INITIAL_TIMESTAMP;
+
+/// Genesis state of the fuzzed chain. `endowed_accounts` get a native balance of 1 << 60 and the
+/// first of them is sudo and the only collator.
+pub fn genesis(endowed_accounts: &[AccountId], assets: AssetsConfig) -> Storage {
+    use generic_runtime_template::{
+        BalancesConfig, CollatorSelectionConfig, RuntimeGenesisConfig, SessionConfig, SessionKeys,
+    };
+    use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+    use sp_runtime::{app_crypto::ByteArray, BuildStorage};
+
+    let initial_authorities: Vec<(AccountId, AuraId)> =
+        vec![([0; 32].into(), AuraId::from_slice(&[0; 32]).unwrap())];
+    let root: AccountId = [0; 32].into();
+
+    RuntimeGenesisConfig {
+        system: Default::default(),
+        balances: BalancesConfig {
+            // Configure endowed accounts with initial balance of 1 << 60.
+            balances: endowed_accounts.iter().cloned().map(|k| (k, 1 << 60)).collect(),
+        },
+        aura: Default::default(),
+        session: SessionConfig {
+            keys: initial_authorities
+                .iter()
+                .map(|x| (x.0.clone(), x.0.clone(), SessionKeys { aura: x.1.clone() }))
+                .collect::<Vec<_>>(),
+        },
+        collator_selection: CollatorSelectionConfig {
+            invulnerables: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
+            candidacy_bond: 1 << 57,
+            desired_candidates: 1,
+        },
+        aura_ext: Default::default(),
+        parachain_info: Default::default(),
+        parachain_system: Default::default(),
+        polkadot_xcm: Default::default(),
+        assets,
+        transaction_payment: Default::default(),
+        sudo: SudoConfig { key: Some(root) },
+        treasury: Default::default(),
+        rosca: Default::default(),
+    }
+    .build_storage()
+    .unwrap()
+}
+
+/// Finalizes the current block, if any, and initializes the one `lapse` blocks after `block`
+/// with its timestamp and parachain inherents.
+pub fn start_block(block: u32, lapse: u32) {
+    println!("\ninitializing block {}", block + lapse);
+
+    let next_block = block + lapse;
+    let current_timestamp = INITIAL_TIMESTAMP + u64::from(next_block) * SLOT_DURATION;
+    let pre_digest = match current_timestamp {
+        INITIAL_TIMESTAMP => Default::default(),
+        _ => Digest {
+            logs: vec![DigestItem::PreRuntime(
+                AURA_ENGINE_ID,
+                Slot::from(current_timestamp / SLOT_DURATION).encode(),
+            )],
+        },
+    };
+
+    let prev_header = match next_block {
+        1 => None,
+        _ => Some(Executive::finalize_block()),
+    };
+
+    let parent_header = &Header::new(
+        next_block + 1,
+        Default::default(),
+        Default::default(),
+        prev_header.clone().map(|x| x.hash()).unwrap_or_default(),
+        pre_digest,
+    );
+    Executive::initialize_block(parent_header);
+
+    // We apply the timestamp extrinsic for the current block.
+    Executive::apply_extrinsic(UncheckedExtrinsic::new_unsigned(RuntimeCall::Timestamp(
+        pallet_timestamp::Call::set { now: current_timestamp },
+    )))
+    .unwrap()
+    .unwrap();
+
+    let parachain_validation_data = {
+        use cumulus_primitives_core::{relay_chain::HeadData, PersistedValidationData};
+        use cumulus_primitives_parachain_inherent::ParachainInherentData;
+        use cumulus_test_relay_sproof_builder::RelayStateSproofBuilder;
+
+        let parent_head =
+            HeadData(prev_header.clone().unwrap_or(parent_header.clone()).encode());
+        let sproof_builder = RelayStateSproofBuilder {
+            para_id: 100.into(),
+            current_slot: Slot::from(2 * current_timestamp / SLOT_DURATION),
+            included_para_head: Some(parent_head.clone()),
+            ..Default::default()
+        };
+
+        let (relay_parent_storage_root, relay_chain_state) =
+            sproof_builder.into_state_root_and_proof();
+        let data = ParachainInherentData {
+            validation_data: PersistedValidationData {
+                parent_head,
+                relay_parent_number: next_block,
+                relay_parent_storage_root,
+                max_pov_size: 1000,
+            },
+            relay_chain_state,
+            downward_messages: Default::default(),
+            horizontal_messages: Default::default(),
+        };
+        cumulus_pallet_parachain_system::Call::set_validation_data { data }
+    };
+
+    Executive::apply_extrinsic(UncheckedExtrinsic::new_unsigned(
+        RuntimeCall::ParachainSystem(parachain_validation_data),
+    ))
+    .unwrap()
+    .unwrap();
+
+    // Calls that need to be called before each block starts (init_calls) go here
+}
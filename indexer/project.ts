@@ -141,6 +141,38 @@ const project: SubstrateProject = {
               method: "NewRoundStarted",
             },
           },
+          {
+            kind: SubstrateHandlerKind.Event,
+            handler: "handlePositionsSwapped",
+            filter: {
+              module: "rosca",
+              method: "PositionsSwapped",
+            },
+          },
+          {
+            kind: SubstrateHandlerKind.Event,
+            handler: "handleParticipantSubstituted",
+            filter: {
+              module: "rosca",
+              method: "ParticipantSubstituted",
+            },
+          },
+          {
+            kind: SubstrateHandlerKind.Event,
+            handler: "handleRoscaResumed",
+            filter: {
+              module: "rosca",
+              method: "RoscaResumed",
+            },
+          },
+          {
+            kind: SubstrateHandlerKind.Event,
+            handler: "handleParticipantEjected",
+            filter: {
+              module: "rosca",
+              method: "ParticipantEjected",
+            },
+          },
         ],
       },
     },
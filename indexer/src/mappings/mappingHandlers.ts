@@ -33,9 +33,9 @@ type RoscaStartedEvent = [
   number           // payment_cutoff
 ];
 
-type ContributionMadeEvent = [number, string, string, number];
+type ContributionMadeEvent = [number, string, string, number, string, number, number];
 
-type DepositDeductedEvent = [number, string, string, number, boolean];
+type DepositDeductedEvent = [number, string, string, number, boolean, number, number];
 
 type JoinedRoscaEvent = [number, string];
 
@@ -43,7 +43,7 @@ type LeftRoscaEvent = [number, string];
 
 type RoscaCompleteEvent = [number];
 
-type SecurityDepositContributionEvent = [number, string, number];
+type SecurityDepositContributionEvent = [number, string, number, string];
 
 type SecurityDepositClaimedEvent = [number, string, number];
 
@@ -51,6 +51,14 @@ type RoscaManuallyEndedEvent = [number];
 
 type NewRoundStartedEvent = [number, string, number];
 
+type PositionsSwappedEvent = [number, string, string, RoundInfo[]];
+
+type ParticipantSubstitutedEvent = [number, string, string, boolean, number, number, number];
+
+type RoscaResumedEvent = [number, number, RoundInfo[]];
+
+type ParticipantEjectedEvent = [number, string, number, number, string, boolean, number, RoundInfo[]];
+
 export async function handleRoscaCreated(event: SubstrateEvent): Promise<void> {
   const [rosca_id, contribution_amount, payment_asset, contribution_frequency, random_order, name, number_of_participants, minimum_participant_threshold, start_by_timestamp, eligible_participants, creator] =
     event.event.data.toJSON() as RoscaCreatedEvent;
@@ -72,7 +80,9 @@ export async function handleRoscaCreated(event: SubstrateEvent): Promise<void> {
     eligibleParticipants: eligible_participants,
     activeParticipants: [creator],
     totalSecurityDeposits: 0,
-    currentRoundNumber: 0
+    currentRoundNumber: 0,
+    totalPlatformFees: BigInt(0),
+    totalOrganiserFees: BigInt(0)
   });
 
   await roscaEntity.save();
@@ -159,10 +169,28 @@ export async function handleParticipantDefaulted(event: SubstrateEvent): Promise
   }
 }
 
+async function recordFees(rosca_id: number, platform_fee: number, organiser_fee: number): Promise<void> {
+  if (!platform_fee && !organiser_fee) {
+    return;
+  }
+
+  const roscaEntity = await Rosca.get(rosca_id.toString());
+  if (!roscaEntity) {
+    logger.warn(`Rosca not found for ${rosca_id}`);
+    return;
+  }
+
+  roscaEntity.totalPlatformFees += BigInt(platform_fee ?? 0);
+  roscaEntity.totalOrganiserFees += BigInt(organiser_fee ?? 0);
+  await roscaEntity.save();
+}
+
 export async function handleContributionMade(event: SubstrateEvent): Promise<void> {
-  const [rosca_id, contributor, recipient, amount] = event.event.data.toJSON() as ContributionMadeEvent;
+  const [rosca_id, contributor, recipient, amount, , platform_fee, organiser_fee] = event.event.data.toJSON() as ContributionMadeEvent;
   logger.info(`ContributionMadeEvent: rosca_id=${rosca_id}, contributor=${contributor}, amount=${amount}`);
 
+  await recordFees(rosca_id, platform_fee, organiser_fee);
+
   const rounds = await Round.getByFields([
     ["parentRoscaId", "=", rosca_id.toString()],
     ["recipient", "=", recipient]
@@ -178,9 +206,11 @@ export async function handleContributionMade(event: SubstrateEvent): Promise<voi
 }
 
 export async function handleDepositDeducted(event: SubstrateEvent): Promise<void> {
-  const [rosca_id, contributor, recipient, amount] = event.event.data.toJSON() as DepositDeductedEvent;
+  const [rosca_id, contributor, recipient, amount, , platform_fee, organiser_fee] = event.event.data.toJSON() as DepositDeductedEvent;
   logger.info(`DepositDeductedEvent: rosca_id=${rosca_id}, contributor=${contributor}, amount=${amount}`);
 
+  await recordFees(rosca_id, platform_fee, organiser_fee);
+
   const rounds = await Round.getByFields([
     ["parentRoscaId", "=", rosca_id.toString()],
     ["recipient", "=", recipient]
@@ -360,3 +390,148 @@ export async function handleNewRoundStarted(event: SubstrateEvent): Promise<void
   await rosca.save();
   logger.info(`Started new round ${rosca.currentRoundNumber} for Rosca ${rosca_id}`);
 }
+
+export async function handlePositionsSwapped(event: SubstrateEvent): Promise<void> {
+  const [rosca_id, proposer, counterparty, rounds] = event.event.data.toJSON() as PositionsSwappedEvent;
+  logger.info(`PositionsSwappedEvent: rosca_id=${rosca_id}, proposer=${proposer}, counterparty=${counterparty}`);
+
+  for (const round of rounds) {
+    const { roundNumber, paymentCutoff: cutoff, expectedContributors, recipient } = round;
+    const roundId = `${rosca_id}-${roundNumber}`;
+    const roundEntity = await Round.get(roundId);
+
+    if (!roundEntity) {
+      logger.warn(`Round not found for swap: ${roundId}`);
+      continue;
+    }
+
+    roundEntity.recipient = recipient;
+    roundEntity.expectedContributors = expectedContributors;
+    roundEntity.paymentCutoff = BigInt(cutoff);
+    await roundEntity.save();
+    logger.info(`Updated Round ${roundId} with recipient ${recipient}`);
+  }
+}
+
+export async function handleParticipantSubstituted(event: SubstrateEvent): Promise<void> {
+  const [rosca_id, departing, substitute, already_received, outstanding_contributions, inherited_debt, deposit_transferred] =
+    event.event.data.toJSON() as ParticipantSubstitutedEvent;
+  logger.info(`ParticipantSubstitutedEvent: rosca_id=${rosca_id}, departing=${departing}, substitute=${substitute}, inherited_debt=${inherited_debt}`);
+
+  const roscaEntity = await Rosca.get(rosca_id.toString());
+  if (!roscaEntity) {
+    logger.warn(`Rosca not found for ${rosca_id}`);
+    return;
+  }
+
+  let account = await Account.get(substitute);
+  if (!account) {
+    account = Account.create({ id: substitute });
+    await account.save();
+    logger.info(`Created Account ${substitute}`);
+  }
+
+  roscaEntity.activeParticipants = roscaEntity.activeParticipants.map(
+    (addr) => addr === departing ? substitute : addr
+  );
+  roscaEntity.eligibleParticipants = roscaEntity.eligibleParticipants.map(
+    (addr) => addr === departing ? substitute : addr
+  );
+  if (roscaEntity.currentRecipient === departing) {
+    roscaEntity.currentRecipient = substitute;
+  }
+  await roscaEntity.save();
+
+  const rounds = await Round.getByFields([
+    ["parentRoscaId", "=", rosca_id.toString()]
+  ]);
+
+  for (const roundEntity of rounds) {
+    if (roundEntity.roundNumber < roscaEntity.currentRoundNumber) {
+      continue;
+    }
+    if (roundEntity.recipient === departing) {
+      roundEntity.recipient = substitute;
+    }
+    roundEntity.expectedContributors = roundEntity.expectedContributors.map(
+      (addr) => addr === departing ? substitute : addr
+    );
+    await roundEntity.save();
+  }
+  logger.info(`Replaced ${departing} with ${substitute} in Rosca ${rosca_id}`);
+}
+
+export async function handleRoscaResumed(event: SubstrateEvent): Promise<void> {
+  const [rosca_id, paused_duration, rounds] = event.event.data.toJSON() as RoscaResumedEvent;
+  logger.info(`RoscaResumedEvent: rosca_id=${rosca_id}, paused_duration=${paused_duration}`);
+
+  const roscaEntity = await Rosca.get(rosca_id.toString());
+  if (!roscaEntity) {
+    logger.warn(`Rosca not found for ${rosca_id}`);
+    return;
+  }
+
+  for (const round of rounds) {
+    const { roundNumber, paymentCutoff: cutoff } = round;
+    const roundId = `${rosca_id}-${roundNumber}`;
+    const roundEntity = await Round.get(roundId);
+
+    if (!roundEntity) {
+      logger.warn(`Round not found for resume: ${roundId}`);
+      continue;
+    }
+
+    roundEntity.paymentCutoff = BigInt(cutoff);
+    await roundEntity.save();
+
+    if (roundNumber === roscaEntity.currentRoundNumber) {
+      roscaEntity.currentRoundPaymentCutoff = BigInt(cutoff);
+    }
+  }
+
+  await roscaEntity.save();
+  logger.info(`Shifted remaining rounds of Rosca ${rosca_id} by ${paused_duration}`);
+}
+
+export async function handleParticipantEjected(event: SubstrateEvent): Promise<void> {
+  const [rosca_id, participant, defaults, forfeited_deposit, forfeited_to, slot_dropped, final_payment_cutoff, rounds] =
+    event.event.data.toJSON() as ParticipantEjectedEvent;
+  logger.info(`ParticipantEjectedEvent: rosca_id=${rosca_id}, participant=${participant}, defaults=${defaults}, slot_dropped=${slot_dropped}`);
+
+  const roscaEntity = await Rosca.get(rosca_id.toString());
+  if (!roscaEntity) {
+    logger.warn(`Rosca not found for ${rosca_id}`);
+    return;
+  }
+
+  roscaEntity.activeParticipants = roscaEntity.activeParticipants.filter(
+    (addr) => addr !== participant
+  );
+  await roscaEntity.save();
+
+  let lastRoundNumber = 0;
+  for (const round of rounds) {
+    const { roundNumber, paymentCutoff: cutoff, expectedContributors, recipient } = round;
+    const roundId = `${rosca_id}-${roundNumber}`;
+    const roundEntity = await Round.get(roundId);
+    lastRoundNumber = Math.max(lastRoundNumber, roundNumber);
+
+    if (!roundEntity) {
+      logger.warn(`Round not found for ejection: ${roundId}`);
+      continue;
+    }
+
+    roundEntity.recipient = recipient;
+    roundEntity.expectedContributors = expectedContributors;
+    roundEntity.paymentCutoff = BigInt(cutoff);
+    await roundEntity.save();
+  }
+
+  if (slot_dropped) {
+    const droppedRoundId = `${rosca_id}-${lastRoundNumber + 1}`;
+    await store.remove('Round', droppedRoundId);
+    logger.info(`Removed dropped Round ${droppedRoundId}`);
+  }
+
+  logger.info(`Ejected ${participant} from Rosca ${rosca_id}, final cutoff now ${final_payment_cutoff}`);
+}